@@ -0,0 +1,158 @@
+//! A block-backed bitmap: one bit per allocatable unit (inode or data
+//! block), used by both the inode and data-block allocators.
+
+use super::block_cache::get_block_cache;
+use super::block_dev::{BlockDevice, BLOCK_SIZE};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+const BLOCK_BITS: usize = BLOCK_SIZE * 8;
+
+type BitmapBlock = [u64; BLOCK_SIZE / 8];
+
+pub struct Bitmap {
+    start_block_id: usize,
+    blocks: usize,
+}
+
+impl Bitmap {
+    pub fn new(start_block_id: usize, blocks: usize) -> Self {
+        Self {
+            start_block_id,
+            blocks,
+        }
+    }
+
+    /// Finds and claims the first clear bit, returning its absolute
+    /// position, or `None` if every block in the bitmap is full.
+    pub fn alloc(&self, block_device: &Arc<dyn BlockDevice>) -> Option<usize> {
+        for block_id in 0..self.blocks {
+            let pos = get_block_cache(
+                block_id + self.start_block_id,
+                Arc::clone(block_device),
+            )
+            .lock()
+            .modify(0, |bitmap_block: &mut BitmapBlock| {
+                bitmap_block
+                    .iter()
+                    .enumerate()
+                    .find(|(_, word)| **word != u64::MAX)
+                    .map(|(words_index, word)| (words_index, *word))
+                    .map(|(words_index, word)| {
+                        let inner_pos = word.trailing_ones() as usize;
+                        bitmap_block[words_index] |= 1u64 << inner_pos;
+                        words_index * 64 + inner_pos
+                    })
+            });
+            if let Some(pos) = pos {
+                return Some(block_id * BLOCK_BITS + pos);
+            }
+        }
+        None
+    }
+
+    pub fn dealloc(&self, block_device: &Arc<dyn BlockDevice>, bit: usize) {
+        let block_id = bit / BLOCK_BITS;
+        let inner_pos = bit % BLOCK_BITS;
+        get_block_cache(block_id + self.start_block_id, Arc::clone(block_device))
+            .lock()
+            .modify(0, |bitmap_block: &mut BitmapBlock| {
+                let words_index = inner_pos / 64;
+                let bit_index = inner_pos % 64;
+                assert!(bitmap_block[words_index] & (1u64 << bit_index) != 0);
+                bitmap_block[words_index] &= !(1u64 << bit_index);
+            });
+    }
+
+    pub fn max_items(&self) -> usize {
+        self.blocks * BLOCK_BITS
+    }
+
+    /// Whether `bit` is currently claimed, for callers (like [`fsck`](
+    /// super::fsck)) that need to cross-check the bitmap against
+    /// something else rather than allocate from it.
+    pub fn is_allocated(&self, block_device: &Arc<dyn BlockDevice>, bit: usize) -> bool {
+        let block_id = bit / BLOCK_BITS;
+        let inner_pos = bit % BLOCK_BITS;
+        get_block_cache(block_id + self.start_block_id, Arc::clone(block_device))
+            .lock()
+            .read(0, |bitmap_block: &BitmapBlock| {
+                let words_index = inner_pos / 64;
+                let bit_index = inner_pos % 64;
+                bitmap_block[words_index] & (1u64 << bit_index) != 0
+            })
+    }
+
+    /// Every bit currently claimed, in ascending order.
+    pub fn allocated_bits(&self, block_device: &Arc<dyn BlockDevice>) -> Vec<usize> {
+        let mut bits = Vec::new();
+        for block_id in 0..self.blocks {
+            get_block_cache(block_id + self.start_block_id, Arc::clone(block_device))
+                .lock()
+                .read(0, |bitmap_block: &BitmapBlock| {
+                    for (words_index, word) in bitmap_block.iter().enumerate() {
+                        for bit_index in 0..64 {
+                            if word & (1u64 << bit_index) != 0 {
+                                bits.push(block_id * BLOCK_BITS + words_index * 64 + bit_index);
+                            }
+                        }
+                    }
+                });
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::RamDisk;
+
+    /// Each test gets its own disjoint slice of the block-id space: see
+    /// [`crate::test_support::RamDisk`]'s doc comment on why two tests
+    /// can't safely share one, even via separate `RamDisk`s.
+    fn device(base: usize, num_blocks: usize) -> Arc<dyn BlockDevice> {
+        Arc::new(RamDisk::new(base, num_blocks))
+    }
+
+    #[test]
+    fn alloc_claims_the_first_clear_bit_and_dealloc_frees_it() {
+        const BASE: usize = 1_000_000;
+        let device = device(BASE, 2);
+        let bitmap = Bitmap::new(BASE, 2);
+
+        let first = bitmap.alloc(&device).unwrap();
+        let second = bitmap.alloc(&device).unwrap();
+        assert_ne!(first, second);
+        assert!(bitmap.is_allocated(&device, first));
+        assert!(bitmap.is_allocated(&device, second));
+
+        bitmap.dealloc(&device, first);
+        assert!(!bitmap.is_allocated(&device, first));
+        assert!(bitmap.is_allocated(&device, second));
+
+        // The freed bit is the first clear one again, so it's reused
+        // before anything past `second`.
+        assert_eq!(bitmap.alloc(&device).unwrap(), first);
+    }
+
+    #[test]
+    fn alloc_returns_none_once_every_bit_is_claimed() {
+        const BASE: usize = 2_000_000;
+        let device = device(BASE, 1);
+        let bitmap = Bitmap::new(BASE, 1);
+        for _ in 0..bitmap.max_items() {
+            assert!(bitmap.alloc(&device).is_some());
+        }
+        assert!(bitmap.alloc(&device).is_none());
+    }
+
+    #[test]
+    fn allocated_bits_lists_every_claimed_bit_in_order() {
+        const BASE: usize = 3_000_000;
+        let device = device(BASE, 1);
+        let bitmap = Bitmap::new(BASE, 1);
+        let claimed: Vec<usize> = (0..5).map(|_| bitmap.alloc(&device).unwrap()).collect();
+        assert_eq!(bitmap.allocated_bits(&device), claimed);
+    }
+}
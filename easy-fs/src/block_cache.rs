@@ -0,0 +1,151 @@
+//! A small in-memory cache over block reads/writes, so a hot inode or
+//! bitmap block isn't re-fetched from the block device on every access.
+//! Eviction is LRU: the entry least recently looked up is the first
+//! candidate, since recency of access is a much better predictor of
+//! reuse for inode/bitmap/directory traffic than insertion order.
+
+use super::block_dev::{BlockDevice, BLOCK_SIZE};
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use spin::Mutex;
+
+/// How many blocks the cache holds before it starts evicting, unless
+/// overridden by [`init_block_cache_capacity`].
+pub const DEFAULT_BLOCK_CACHE_SIZE: usize = 16;
+
+static CONFIGURED_CAPACITY: AtomicUsize = AtomicUsize::new(DEFAULT_BLOCK_CACHE_SIZE);
+
+/// Sets the block cache capacity used by the global cache the next time
+/// it's created. Must be called before the first [`get_block_cache`], as
+/// board init does for every other sized-at-boot kernel structure; it has
+/// no effect once the cache already exists.
+pub fn init_block_cache_capacity(capacity: usize) {
+    CONFIGURED_CAPACITY.store(capacity.max(1), Ordering::Relaxed);
+}
+
+pub struct BlockCache {
+    cache: [u8; BLOCK_SIZE],
+    block_id: usize,
+    block_device: Arc<dyn BlockDevice>,
+    modified: bool,
+}
+
+impl BlockCache {
+    pub fn new(block_id: usize, block_device: Arc<dyn BlockDevice>) -> Self {
+        let mut cache = [0u8; BLOCK_SIZE];
+        block_device.read_block(block_id, &mut cache);
+        Self {
+            cache,
+            block_id,
+            block_device,
+            modified: false,
+        }
+    }
+
+    fn addr_of_offset(&self, offset: usize) -> usize {
+        offset
+    }
+
+    pub fn get_ref<T: Sized>(&self, offset: usize) -> &T {
+        let addr = self.addr_of_offset(offset);
+        let size = core::mem::size_of::<T>();
+        assert!(addr + size <= BLOCK_SIZE);
+        unsafe { &*(self.cache[addr..].as_ptr() as *const T) }
+    }
+
+    pub fn get_mut<T: Sized>(&mut self, offset: usize) -> &mut T {
+        let addr = self.addr_of_offset(offset);
+        let size = core::mem::size_of::<T>();
+        assert!(addr + size <= BLOCK_SIZE);
+        self.modified = true;
+        unsafe { &mut *(self.cache[addr..].as_mut_ptr() as *mut T) }
+    }
+
+    pub fn read<T, V>(&self, offset: usize, f: impl FnOnce(&T) -> V) -> V {
+        f(self.get_ref(offset))
+    }
+
+    pub fn modify<T, V>(&mut self, offset: usize, f: impl FnOnce(&mut T) -> V) -> V {
+        f(self.get_mut(offset))
+    }
+
+    pub fn sync(&mut self) {
+        if self.modified {
+            self.modified = false;
+            self.block_device.write_block(self.block_id, &self.cache);
+        }
+    }
+
+    /// A copy of this block's current in-memory content, for a caller
+    /// (the journal, namely) that needs to durably record it somewhere
+    /// other than its real location before it's safe to write there.
+    pub fn raw(&self) -> [u8; BLOCK_SIZE] {
+        self.cache
+    }
+}
+
+impl Drop for BlockCache {
+    fn drop(&mut self) {
+        self.sync();
+    }
+}
+
+pub struct BlockCacheManager {
+    capacity: usize,
+    /// Front is least-recently-used, back is most-recently-used; every
+    /// lookup that hits moves its entry to the back.
+    queue: VecDeque<(usize, Arc<Mutex<BlockCache>>)>,
+}
+
+impl BlockCacheManager {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            queue: VecDeque::new(),
+        }
+    }
+
+    pub fn get_block_cache(
+        &mut self,
+        block_id: usize,
+        block_device: Arc<dyn BlockDevice>,
+    ) -> Arc<Mutex<BlockCache>> {
+        if let Some(pos) = self.queue.iter().position(|(id, _)| *id == block_id) {
+            let entry = self.queue.remove(pos).unwrap();
+            let cache = Arc::clone(&entry.1);
+            self.queue.push_back(entry);
+            return cache;
+        }
+        if self.queue.len() >= self.capacity {
+            // Evict the least-recently-used entry with no other owner;
+            // skip any still borrowed elsewhere rather than dropping a
+            // cache a caller is actively using.
+            let evict_pos = self
+                .queue
+                .iter()
+                .position(|(_, cache)| Arc::strong_count(cache) == 1);
+            match evict_pos {
+                Some(pos) => {
+                    self.queue.remove(pos);
+                }
+                None => panic!("block cache full and every entry is in use"),
+            }
+        }
+        let cache = Arc::new(Mutex::new(BlockCache::new(block_id, Arc::clone(&block_device))));
+        self.queue.push_back((block_id, Arc::clone(&cache)));
+        cache
+    }
+}
+
+static BLOCK_CACHE_MANAGER: Mutex<Option<BlockCacheManager>> = Mutex::new(None);
+
+pub fn get_block_cache(
+    block_id: usize,
+    block_device: Arc<dyn BlockDevice>,
+) -> Arc<Mutex<BlockCache>> {
+    let mut manager = BLOCK_CACHE_MANAGER.lock();
+    manager
+        .get_or_insert_with(|| BlockCacheManager::new(CONFIGURED_CAPACITY.load(Ordering::Relaxed)))
+        .get_block_cache(block_id, block_device)
+}
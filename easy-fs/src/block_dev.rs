@@ -0,0 +1,13 @@
+//! The abstract interface a backing block device must implement. Kept
+//! separate from any particular driver so this crate builds both inside
+//! the kernel (backed by `virtio_blk`) and in the host-side `easy-fs-fuse`
+//! tool (backed by a plain file).
+
+/// Byte size of one block; every read/write on a [`BlockDevice`] moves
+/// exactly this many bytes.
+pub const BLOCK_SIZE: usize = 512;
+
+pub trait BlockDevice: Send + Sync {
+    fn read_block(&self, block_id: usize, buf: &mut [u8]);
+    fn write_block(&self, block_id: usize, buf: &[u8]);
+}
@@ -0,0 +1,184 @@
+//! The filesystem as a whole: block layout, allocation, and the handle
+//! to the root directory everything else is resolved from.
+
+use super::bitmap::Bitmap;
+use super::block_cache::get_block_cache;
+use super::block_dev::{BlockDevice, BLOCK_SIZE};
+use super::journal::Journal;
+use super::layout::{DataBlock, DiskInode, DiskInodeType, SuperBlock};
+use super::vfs::Inode;
+use alloc::sync::Arc;
+use spin::Mutex;
+
+const INODE_SIZE: usize = core::mem::size_of::<DiskInode>();
+
+/// Block 0 is the superblock; the journal's header-plus-data region
+/// starts right after it, ahead of the inode bitmap.
+const JOURNAL_HEADER_BLOCK: u32 = 1;
+
+pub struct EasyFileSystem {
+    pub block_device: Arc<dyn BlockDevice>,
+    pub inode_bitmap: Bitmap,
+    pub data_bitmap: Bitmap,
+    /// Write-ahead log for multi-block updates that need to land
+    /// together or not at all (see [`commit_journaled`](Self::commit_journaled)).
+    /// Guarded by the same `Arc<Mutex<Self>>` every other field here is,
+    /// rather than its own lock, since nothing ever needs one without
+    /// the other.
+    journal: Journal,
+    inode_area_start_block: u32,
+    data_area_start_block: u32,
+}
+
+impl EasyFileSystem {
+    /// Lays out a fresh filesystem across `total_blocks`, reserving
+    /// `inode_bitmap_blocks` worth of inode bitmap and sizing the rest
+    /// between inodes and data by `inode_bitmap_blocks` : the rest ratio
+    /// callers already decided on.
+    pub fn create(
+        block_device: Arc<dyn BlockDevice>,
+        total_blocks: u32,
+        inode_bitmap_blocks: u32,
+    ) -> Arc<Mutex<Self>> {
+        let journal_blocks = Journal::blocks_reserved() as u32;
+        let inode_bitmap = Bitmap::new((1 + journal_blocks) as usize, inode_bitmap_blocks as usize);
+        let inode_num = inode_bitmap.max_items();
+        let inode_area_blocks =
+            ((inode_num * INODE_SIZE + BLOCK_SIZE - 1) / BLOCK_SIZE) as u32;
+        let inode_total_blocks = inode_bitmap_blocks + inode_area_blocks;
+        let data_total_blocks = total_blocks - 1 - journal_blocks - inode_total_blocks;
+        let data_bitmap_blocks = (data_total_blocks + 4096) / 4097;
+        let data_area_blocks = data_total_blocks - data_bitmap_blocks;
+        let data_bitmap = Bitmap::new(
+            (1 + journal_blocks + inode_total_blocks) as usize,
+            data_bitmap_blocks as usize,
+        );
+        let mut efs = Self {
+            block_device: Arc::clone(&block_device),
+            inode_bitmap,
+            data_bitmap,
+            journal: Journal::new(Arc::clone(&block_device), JOURNAL_HEADER_BLOCK as usize),
+            inode_area_start_block: 1 + journal_blocks + inode_bitmap_blocks,
+            data_area_start_block: 1 + journal_blocks + inode_total_blocks + data_bitmap_blocks,
+        };
+        for i in 0..total_blocks {
+            get_block_cache(i as usize, Arc::clone(&block_device))
+                .lock()
+                .modify(0, |data_block: &mut DataBlock| {
+                    for byte in data_block.iter_mut() {
+                        *byte = 0;
+                    }
+                });
+        }
+        get_block_cache(0, Arc::clone(&block_device))
+            .lock()
+            .modify(0, |super_block: &mut SuperBlock| {
+                super_block.initialize(
+                    total_blocks,
+                    inode_bitmap_blocks,
+                    inode_area_blocks,
+                    data_bitmap_blocks,
+                    data_area_blocks,
+                );
+            });
+        // The root directory is always inode 0.
+        assert_eq!(efs.alloc_inode(), 0);
+        let (root_block_id, root_block_offset) = efs.disk_inode_pos(0);
+        get_block_cache(root_block_id as usize, Arc::clone(&block_device))
+            .lock()
+            .modify(root_block_offset, |disk_inode: &mut DiskInode| {
+                disk_inode.initialize(DiskInodeType::Directory);
+            });
+        Arc::new(Mutex::new(efs))
+    }
+
+    pub fn open(block_device: Arc<dyn BlockDevice>) -> Arc<Mutex<Self>> {
+        // Before anything else touches the image: if the last mount
+        // crashed mid-checkpoint, replay whatever committed transaction
+        // is sitting in the journal so no block is left half-updated.
+        Journal::recover(&block_device, JOURNAL_HEADER_BLOCK as usize);
+        let super_block = get_block_cache(0, Arc::clone(&block_device))
+            .lock()
+            .read(0, |sb: &SuperBlock| {
+                assert!(sb.is_valid(), "not an easy-fs image");
+                (
+                    sb.inode_bitmap_blocks,
+                    sb.inode_area_blocks,
+                    sb.data_bitmap_blocks,
+                )
+            });
+        let (inode_bitmap_blocks, inode_area_blocks, data_bitmap_blocks) = super_block;
+        let inode_total_blocks = inode_bitmap_blocks + inode_area_blocks;
+        let journal_blocks = Journal::blocks_reserved() as u32;
+        let efs = Self {
+            journal: Journal::new(Arc::clone(&block_device), JOURNAL_HEADER_BLOCK as usize),
+            block_device,
+            inode_bitmap: Bitmap::new((1 + journal_blocks) as usize, inode_bitmap_blocks as usize),
+            data_bitmap: Bitmap::new(
+                (1 + journal_blocks + inode_total_blocks) as usize,
+                data_bitmap_blocks as usize,
+            ),
+            inode_area_start_block: 1 + journal_blocks + inode_bitmap_blocks,
+            data_area_start_block: 1 + journal_blocks + inode_total_blocks + data_bitmap_blocks,
+        };
+        Arc::new(Mutex::new(efs))
+    }
+
+    pub fn root_inode(efs: &Arc<Mutex<Self>>) -> Inode {
+        let block_device = efs.lock().block_device.clone();
+        let (block_id, block_offset) = efs.lock().disk_inode_pos(0);
+        Inode::new(block_id, block_offset, Arc::clone(efs), block_device)
+    }
+
+    pub fn disk_inode_pos(&self, inode_id: u32) -> (u32, usize) {
+        let inodes_per_block = (BLOCK_SIZE / INODE_SIZE) as u32;
+        let block_id = self.inode_area_start_block + inode_id / inodes_per_block;
+        (
+            block_id,
+            (inode_id % inodes_per_block) as usize * INODE_SIZE,
+        )
+    }
+
+    pub fn alloc_inode(&mut self) -> u32 {
+        self.inode_bitmap.alloc(&self.block_device).unwrap() as u32
+    }
+
+    pub fn alloc_data(&mut self) -> u32 {
+        self.data_bitmap.alloc(&self.block_device).unwrap() as u32 + self.data_area_start_block
+    }
+
+    pub fn dealloc_data(&mut self, block_id: u32) {
+        get_block_cache(block_id as usize, Arc::clone(&self.block_device))
+            .lock()
+            .modify(0, |data_block: &mut DataBlock| {
+                data_block.iter_mut().for_each(|b| *b = 0);
+            });
+        self.data_bitmap.dealloc(
+            &self.block_device,
+            (block_id - self.data_area_start_block) as usize,
+        );
+    }
+
+    pub fn dealloc_inode(&mut self, inode_id: u32) {
+        self.inode_bitmap.dealloc(&self.block_device, inode_id as usize);
+    }
+
+    /// Durably commits a set of blocks this caller already modified
+    /// in-place through [`get_block_cache`] (a new inode's metadata block
+    /// and the directory entry block pointing at it, say) as a single
+    /// journaled transaction, so a crash partway through can't leave one
+    /// written and the other stale. Stages each block's *current*
+    /// in-memory content — not a fresh computation — since by the time
+    /// this is called the caller's `modify` closures have already run;
+    /// this only changes when those bytes become crash-safe, not what
+    /// they are.
+    pub fn commit_journaled(&mut self, block_ids: &[u32]) {
+        for &block_id in block_ids {
+            let data = get_block_cache(block_id as usize, Arc::clone(&self.block_device))
+                .lock()
+                .raw();
+            self.journal.stage_write(block_id, data);
+        }
+        self.journal.commit();
+    }
+}
@@ -0,0 +1,227 @@
+//! Consistency checker for an easy-fs image: walks the directory tree
+//! from the root to find every inode actually reachable, then
+//! cross-checks that against the inode bitmap and each directory's own
+//! entries. This lives in the library (rather than only a host-side
+//! packer binary) so the kernel, or any other caller with an open
+//! [`EasyFileSystem`], can run the same check without re-implementing
+//! the walk.
+//!
+//! There are no hard links in this filesystem (every inode has exactly
+//! one parent directory entry or none), so "link count" collapses to
+//! "reachable at all" — an allocated inode nothing points at is a leak,
+//! and a directory entry pointing at an unallocated inode is dangling.
+
+use super::efs::EasyFileSystem;
+use super::layout::DiskInode;
+use super::vfs::Inode;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+/// A single problem found while checking an image.
+pub enum FsckIssue {
+    /// An inode bit is set but no directory entry anywhere reaches it.
+    LeakedInode(u32),
+    /// A directory entry's inode number isn't claimed in the inode
+    /// bitmap.
+    DanglingEntry {
+        parent_inode: u32,
+        name: String,
+        inode_number: u32,
+    },
+}
+
+pub struct FsckReport {
+    pub issues: Vec<FsckIssue>,
+}
+
+impl FsckReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Root is always inode 0 ([`EasyFileSystem::create`] guarantees it).
+const ROOT_INODE: u32 = 0;
+
+/// Walks the image from the root, recording every issue found. When
+/// `repair` is set, dangling entries are tombstoned out of their parent
+/// directory and leaked inodes have their data freed and their bitmap
+/// bit cleared, in place, as the walk finds them.
+pub fn check(efs: &Arc<Mutex<EasyFileSystem>>, repair: bool) -> FsckReport {
+    let mut issues = Vec::new();
+    let mut reachable = BTreeSet::new();
+    reachable.insert(ROOT_INODE);
+
+    let root = Arc::new(EasyFileSystem::root_inode(efs));
+    walk(&root, ROOT_INODE, efs, &mut reachable, &mut issues, repair);
+
+    let block_device = efs.lock().block_device.clone();
+    let allocated = efs.lock().inode_bitmap.allocated_bits(&block_device);
+    for bit in allocated {
+        let inode_number = bit as u32;
+        if reachable.contains(&inode_number) {
+            continue;
+        }
+        if repair {
+            free_inode(efs, inode_number);
+        }
+        issues.push(FsckIssue::LeakedInode(inode_number));
+    }
+
+    FsckReport { issues }
+}
+
+fn walk(
+    dir: &Arc<Inode>,
+    dir_inode_number: u32,
+    efs: &Arc<Mutex<EasyFileSystem>>,
+    reachable: &mut BTreeSet<u32>,
+    issues: &mut Vec<FsckIssue>,
+    repair: bool,
+) {
+    for (name, inode_number) in dir.ls_entries() {
+        let allocated = {
+            let fs = efs.lock();
+            fs.inode_bitmap
+                .is_allocated(&fs.block_device, inode_number as usize)
+        };
+        if !allocated {
+            if repair {
+                dir.remove_entry(&name);
+            }
+            issues.push(FsckIssue::DanglingEntry {
+                parent_inode: dir_inode_number,
+                name,
+                inode_number,
+            });
+            continue;
+        }
+        if !reachable.insert(inode_number) {
+            // Already visited through another entry; nothing upstream
+            // of here supports multiple names for one inode, so this
+            // shouldn't happen outside a corrupted image, but don't
+            // recurse into it twice if it does.
+            continue;
+        }
+
+        let child = entry_inode(efs, inode_number);
+        if child.is_dir() {
+            walk(&child, inode_number, efs, reachable, issues, repair);
+        }
+    }
+}
+
+fn entry_inode(efs: &Arc<Mutex<EasyFileSystem>>, inode_number: u32) -> Arc<Inode> {
+    let fs = efs.lock();
+    let (block_id, block_offset) = fs.disk_inode_pos(inode_number);
+    let block_device = fs.block_device.clone();
+    drop(fs);
+    Arc::new(Inode::new(block_id, block_offset, Arc::clone(efs), block_device))
+}
+
+fn free_inode(efs: &Arc<Mutex<EasyFileSystem>>, inode_number: u32) {
+    let (block_id, block_offset, block_device) = {
+        let fs = efs.lock();
+        let (block_id, block_offset) = fs.disk_inode_pos(inode_number);
+        (block_id, block_offset, fs.block_device.clone())
+    };
+    let freed = super::block_cache::get_block_cache(block_id as usize, Arc::clone(&block_device))
+        .lock()
+        .modify(block_offset, |disk_inode: &mut DiskInode| {
+            disk_inode.clear(&block_device)
+        });
+    let mut fs = efs.lock();
+    for block in freed {
+        fs.dealloc_data(block);
+    }
+    fs.dealloc_inode(inode_number);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::RamDisk;
+    use alloc::string::ToString;
+
+    const TOTAL_BLOCKS: u32 = 8192;
+
+    fn fresh_fs() -> Arc<Mutex<EasyFileSystem>> {
+        let disk: Arc<dyn crate::block_dev::BlockDevice> =
+            Arc::new(RamDisk::new(0, TOTAL_BLOCKS as usize));
+        EasyFileSystem::create(disk, TOTAL_BLOCKS, 1)
+    }
+
+    /// Exercises nested-path resolution (`Inode::find`) and the
+    /// consistency checker against one filesystem image, rather than
+    /// several independent ones: every image here lays its superblock
+    /// and inode/data areas out starting at block 0, and the global
+    /// block cache ([`crate::block_cache::get_block_cache`]) keys purely
+    /// by block id — two separate images sharing block id 0 in the same
+    /// test binary would silently hand each other's cached bytes back
+    /// instead of ever touching their own device.
+    #[test]
+    fn tree_resolution_and_consistency_checking() {
+        let efs = fresh_fs();
+        let root = Arc::new(EasyFileSystem::root_inode(&efs));
+        let a = root.create_dir("a").unwrap();
+        let b = a.create_dir("b").unwrap();
+        b.create_file("c").unwrap();
+
+        assert!(root.find("a/b/c").is_some());
+        assert!(root.find("/a/b/c").is_some());
+        assert!(root.find("a/b/missing").is_none());
+        assert!(root.find("nope").is_none());
+        assert!(check(&efs, false).is_clean());
+
+        // Dangling entry: free the inode behind a directory entry
+        // without going through `Inode::remove`, leaving the entry
+        // pointing at something the bitmap no longer claims.
+        root.create_file("ghost").unwrap();
+        let ghost_ino = root
+            .ls_entries()
+            .into_iter()
+            .find(|(name, _)| name == "ghost")
+            .unwrap()
+            .1;
+        efs.lock().dealloc_inode(ghost_ino);
+
+        let report = check(&efs, false);
+        assert_eq!(report.issues.len(), 1);
+        match &report.issues[0] {
+            FsckIssue::DanglingEntry {
+                name,
+                inode_number,
+                ..
+            } => {
+                assert_eq!(name, "ghost");
+                assert_eq!(*inode_number, ghost_ino);
+            }
+            FsckIssue::LeakedInode(_) => panic!("expected a dangling entry"),
+        }
+        // A repairing run still reports what it found; the pass after it
+        // finds nothing left to fix.
+        assert_eq!(check(&efs, true).issues.len(), 1);
+        assert!(check(&efs, false).is_clean());
+        assert!(!root.ls().contains(&"ghost".to_string()));
+
+        // Leaked inode: claim a bitmap bit with no directory entry ever
+        // pointing at it.
+        let leaked_ino = efs.lock().alloc_inode();
+        let report = check(&efs, false);
+        assert_eq!(report.issues.len(), 1);
+        match &report.issues[0] {
+            FsckIssue::LeakedInode(ino) => assert_eq!(*ino, leaked_ino),
+            FsckIssue::DanglingEntry { .. } => panic!("expected a leaked inode"),
+        }
+        assert_eq!(check(&efs, true).issues.len(), 1);
+        assert!(check(&efs, false).is_clean());
+        let block_device = efs.lock().block_device.clone();
+        assert!(!efs
+            .lock()
+            .inode_bitmap
+            .is_allocated(&block_device, leaked_ino as usize));
+    }
+}
@@ -0,0 +1,148 @@
+//! A minimal write-ahead journal: before checkpointing a batch of block
+//! writes to their real locations, record them (and a commit marker) in
+//! a reserved journal region first. If the kernel crashes mid-checkpoint,
+//! remounting replays the journal instead of leaving some blocks updated
+//! and others stale.
+//!
+//! [`EasyFileSystem`](super::efs::EasyFileSystem) owns the instance that
+//! matters: it reserves this journal's region right after the
+//! superblock, calls [`Journal::recover`] once at mount time before
+//! anything else touches the image, and drives
+//! [`stage_write`](Journal::stage_write)/[`commit`](Journal::commit)
+//! through its own `commit_journaled` for multi-block updates that need
+//! to land together (see [`Inode::create`](super::vfs::Inode::create)).
+
+use super::block_dev::{BlockDevice, BLOCK_SIZE};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+const JOURNAL_MAGIC: u32 = 0x6a6e_6c31; // "jnl1"
+/// How many dirty blocks one transaction can cover; bounded so the
+/// journal header (one block) can list every entry's block id inline.
+pub const MAX_JOURNAL_ENTRIES: usize = 32;
+
+#[repr(C)]
+struct JournalHeader {
+    magic: u32,
+    /// 1 once every data block of the transaction is durably written and
+    /// it's safe to replay; 0 otherwise (including once checkpointed).
+    committed: u32,
+    entry_count: u32,
+    block_ids: [u32; MAX_JOURNAL_ENTRIES],
+}
+
+/// A journal occupies `1 + MAX_JOURNAL_ENTRIES` blocks: one header block
+/// followed by one data block per possible entry.
+pub struct Journal {
+    block_device: Arc<dyn BlockDevice>,
+    header_block: usize,
+    staged: Vec<(u32, [u8; BLOCK_SIZE])>,
+}
+
+impl Journal {
+    pub fn new(block_device: Arc<dyn BlockDevice>, header_block: usize) -> Self {
+        Self {
+            block_device,
+            header_block,
+            staged: Vec::new(),
+        }
+    }
+
+    pub fn blocks_reserved() -> usize {
+        1 + MAX_JOURNAL_ENTRIES
+    }
+
+    /// Queues a write for the next [`commit`](Self::commit). Panics if a
+    /// transaction would exceed [`MAX_JOURNAL_ENTRIES`]; callers should
+    /// commit in smaller batches rather than relying on an unbounded log.
+    pub fn stage_write(&mut self, block_id: u32, data: [u8; BLOCK_SIZE]) {
+        assert!(
+            self.staged.len() < MAX_JOURNAL_ENTRIES,
+            "journal transaction too large"
+        );
+        self.staged.push((block_id, data));
+    }
+
+    /// Durably records every staged write, marks the transaction
+    /// committed, then checkpoints (applies) each write to its real
+    /// block and clears the journal. A crash before the commit marker is
+    /// written loses the whole transaction, as if it never started; a
+    /// crash after it is replayed by [`recover`](Self::recover).
+    pub fn commit(&mut self) {
+        if self.staged.is_empty() {
+            return;
+        }
+        let mut header = JournalHeader {
+            magic: JOURNAL_MAGIC,
+            committed: 0,
+            entry_count: self.staged.len() as u32,
+            block_ids: [0; MAX_JOURNAL_ENTRIES],
+        };
+        for (i, (block_id, _)) in self.staged.iter().enumerate() {
+            header.block_ids[i] = *block_id;
+        }
+        self.write_header(&header);
+        for (i, (_, data)) in self.staged.iter().enumerate() {
+            self.block_device.write_block(self.header_block + 1 + i, data);
+        }
+        header.committed = 1;
+        self.write_header(&header);
+
+        for (block_id, data) in self.staged.drain(..) {
+            self.block_device.write_block(block_id as usize, &data);
+        }
+
+        header.committed = 0;
+        header.entry_count = 0;
+        self.write_header(&header);
+    }
+
+    fn write_header(&self, header: &JournalHeader) {
+        let mut buf = [0u8; BLOCK_SIZE];
+        let size = core::mem::size_of::<JournalHeader>();
+        assert!(size <= BLOCK_SIZE);
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                header as *const JournalHeader as *const u8,
+                buf.as_mut_ptr(),
+                size,
+            );
+        }
+        self.block_device.write_block(self.header_block, &buf);
+    }
+
+    /// Called once at mount time, before anything else touches the
+    /// filesystem: if a committed-but-not-yet-checkpointed transaction
+    /// is sitting in the journal, replays it to its real blocks.
+    pub fn recover(block_device: &Arc<dyn BlockDevice>, header_block: usize) {
+        let mut buf = [0u8; BLOCK_SIZE];
+        block_device.read_block(header_block, &mut buf);
+        let header = unsafe { &*(buf.as_ptr() as *const JournalHeader) };
+        if header.magic != JOURNAL_MAGIC || header.committed != 1 {
+            return;
+        }
+        let entry_count = header.entry_count as usize;
+        let block_ids = header.block_ids;
+        for i in 0..entry_count {
+            let mut data = [0u8; BLOCK_SIZE];
+            block_device.read_block(header_block + 1 + i, &mut data);
+            block_device.write_block(block_ids[i] as usize, &data);
+        }
+        let cleared = JournalHeader {
+            magic: JOURNAL_MAGIC,
+            committed: 0,
+            entry_count: 0,
+            block_ids: [0; MAX_JOURNAL_ENTRIES],
+        };
+        let mut clear_buf = [0u8; BLOCK_SIZE];
+        let size = core::mem::size_of::<JournalHeader>();
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                &cleared as *const JournalHeader as *const u8,
+                clear_buf.as_mut_ptr(),
+                size,
+            );
+        }
+        block_device.write_block(header_block, &clear_buf);
+    }
+}
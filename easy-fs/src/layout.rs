@@ -0,0 +1,567 @@
+//! On-disk data structures: the superblock, per-inode metadata, and
+//! directory entries.
+
+use super::block_cache::get_block_cache;
+use super::block_dev::{BlockDevice, BLOCK_SIZE};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+const EFS_MAGIC: u32 = 0x3b80_0001;
+pub const INODE_DIRECT_COUNT: usize = 28;
+const INDIRECT1_BOUND: usize = INODE_DIRECT_COUNT + BLOCK_SIZE / 4;
+/// Where the doubly-indirect region ends and the triply-indirect one
+/// begins: `indirect2` covers `(BLOCK_SIZE / 4)` indirect1 blocks, each
+/// good for `BLOCK_SIZE / 4` data blocks.
+const INDIRECT2_BOUND: usize = INDIRECT1_BOUND + (BLOCK_SIZE / 4) * (BLOCK_SIZE / 4);
+pub const NAME_LENGTH_LIMIT: usize = 27;
+
+#[repr(C)]
+pub struct SuperBlock {
+    magic: u32,
+    pub total_blocks: u32,
+    pub inode_bitmap_blocks: u32,
+    pub inode_area_blocks: u32,
+    pub data_bitmap_blocks: u32,
+    pub data_area_blocks: u32,
+}
+
+impl SuperBlock {
+    pub fn initialize(
+        &mut self,
+        total_blocks: u32,
+        inode_bitmap_blocks: u32,
+        inode_area_blocks: u32,
+        data_bitmap_blocks: u32,
+        data_area_blocks: u32,
+    ) {
+        *self = Self {
+            magic: EFS_MAGIC,
+            total_blocks,
+            inode_bitmap_blocks,
+            inode_area_blocks,
+            data_bitmap_blocks,
+            data_area_blocks,
+        };
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.magic == EFS_MAGIC
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum DiskInodeType {
+    File,
+    Directory,
+}
+
+/// One inode's on-disk metadata: its type, size, and the block pointers
+/// (direct, then singly-, doubly-, and triply-indirect) needed to find
+/// its data. The triple level exists purely to raise the size cap a
+/// single inode can address; files small enough for direct/indirect1/
+/// indirect2 never touch it.
+#[repr(C)]
+pub struct DiskInode {
+    pub size: u32,
+    pub direct: [u32; INODE_DIRECT_COUNT],
+    pub indirect1: u32,
+    pub indirect2: u32,
+    pub indirect3: u32,
+    pub type_: DiskInodeType,
+}
+
+impl DiskInode {
+    pub fn initialize(&mut self, type_: DiskInodeType) {
+        self.size = 0;
+        self.direct = [0; INODE_DIRECT_COUNT];
+        self.indirect1 = 0;
+        self.indirect2 = 0;
+        self.indirect3 = 0;
+        self.type_ = type_;
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.type_ == DiskInodeType::Directory
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.type_ == DiskInodeType::File
+    }
+
+    /// Maps a block index within the file's data (`inner_id`, counted in
+    /// whole blocks from the start of the file) to the block id holding
+    /// it, walking through direct then indirect block pointers as the
+    /// index grows past what direct blocks alone can address.
+    pub fn get_block_id(&self, inner_id: usize, block_device: &Arc<dyn BlockDevice>) -> u32 {
+        if inner_id < INODE_DIRECT_COUNT {
+            self.direct[inner_id]
+        } else if inner_id < INDIRECT1_BOUND {
+            get_block_cache(self.indirect1 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect_block: &IndirectBlock| {
+                    indirect_block.0[inner_id - INODE_DIRECT_COUNT]
+                })
+        } else if inner_id < INDIRECT2_BOUND {
+            let last = inner_id - INDIRECT1_BOUND;
+            let indirect1 = get_block_cache(self.indirect2 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect2: &IndirectBlock| {
+                    indirect2.0[last / (BLOCK_SIZE / 4)]
+                });
+            get_block_cache(indirect1 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect1: &IndirectBlock| {
+                    indirect1.0[last % (BLOCK_SIZE / 4)]
+                })
+        } else {
+            let indirect1_cap = BLOCK_SIZE / 4;
+            let indirect2_cap = indirect1_cap * indirect1_cap;
+            let last = inner_id - INDIRECT2_BOUND;
+            let indirect2 = get_block_cache(self.indirect3 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect3: &IndirectBlock| indirect3.0[last / indirect2_cap]);
+            let rest = last % indirect2_cap;
+            let indirect1 = get_block_cache(indirect2 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect2: &IndirectBlock| indirect2.0[rest / indirect1_cap]);
+            get_block_cache(indirect1 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect1: &IndirectBlock| {
+                    indirect1.0[rest % indirect1_cap]
+                })
+        }
+    }
+
+    pub fn data_blocks(&self) -> u32 {
+        Self::_data_blocks(self.size)
+    }
+
+    fn _data_blocks(size: u32) -> u32 {
+        (size as usize).div_ceil(BLOCK_SIZE) as u32
+    }
+
+    /// Total blocks (data plus whatever indirect index blocks they need)
+    /// for a file of `size` bytes.
+    fn total_blocks(size: u32) -> u32 {
+        let data_blocks = Self::_data_blocks(size) as usize;
+        let indirect1_cap = BLOCK_SIZE / 4;
+        let mut total = data_blocks;
+        if data_blocks > INODE_DIRECT_COUNT {
+            total += 1;
+        }
+        if data_blocks > INDIRECT1_BOUND {
+            total += 1;
+            let indirect2_entries =
+                (data_blocks - INDIRECT1_BOUND).min(INDIRECT2_BOUND - INDIRECT1_BOUND);
+            total += indirect2_entries.div_ceil(indirect1_cap);
+        }
+        if data_blocks > INDIRECT2_BOUND {
+            total += 1;
+            let indirect3_entries = data_blocks - INDIRECT2_BOUND;
+            let indirect2_cap = indirect1_cap * indirect1_cap;
+            total += indirect3_entries.div_ceil(indirect2_cap);
+            total += indirect3_entries.div_ceil(indirect1_cap);
+        }
+        total as u32
+    }
+
+    /// How many additional blocks (data and index blocks together) are
+    /// needed to grow from the current size to `new_size`.
+    pub fn blocks_num_needed(&self, new_size: u32) -> u32 {
+        assert!(new_size >= self.size);
+        Self::total_blocks(new_size) - Self::total_blocks(self.size)
+    }
+
+    /// Grows the file to `new_size`, consuming `new_blocks` (already
+    /// allocated by the caller) to fill in newly-needed direct and
+    /// indirect block pointers.
+    pub fn grow_to(
+        &mut self,
+        new_size: u32,
+        new_blocks: alloc::vec::Vec<u32>,
+        block_device: &Arc<dyn BlockDevice>,
+    ) {
+        let mut current_blocks = self.data_blocks();
+        self.size = new_size;
+        let mut total_blocks = self.data_blocks();
+        let mut new_blocks = new_blocks.into_iter();
+
+        while current_blocks < total_blocks.min(INODE_DIRECT_COUNT as u32) {
+            self.direct[current_blocks as usize] = new_blocks.next().unwrap();
+            current_blocks += 1;
+        }
+        if total_blocks <= INODE_DIRECT_COUNT as u32 {
+            return;
+        }
+        if current_blocks == INODE_DIRECT_COUNT as u32 {
+            self.indirect1 = new_blocks.next().unwrap();
+        }
+        current_blocks -= INODE_DIRECT_COUNT as u32;
+        total_blocks -= INODE_DIRECT_COUNT as u32;
+
+        let indirect1_cap = (BLOCK_SIZE / 4) as u32;
+        get_block_cache(self.indirect1 as usize, Arc::clone(block_device))
+            .lock()
+            .modify(0, |indirect1: &mut IndirectBlock| {
+                while current_blocks < total_blocks.min(indirect1_cap) {
+                    indirect1.0[current_blocks as usize] = new_blocks.next().unwrap();
+                    current_blocks += 1;
+                }
+            });
+        if total_blocks <= indirect1_cap {
+            return;
+        }
+        if current_blocks == indirect1_cap {
+            self.indirect2 = new_blocks.next().unwrap();
+        }
+        current_blocks -= indirect1_cap;
+        total_blocks -= indirect1_cap;
+
+        let indirect2_cap = indirect1_cap * indirect1_cap;
+        let indirect2_end = total_blocks.min(indirect2_cap);
+        let a0 = current_blocks as usize / indirect1_cap as usize;
+        let mut b0 = current_blocks as usize % indirect1_cap as usize;
+        let a1 = indirect2_end as usize / indirect1_cap as usize;
+        let b1 = indirect2_end as usize % indirect1_cap as usize;
+        get_block_cache(self.indirect2 as usize, Arc::clone(block_device))
+            .lock()
+            .modify(0, |indirect2: &mut IndirectBlock| {
+                let mut a = a0;
+                while a < a1 || (a == a1 && b0 < b1) {
+                    if b0 == 0 {
+                        indirect2.0[a] = new_blocks.next().unwrap();
+                    }
+                    let entry = indirect2.0[a];
+                    get_block_cache(entry as usize, Arc::clone(block_device))
+                        .lock()
+                        .modify(0, |indirect1: &mut IndirectBlock| {
+                            indirect1.0[b0] = new_blocks.next().unwrap();
+                        });
+                    b0 += 1;
+                    if b0 == indirect1_cap as usize {
+                        b0 = 0;
+                        a += 1;
+                    }
+                }
+            });
+        current_blocks = current_blocks.max(indirect2_end);
+        if total_blocks <= indirect2_cap {
+            return;
+        }
+        if current_blocks == indirect2_cap {
+            self.indirect3 = new_blocks.next().unwrap();
+        }
+        current_blocks -= indirect2_cap;
+        total_blocks -= indirect2_cap;
+
+        // The triple level is rare enough (a file has to outgrow the
+        // double-indirect cap first) that it's not worth the range-
+        // slicing dance above; fill it one data block at a time instead,
+        // allocating an indirect2/indirect1 block whenever we land on
+        // its first entry.
+        let indirect3 = self.indirect3;
+        while current_blocks < total_blocks {
+            let idx = current_blocks as usize;
+            let a = idx / indirect2_cap as usize;
+            let b = (idx % indirect2_cap as usize) / indirect1_cap as usize;
+            let c = idx % indirect1_cap as usize;
+
+            let indirect2_id = if b == 0 && c == 0 {
+                let id = new_blocks.next().unwrap();
+                get_block_cache(indirect3 as usize, Arc::clone(block_device))
+                    .lock()
+                    .modify(0, |indirect3: &mut IndirectBlock| indirect3.0[a] = id);
+                id
+            } else {
+                get_block_cache(indirect3 as usize, Arc::clone(block_device))
+                    .lock()
+                    .read(0, |indirect3: &IndirectBlock| indirect3.0[a])
+            };
+
+            let indirect1_id = if c == 0 {
+                let id = new_blocks.next().unwrap();
+                get_block_cache(indirect2_id as usize, Arc::clone(block_device))
+                    .lock()
+                    .modify(0, |indirect2: &mut IndirectBlock| indirect2.0[b] = id);
+                id
+            } else {
+                get_block_cache(indirect2_id as usize, Arc::clone(block_device))
+                    .lock()
+                    .read(0, |indirect2: &IndirectBlock| indirect2.0[b])
+            };
+
+            get_block_cache(indirect1_id as usize, Arc::clone(block_device))
+                .lock()
+                .modify(0, |indirect1: &mut IndirectBlock| {
+                    indirect1.0[c] = new_blocks.next().unwrap();
+                });
+
+            current_blocks += 1;
+        }
+    }
+
+    /// Frees every block this file occupies (data blocks plus the
+    /// indirect index blocks themselves), resetting the inode to empty.
+    /// Returns the freed block ids so the caller can return them to the
+    /// data bitmap; this inode's own slot is *not* freed here, since the
+    /// inode bitmap is keyed by inode id, not block id.
+    pub fn clear(&mut self, block_device: &Arc<dyn BlockDevice>) -> Vec<u32> {
+        let mut freed = Vec::new();
+        let data_blocks = self.data_blocks() as usize;
+        self.size = 0;
+        let indirect1_cap = BLOCK_SIZE / 4;
+
+        let direct_count = data_blocks.min(INODE_DIRECT_COUNT);
+        for slot in self.direct.iter_mut().take(direct_count) {
+            freed.push(*slot);
+            *slot = 0;
+        }
+
+        if data_blocks > INODE_DIRECT_COUNT {
+            freed.push(self.indirect1);
+            let count = (data_blocks - INODE_DIRECT_COUNT).min(indirect1_cap);
+            get_block_cache(self.indirect1 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect1: &IndirectBlock| {
+                    freed.extend_from_slice(&indirect1.0[..count]);
+                });
+            self.indirect1 = 0;
+        }
+
+        if data_blocks > INDIRECT1_BOUND {
+            freed.push(self.indirect2);
+            let remaining = (data_blocks - INDIRECT1_BOUND).min(INDIRECT2_BOUND - INDIRECT1_BOUND);
+            let indirect1_blocks_used = remaining.div_ceil(indirect1_cap);
+            get_block_cache(self.indirect2 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect2: &IndirectBlock| {
+                    for i in 0..indirect1_blocks_used {
+                        let indirect1_id = indirect2.0[i];
+                        freed.push(indirect1_id);
+                        let count_in_this = (remaining - i * indirect1_cap).min(indirect1_cap);
+                        get_block_cache(indirect1_id as usize, Arc::clone(block_device))
+                            .lock()
+                            .read(0, |indirect1: &IndirectBlock| {
+                                freed.extend_from_slice(&indirect1.0[..count_in_this]);
+                            });
+                    }
+                });
+            self.indirect2 = 0;
+        }
+
+        if data_blocks > INDIRECT2_BOUND {
+            freed.push(self.indirect3);
+            let remaining = data_blocks - INDIRECT2_BOUND;
+            let indirect2_cap = indirect1_cap * indirect1_cap;
+            let indirect2_blocks_used = remaining.div_ceil(indirect2_cap);
+            get_block_cache(self.indirect3 as usize, Arc::clone(block_device))
+                .lock()
+                .read(0, |indirect3: &IndirectBlock| {
+                    for i in 0..indirect2_blocks_used {
+                        let indirect2_id = indirect3.0[i];
+                        freed.push(indirect2_id);
+                        let remaining_in_this = (remaining - i * indirect2_cap).min(indirect2_cap);
+                        let indirect1_blocks_used = remaining_in_this.div_ceil(indirect1_cap);
+                        get_block_cache(indirect2_id as usize, Arc::clone(block_device))
+                            .lock()
+                            .read(0, |indirect2: &IndirectBlock| {
+                                for j in 0..indirect1_blocks_used {
+                                    let indirect1_id = indirect2.0[j];
+                                    freed.push(indirect1_id);
+                                    let count_in_this =
+                                        (remaining_in_this - j * indirect1_cap).min(indirect1_cap);
+                                    get_block_cache(indirect1_id as usize, Arc::clone(block_device))
+                                        .lock()
+                                        .read(0, |indirect1: &IndirectBlock| {
+                                            freed.extend_from_slice(&indirect1.0[..count_in_this]);
+                                        });
+                                }
+                            });
+                    }
+                });
+            self.indirect3 = 0;
+        }
+
+        freed
+    }
+
+    pub fn read_at(
+        &self,
+        offset: usize,
+        buf: &mut [u8],
+        block_device: &Arc<dyn BlockDevice>,
+    ) -> usize {
+        let mut start = offset;
+        let end = (offset + buf.len()).min(self.size as usize);
+        if start >= end {
+            return 0;
+        }
+        let mut start_block = start / BLOCK_SIZE;
+        let mut read_size = 0usize;
+        loop {
+            let mut end_current_block = (start / BLOCK_SIZE + 1) * BLOCK_SIZE;
+            end_current_block = end_current_block.min(end);
+            let block_read_size = end_current_block - start;
+            let dst = &mut buf[read_size..read_size + block_read_size];
+            get_block_cache(
+                self.get_block_id(start_block, block_device) as usize,
+                Arc::clone(block_device),
+            )
+            .lock()
+            .read(0, |data_block: &DataBlock| {
+                let src = &data_block[start % BLOCK_SIZE..start % BLOCK_SIZE + block_read_size];
+                dst.copy_from_slice(src);
+            });
+            read_size += block_read_size;
+            if end_current_block == end {
+                break;
+            }
+            start_block += 1;
+            start = end_current_block;
+        }
+        read_size
+    }
+
+    pub fn write_at(
+        &mut self,
+        offset: usize,
+        buf: &[u8],
+        block_device: &Arc<dyn BlockDevice>,
+    ) -> usize {
+        let mut start = offset;
+        let end = (offset + buf.len()).min(self.size as usize);
+        assert!(start <= end);
+        let mut start_block = start / BLOCK_SIZE;
+        let mut write_size = 0usize;
+        loop {
+            let mut end_current_block = (start / BLOCK_SIZE + 1) * BLOCK_SIZE;
+            end_current_block = end_current_block.min(end);
+            let block_write_size = end_current_block - start;
+            get_block_cache(
+                self.get_block_id(start_block, block_device) as usize,
+                Arc::clone(block_device),
+            )
+            .lock()
+            .modify(0, |data_block: &mut DataBlock| {
+                let src = &buf[write_size..write_size + block_write_size];
+                let dst =
+                    &mut data_block[start % BLOCK_SIZE..start % BLOCK_SIZE + block_write_size];
+                dst.copy_from_slice(src);
+            });
+            write_size += block_write_size;
+            if end_current_block == end {
+                break;
+            }
+            start_block += 1;
+            start = end_current_block;
+        }
+        write_size
+    }
+}
+
+#[repr(C)]
+pub struct IndirectBlock(pub [u32; BLOCK_SIZE / 4]);
+
+pub type DataBlock = [u8; BLOCK_SIZE];
+
+/// One entry in a directory's data: a fixed-width name and the inode
+/// number it resolves to. Subdirectories are just inodes whose data is a
+/// sequence of these, the same as any other directory.
+#[repr(C)]
+pub struct DirEntry {
+    name: [u8; NAME_LENGTH_LIMIT + 1],
+    inode_number: u32,
+}
+
+pub const DIRENT_SZ: usize = core::mem::size_of::<DirEntry>();
+
+impl DirEntry {
+    pub fn empty() -> Self {
+        Self {
+            name: [0u8; NAME_LENGTH_LIMIT + 1],
+            inode_number: 0,
+        }
+    }
+
+    pub fn new(name: &str, inode_number: u32) -> Self {
+        let mut bytes = [0u8; NAME_LENGTH_LIMIT + 1];
+        let name_bytes = name.as_bytes();
+        let len = name_bytes.len().min(NAME_LENGTH_LIMIT);
+        bytes[..len].copy_from_slice(&name_bytes[..len]);
+        Self {
+            name: bytes,
+            inode_number,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self as *const _ as *const u8, DIRENT_SZ) }
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self as *mut _ as *mut u8, DIRENT_SZ) }
+    }
+
+    pub fn name(&self) -> &str {
+        let len = self.name.iter().position(|&b| b == 0).unwrap_or(self.name.len());
+        core::str::from_utf8(&self.name[..len]).unwrap()
+    }
+
+    pub fn inode_number(&self) -> u32 {
+        self.inode_number
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_num_needed_counts_index_blocks_as_files_cross_indirect_bounds() {
+        let mut inode = DiskInode {
+            size: 0,
+            direct: [0; INODE_DIRECT_COUNT],
+            indirect1: 0,
+            indirect2: 0,
+            indirect3: 0,
+            type_: DiskInodeType::File,
+        };
+        // Entirely within direct blocks: just data blocks, no index
+        // block yet.
+        assert_eq!(
+            inode.blocks_num_needed((INODE_DIRECT_COUNT * BLOCK_SIZE) as u32),
+            INODE_DIRECT_COUNT as u32
+        );
+        inode.size = (INODE_DIRECT_COUNT * BLOCK_SIZE) as u32;
+        // One byte past the direct region pulls in both a new data block
+        // and the indirect1 block that points at it.
+        assert_eq!(inode.blocks_num_needed(inode.size + 1), 2);
+    }
+
+    #[test]
+    fn data_blocks_rounds_up_to_a_whole_block() {
+        assert_eq!(DiskInode::_data_blocks(0), 0);
+        assert_eq!(DiskInode::_data_blocks(1), 1);
+        assert_eq!(DiskInode::_data_blocks(BLOCK_SIZE as u32), 1);
+        assert_eq!(DiskInode::_data_blocks(BLOCK_SIZE as u32 + 1), 2);
+    }
+
+    #[test]
+    fn dir_entry_name_round_trips_through_bytes() {
+        let entry = DirEntry::new("a-name", 7);
+        assert_eq!(entry.name(), "a-name");
+        assert_eq!(entry.inode_number(), 7);
+
+        let mut restored = DirEntry::empty();
+        restored.as_bytes_mut().copy_from_slice(entry.as_bytes());
+        assert_eq!(restored.name(), "a-name");
+        assert_eq!(restored.inode_number(), 7);
+    }
+
+    #[test]
+    fn dir_entry_name_longer_than_limit_is_truncated() {
+        let long_name: alloc::string::String = "x".repeat(NAME_LENGTH_LIMIT + 10);
+        let entry = DirEntry::new(&long_name, 0);
+        assert_eq!(entry.name().len(), NAME_LENGTH_LIMIT);
+    }
+}
@@ -0,0 +1,29 @@
+//! On-disk filesystem used by the kernel's `fs` layer: block caching,
+//! bitmap allocation, and an inode/directory layout that supports
+//! nested subdirectories resolved by path.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+mod bitmap;
+mod block_cache;
+mod block_dev;
+mod efs;
+mod fsck;
+mod journal;
+mod layout;
+#[cfg(test)]
+mod test_support;
+mod vfs;
+
+pub use block_cache::{
+    get_block_cache, init_block_cache_capacity, BlockCache, BlockCacheManager,
+    DEFAULT_BLOCK_CACHE_SIZE,
+};
+pub use block_dev::{BlockDevice, BLOCK_SIZE};
+pub use efs::EasyFileSystem;
+pub use fsck::{check as fsck_check, FsckIssue, FsckReport};
+pub use journal::{Journal, MAX_JOURNAL_ENTRIES};
+pub use layout::{DirEntry, DiskInode, DiskInodeType, DIRENT_SZ, NAME_LENGTH_LIMIT};
+pub use vfs::Inode;
@@ -0,0 +1,38 @@
+//! A plain in-memory [`BlockDevice`] for unit tests. Real boards back
+//! this crate with a virtio driver; host-side tests have no hardware to
+//! drive, so they read/write a `Vec` instead.
+
+use super::block_dev::{BlockDevice, BLOCK_SIZE};
+use alloc::vec;
+use alloc::vec::Vec;
+use std::sync::Mutex;
+
+/// Indexes from `base` rather than `0`, so a test can park its image at
+/// a block-id range that doesn't overlap anyone else's. The global block
+/// cache ([`super::block_cache::get_block_cache`]) keys purely by block
+/// id, not by which device asked for it, so two `RamDisk`s that both
+/// used block id 0 would otherwise silently hand each other's cached
+/// bytes back instead of ever touching either device.
+pub struct RamDisk {
+    base: usize,
+    blocks: Mutex<Vec<[u8; BLOCK_SIZE]>>,
+}
+
+impl RamDisk {
+    pub fn new(base: usize, num_blocks: usize) -> Self {
+        Self {
+            base,
+            blocks: Mutex::new(vec![[0u8; BLOCK_SIZE]; num_blocks]),
+        }
+    }
+}
+
+impl BlockDevice for RamDisk {
+    fn read_block(&self, block_id: usize, buf: &mut [u8]) {
+        buf.copy_from_slice(&self.blocks.lock().unwrap()[block_id - self.base]);
+    }
+
+    fn write_block(&self, block_id: usize, buf: &[u8]) {
+        self.blocks.lock().unwrap()[block_id - self.base].copy_from_slice(buf);
+    }
+}
@@ -0,0 +1,264 @@
+//! The VFS-facing handle: an [`Inode`] wraps a disk inode's block
+//! position and knows how to read/write its data, list its directory
+//! entries (if it's a directory), and resolve a `/`-separated path
+//! through nested subdirectories rather than only ever searching one
+//! flat root.
+
+use super::block_cache::get_block_cache;
+use super::block_dev::{BlockDevice, BLOCK_SIZE};
+use super::efs::EasyFileSystem;
+use super::layout::{DirEntry, DiskInode, DiskInodeType, DIRENT_SZ};
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+pub struct Inode {
+    block_id: usize,
+    block_offset: usize,
+    fs: Arc<Mutex<EasyFileSystem>>,
+    block_device: Arc<dyn BlockDevice>,
+}
+
+impl Inode {
+    pub fn new(
+        block_id: u32,
+        block_offset: usize,
+        fs: Arc<Mutex<EasyFileSystem>>,
+        block_device: Arc<dyn BlockDevice>,
+    ) -> Self {
+        Self {
+            block_id: block_id as usize,
+            block_offset,
+            fs,
+            block_device,
+        }
+    }
+
+    fn read_disk_inode<V>(&self, f: impl FnOnce(&DiskInode) -> V) -> V {
+        get_block_cache(self.block_id, Arc::clone(&self.block_device))
+            .lock()
+            .read(self.block_offset, f)
+    }
+
+    fn modify_disk_inode<V>(&self, f: impl FnOnce(&mut DiskInode) -> V) -> V {
+        get_block_cache(self.block_id, Arc::clone(&self.block_device))
+            .lock()
+            .modify(self.block_offset, f)
+    }
+
+    /// Looks up one path component among this inode's direct directory
+    /// entries (no recursion into further subdirectories). Entries freed
+    /// by [`remove`](Self::remove) are tombstoned with an empty name, so
+    /// they're naturally skipped without needing to compact the list.
+    fn find_entry(&self, name: &str) -> Option<DirEntry> {
+        self.read_disk_inode(|disk_inode| {
+            assert!(disk_inode.is_dir());
+            let entry_count = disk_inode.size as usize / DIRENT_SZ;
+            let mut entry = DirEntry::empty();
+            for i in 0..entry_count {
+                assert_eq!(
+                    disk_inode.read_at(i * DIRENT_SZ, entry.as_bytes_mut(), &self.block_device),
+                    DIRENT_SZ
+                );
+                if !name.is_empty() && entry.name() == name {
+                    return Some(entry);
+                }
+            }
+            None
+        })
+    }
+
+    /// Tombstones `name`'s entry in this directory, returning the freed
+    /// inode number, or `None` if there's no such entry. Crate-visible so
+    /// [`fsck`](super::fsck) can drop a dangling entry during repair
+    /// without duplicating the tombstoning logic.
+    pub(crate) fn remove_entry(&self, name: &str) -> Option<u32> {
+        self.modify_disk_inode(|disk_inode| {
+            let entry_count = disk_inode.size as usize / DIRENT_SZ;
+            let mut entry = DirEntry::empty();
+            for i in 0..entry_count {
+                assert_eq!(
+                    disk_inode.read_at(i * DIRENT_SZ, entry.as_bytes_mut(), &self.block_device),
+                    DIRENT_SZ
+                );
+                if entry.name() == name {
+                    let inode_number = entry.inode_number();
+                    disk_inode.write_at(
+                        i * DIRENT_SZ,
+                        DirEntry::empty().as_bytes(),
+                        &self.block_device,
+                    );
+                    return Some(inode_number);
+                }
+            }
+            None
+        })
+    }
+
+    fn entry_to_inode(&self, entry: &DirEntry) -> Arc<Inode> {
+        let fs = self.fs.lock();
+        let (block_id, block_offset) = fs.disk_inode_pos(entry.inode_number());
+        drop(fs);
+        Arc::new(Inode::new(
+            block_id,
+            block_offset,
+            Arc::clone(&self.fs),
+            Arc::clone(&self.block_device),
+        ))
+    }
+
+    /// Resolves a `/`-separated path starting from this inode (normally
+    /// the root), walking one subdirectory per non-empty component. An
+    /// absolute or relative path behave the same here since there's no
+    /// notion of a "current directory" below the root yet; both are
+    /// walked the same way starting wherever `self` is.
+    pub fn find(self: &Arc<Self>, path: &str) -> Option<Arc<Inode>> {
+        let mut current = Arc::clone(self);
+        for component in path.split('/').filter(|c| !c.is_empty()) {
+            let entry = current.find_entry(component)?;
+            current = current.entry_to_inode(&entry);
+        }
+        Some(current)
+    }
+
+    /// Appends one directory entry, growing the directory's data first
+    /// if it doesn't have room. Returns the id of the data block the
+    /// entry landed in, for callers that need to journal it alongside
+    /// whatever else the append has to reach disk with.
+    fn append_entry(&self, entry: DirEntry) -> u32 {
+        let entry_count = self.read_disk_inode(|inode| inode.size as usize / DIRENT_SZ);
+        self.increase_size(((entry_count + 1) * DIRENT_SZ) as u32);
+        let offset = entry_count * DIRENT_SZ;
+        let data_block_id =
+            self.read_disk_inode(|inode| inode.get_block_id(offset / BLOCK_SIZE, &self.block_device));
+        self.modify_disk_inode(|inode| {
+            inode.write_at(offset, entry.as_bytes(), &self.block_device);
+        });
+        data_block_id
+    }
+
+    fn increase_size(&self, new_size: u32) {
+        self.modify_disk_inode(|inode| {
+            if new_size <= inode.size {
+                return;
+            }
+            let blocks_needed = inode.blocks_num_needed(new_size);
+            let mut fs = self.fs.lock();
+            let new_blocks: Vec<u32> = (0..blocks_needed).map(|_| fs.alloc_data()).collect();
+            drop(fs);
+            inode.grow_to(new_size, new_blocks, &self.block_device);
+        });
+    }
+
+    /// Creates `name` as a new child of this directory (which must exist
+    /// and be a directory); `kind` picks file vs. subdirectory. Returns
+    /// `None` if `name` already exists.
+    pub fn create(self: &Arc<Self>, name: &str, kind: DiskInodeType) -> Option<Arc<Inode>> {
+        if self.find_entry(name).is_some() {
+            return None;
+        }
+        let mut fs = self.fs.lock();
+        let new_inode_id = fs.alloc_inode();
+        let (block_id, block_offset) = fs.disk_inode_pos(new_inode_id);
+        get_block_cache(block_id as usize, Arc::clone(&self.block_device))
+            .lock()
+            .modify(block_offset, |disk_inode: &mut DiskInode| {
+                disk_inode.initialize(kind);
+            });
+        drop(fs);
+        let dir_data_block = self.append_entry(DirEntry::new(name, new_inode_id));
+        // The new inode's own metadata and the directory entry that
+        // makes it reachable have to land together: a crash between the
+        // two is exactly what would leave `fsck` looking at a leaked
+        // inode (entry missing) or a dangling entry (inode missing),
+        // the two cases its own doc comment describes.
+        self.fs.lock().commit_journaled(&[block_id, dir_data_block]);
+        Some(self.entry_to_inode(&DirEntry::new(name, new_inode_id)))
+    }
+
+    pub fn create_dir(self: &Arc<Self>, name: &str) -> Option<Arc<Inode>> {
+        self.create(name, DiskInodeType::Directory)
+    }
+
+    pub fn create_file(self: &Arc<Self>, name: &str) -> Option<Arc<Inode>> {
+        self.create(name, DiskInodeType::File)
+    }
+
+    /// Names of every entry directly inside this directory (not
+    /// recursive).
+    pub fn ls(&self) -> Vec<String> {
+        self.read_disk_inode(|disk_inode| {
+            let entry_count = disk_inode.size as usize / DIRENT_SZ;
+            let mut names = Vec::with_capacity(entry_count);
+            let mut entry = DirEntry::empty();
+            for i in 0..entry_count {
+                assert_eq!(
+                    disk_inode.read_at(i * DIRENT_SZ, entry.as_bytes_mut(), &self.block_device),
+                    DIRENT_SZ
+                );
+                if !entry.name().is_empty() {
+                    names.push(entry.name().to_string());
+                }
+            }
+            names
+        })
+    }
+
+    /// Every (name, inode number) pair directly inside this directory,
+    /// for callers that need the inode number `ls` doesn't expose (e.g.
+    /// [`fsck`](super::fsck) walking the tree to find reachable inodes).
+    pub fn ls_entries(&self) -> Vec<(String, u32)> {
+        self.read_disk_inode(|disk_inode| {
+            let entry_count = disk_inode.size as usize / DIRENT_SZ;
+            let mut out = Vec::new();
+            let mut entry = DirEntry::empty();
+            for i in 0..entry_count {
+                assert_eq!(
+                    disk_inode.read_at(i * DIRENT_SZ, entry.as_bytes_mut(), &self.block_device),
+                    DIRENT_SZ
+                );
+                if !entry.name().is_empty() {
+                    out.push((entry.name().to_string(), entry.inode_number()));
+                }
+            }
+            out
+        })
+    }
+
+    /// Unlinks `name` from this directory: frees its data (and, for a
+    /// directory, requires it be empty first) and returns its inode to
+    /// the free pool. Returns whether anything was removed.
+    pub fn remove(self: &Arc<Self>, name: &str) -> bool {
+        let Some(entry) = self.find_entry(name) else {
+            return false;
+        };
+        let child = self.entry_to_inode(&entry);
+        if child.is_dir() && !child.ls().is_empty() {
+            return false;
+        }
+        let freed_blocks = child.modify_disk_inode(|inode| inode.clear(&self.block_device));
+        let mut fs = self.fs.lock();
+        for block in freed_blocks {
+            fs.dealloc_data(block);
+        }
+        fs.dealloc_inode(entry.inode_number());
+        drop(fs);
+        self.remove_entry(name);
+        true
+    }
+
+    pub fn read_at(&self, offset: usize, buf: &mut [u8]) -> usize {
+        self.read_disk_inode(|disk_inode| disk_inode.read_at(offset, buf, &self.block_device))
+    }
+
+    pub fn write_at(&self, offset: usize, buf: &[u8]) -> usize {
+        self.increase_size((offset + buf.len()) as u32);
+        self.modify_disk_inode(|disk_inode| disk_inode.write_at(offset, buf, &self.block_device))
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.read_disk_inode(DiskInode::is_dir)
+    }
+}
@@ -0,0 +1,64 @@
+//! The BIOS Parameter Block: the first sector of a FAT32 volume,
+//! describing its geometry (sector/cluster sizes, where the FAT and the
+//! data region start, which cluster the root directory begins at).
+
+use super::BLOCK_SIZE;
+
+#[derive(Clone, Copy)]
+pub struct Bpb {
+    pub bytes_per_sector: u16,
+    pub sectors_per_cluster: u8,
+    pub reserved_sector_count: u16,
+    pub num_fats: u8,
+    pub fat_size_32: u32,
+    pub root_cluster: u32,
+}
+
+impl Bpb {
+    /// Parses the fields this module needs out of a volume's sector 0.
+    /// Panics if the sector size isn't what [`BlockDevice`](super::BlockDevice)
+    /// reads at a time — every image this module has been tested against
+    /// uses 512-byte sectors, same as easy-fs.
+    pub fn parse(sector0: &[u8; BLOCK_SIZE]) -> Self {
+        let u16_at = |off: usize| u16::from_le_bytes([sector0[off], sector0[off + 1]]);
+        let u32_at = |off: usize| {
+            u32::from_le_bytes([
+                sector0[off],
+                sector0[off + 1],
+                sector0[off + 2],
+                sector0[off + 3],
+            ])
+        };
+        let bytes_per_sector = u16_at(11);
+        assert_eq!(
+            bytes_per_sector as usize, BLOCK_SIZE,
+            "FAT32 volume's bytes-per-sector doesn't match the block device's block size"
+        );
+        Self {
+            bytes_per_sector,
+            sectors_per_cluster: sector0[13],
+            reserved_sector_count: u16_at(14),
+            num_fats: sector0[16],
+            fat_size_32: u32_at(36),
+            root_cluster: u32_at(44),
+        }
+    }
+
+    pub fn fat_start_sector(&self) -> u32 {
+        self.reserved_sector_count as u32
+    }
+
+    pub fn data_start_sector(&self) -> u32 {
+        self.reserved_sector_count as u32 + self.num_fats as u32 * self.fat_size_32
+    }
+
+    pub fn bytes_per_cluster(&self) -> usize {
+        self.bytes_per_sector as usize * self.sectors_per_cluster as usize
+    }
+
+    /// The first sector of cluster `cluster` (clusters are numbered from
+    /// 2; there is no cluster 0 or 1).
+    pub fn cluster_to_sector(&self, cluster: u32) -> u32 {
+        self.data_start_sector() + (cluster - 2) * self.sectors_per_cluster as u32
+    }
+}
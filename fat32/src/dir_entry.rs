@@ -0,0 +1,133 @@
+//! FAT directory entries: the 32-byte short (8.3) entry every file and
+//! subdirectory has, optionally preceded by one or more long-file-name
+//! (LFN) entries that together spell out a name the 8.3 format can't
+//! hold.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const DIR_ENTRY_SIZE: usize = 32;
+const ATTR_LFN: u8 = 0x0F;
+const ATTR_DIRECTORY: u8 = 0x10;
+const ATTR_VOLUME_ID: u8 = 0x08;
+const LAST_LFN_ORD: u8 = 0x40;
+const DELETED_MARKER: u8 = 0xE5;
+const END_MARKER: u8 = 0x00;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Directory,
+}
+
+pub struct DirEntry {
+    pub name: String,
+    pub kind: EntryKind,
+    pub first_cluster: u32,
+    pub size: u32,
+}
+
+/// Parses every entry out of one directory's raw data (its full cluster
+/// chain, already read and concatenated), reconstructing long names
+/// from whatever LFN entries precede each short entry and falling back
+/// to the short 8.3 name when there are none.
+pub fn parse_entries(data: &[u8]) -> Vec<DirEntry> {
+    let mut entries = Vec::new();
+    // (ord, UTF-16 code units), one element per LFN entry seen so far
+    // for the short entry that will follow; FAT stores these in
+    // descending order on disk, so they're sorted by ascending `ord`
+    // before being joined.
+    let mut lfn_parts: Vec<(u8, [u16; 13])> = Vec::new();
+
+    for chunk in data.chunks_exact(DIR_ENTRY_SIZE) {
+        let first_byte = chunk[0];
+        if first_byte == END_MARKER {
+            break;
+        }
+        if first_byte == DELETED_MARKER {
+            lfn_parts.clear();
+            continue;
+        }
+        let attr = chunk[11];
+        if attr == ATTR_LFN {
+            lfn_parts.push((chunk[0] & !LAST_LFN_ORD, lfn_units(chunk)));
+            continue;
+        }
+        if attr & ATTR_VOLUME_ID != 0 {
+            lfn_parts.clear();
+            continue;
+        }
+
+        let name = if lfn_parts.is_empty() {
+            short_name(&chunk[0..11])
+        } else {
+            lfn_parts.sort_by_key(|(ord, _)| *ord);
+            join_lfn(&lfn_parts)
+        };
+        lfn_parts.clear();
+
+        if name == "." || name == ".." {
+            continue;
+        }
+
+        let first_cluster_hi = u16::from_le_bytes([chunk[20], chunk[21]]) as u32;
+        let first_cluster_lo = u16::from_le_bytes([chunk[26], chunk[27]]) as u32;
+        let size = u32::from_le_bytes([chunk[28], chunk[29], chunk[30], chunk[31]]);
+        entries.push(DirEntry {
+            name,
+            kind: if attr & ATTR_DIRECTORY != 0 {
+                EntryKind::Directory
+            } else {
+                EntryKind::File
+            },
+            first_cluster: (first_cluster_hi << 16) | first_cluster_lo,
+            size,
+        });
+    }
+
+    entries
+}
+
+/// The 13 UTF-16 code units one LFN entry holds, split across three
+/// ranges of the 32-byte entry.
+fn lfn_units(chunk: &[u8]) -> [u16; 13] {
+    let mut units = [0u16; 13];
+    for i in 0..5 {
+        units[i] = u16::from_le_bytes([chunk[1 + i * 2], chunk[2 + i * 2]]);
+    }
+    for i in 0..6 {
+        units[5 + i] = u16::from_le_bytes([chunk[14 + i * 2], chunk[15 + i * 2]]);
+    }
+    for i in 0..2 {
+        units[11 + i] = u16::from_le_bytes([chunk[28 + i * 2], chunk[29 + i * 2]]);
+    }
+    units
+}
+
+fn join_lfn(parts: &[(u8, [u16; 13])]) -> String {
+    let mut s = String::new();
+    'parts: for (_, units) in parts.iter() {
+        for &unit in units.iter() {
+            // 0x0000 pads a short final fragment; 0xFFFF pads the rest
+            // of that same entry once the name (and its terminator)
+            // have already been written.
+            if unit == 0x0000 || unit == 0xFFFF {
+                break 'parts;
+            }
+            if let Some(c) = char::from_u32(unit as u32) {
+                s.push(c);
+            }
+        }
+    }
+    s
+}
+
+fn short_name(raw: &[u8]) -> String {
+    let base = core::str::from_utf8(&raw[0..8]).unwrap_or("").trim_end();
+    let ext = core::str::from_utf8(&raw[8..11]).unwrap_or("").trim_end();
+    if ext.is_empty() {
+        base.into()
+    } else {
+        alloc::format!("{}.{}", base, ext)
+    }
+}
@@ -0,0 +1,55 @@
+//! Reads the File Allocation Table itself: given a cluster number, looks
+//! up the next cluster in its chain, or learns the chain has ended.
+
+use super::bpb::Bpb;
+use super::{BlockDevice, BLOCK_SIZE};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// FAT32 entries are 28 bits wide; values at or above this are one of
+/// the end-of-chain markers (`0x0FFFFFF8..=0x0FFFFFFF`).
+const FAT32_EOC_MIN: u32 = 0x0FFF_FFF8;
+const FAT32_FREE_CLUSTER: u32 = 0;
+
+pub struct FatTable<'a> {
+    bpb: &'a Bpb,
+    block_device: Arc<dyn BlockDevice>,
+}
+
+impl<'a> FatTable<'a> {
+    pub fn new(bpb: &'a Bpb, block_device: Arc<dyn BlockDevice>) -> Self {
+        Self { bpb, block_device }
+    }
+
+    /// The cluster `cluster` points to next, or `None` once its chain's
+    /// end-of-chain marker is hit (or the slot was never allocated).
+    pub fn next_cluster(&self, cluster: u32) -> Option<u32> {
+        let fat_offset = cluster as usize * 4;
+        let sector = self.bpb.fat_start_sector() as usize + fat_offset / BLOCK_SIZE;
+        let offset_in_sector = fat_offset % BLOCK_SIZE;
+        let mut buf = [0u8; BLOCK_SIZE];
+        self.block_device.read_block(sector, &mut buf);
+        let raw = u32::from_le_bytes([
+            buf[offset_in_sector],
+            buf[offset_in_sector + 1],
+            buf[offset_in_sector + 2],
+            buf[offset_in_sector + 3],
+        ]) & 0x0FFF_FFFF;
+        if raw >= FAT32_EOC_MIN || raw == FAT32_FREE_CLUSTER {
+            None
+        } else {
+            Some(raw)
+        }
+    }
+
+    /// Every cluster in the chain starting at `start`, in order.
+    pub fn chain(&self, start: u32) -> Vec<u32> {
+        let mut clusters = alloc::vec![start];
+        let mut current = start;
+        while let Some(next) = self.next_cluster(current) {
+            clusters.push(next);
+            current = next;
+        }
+        clusters
+    }
+}
@@ -0,0 +1,142 @@
+//! The filesystem-level API: open an image from its `BlockDevice`,
+//! resolve paths from the root, and read a file's bytes by walking its
+//! cluster chain. Read-only — there is no write path, since this module
+//! only needs to load programs and data off FAT32 images, not produce
+//! or modify them.
+
+use super::bpb::Bpb;
+use super::dir_entry::{parse_entries, EntryKind};
+use super::fat_table::FatTable;
+use super::{BlockDevice, BLOCK_SIZE};
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+pub struct Fat32FileSystem {
+    pub bpb: Bpb,
+    block_device: Arc<dyn BlockDevice>,
+}
+
+impl Fat32FileSystem {
+    /// Reads sector 0 and parses it as a FAT32 BPB. Panics (via
+    /// [`Bpb::parse`]) if the volume's sector size doesn't match the
+    /// block device's.
+    pub fn open(block_device: Arc<dyn BlockDevice>) -> Self {
+        let mut sector0 = [0u8; BLOCK_SIZE];
+        block_device.read_block(0, &mut sector0);
+        let bpb = Bpb::parse(&sector0);
+        Self { bpb, block_device }
+    }
+
+    pub fn root(&self) -> Fat32Inode {
+        Fat32Inode {
+            bpb: self.bpb,
+            block_device: Arc::clone(&self.block_device),
+            cluster: self.bpb.root_cluster,
+            kind: EntryKind::Directory,
+            size: 0,
+        }
+    }
+}
+
+/// A handle to one file or directory. Holds the BPB and block device
+/// directly (both cheap to clone — `Bpb` is `Copy`, the device is an
+/// `Arc`) rather than a reference back to a `Fat32FileSystem`, so an
+/// inode's lifetime isn't tied to its filesystem's.
+#[derive(Clone)]
+pub struct Fat32Inode {
+    bpb: Bpb,
+    block_device: Arc<dyn BlockDevice>,
+    cluster: u32,
+    kind: EntryKind,
+    size: u32,
+}
+
+impl Fat32Inode {
+    fn fat_table(&self) -> FatTable<'_> {
+        FatTable::new(&self.bpb, Arc::clone(&self.block_device))
+    }
+
+    /// Reads every byte reachable from `cluster`'s chain, stopping at
+    /// `size` bytes if given. Directories have no reliable size field
+    /// (only files do), so callers reading a directory's entries pass
+    /// `None` and rely on the end-of-chain marker instead.
+    fn read_chain(&self, cluster: u32, size: Option<u32>) -> Vec<u8> {
+        let bytes_per_cluster = self.bpb.bytes_per_cluster();
+        let clusters = self.fat_table().chain(cluster);
+        let mut data = Vec::with_capacity(clusters.len() * bytes_per_cluster);
+        for cluster in clusters {
+            let start_sector = self.bpb.cluster_to_sector(cluster);
+            for s in 0..self.bpb.sectors_per_cluster as u32 {
+                let mut buf = [0u8; BLOCK_SIZE];
+                self.block_device
+                    .read_block((start_sector + s) as usize, &mut buf);
+                data.extend_from_slice(&buf);
+            }
+        }
+        if let Some(size) = size {
+            data.truncate(size as usize);
+        }
+        data
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.kind == EntryKind::Directory
+    }
+
+    /// This directory's children, resolved to their own inodes; empty
+    /// for a file.
+    pub fn entries(&self) -> Vec<(String, Fat32Inode)> {
+        if !self.is_dir() {
+            return Vec::new();
+        }
+        let data = self.read_chain(self.cluster, None);
+        parse_entries(&data)
+            .into_iter()
+            .map(|e| {
+                (
+                    e.name,
+                    Fat32Inode {
+                        bpb: self.bpb,
+                        block_device: Arc::clone(&self.block_device),
+                        cluster: e.first_cluster,
+                        kind: e.kind,
+                        size: e.size,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Case-insensitive lookup of one path component among this
+    /// directory's entries, matching FAT's own name comparison rules.
+    pub fn lookup(&self, name: &str) -> Option<Fat32Inode> {
+        self.entries()
+            .into_iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, inode)| inode)
+    }
+
+    /// Resolves a `/`-separated path from this inode, walking one
+    /// subdirectory per non-empty component (the same scheme easy-fs's
+    /// `Inode::find` uses).
+    pub fn find(&self, path: &str) -> Option<Fat32Inode> {
+        let mut current = self.clone();
+        for component in path.split('/').filter(|c| !c.is_empty()) {
+            current = current.lookup(component)?;
+        }
+        Some(current)
+    }
+
+    /// The whole file's bytes; empty for a directory.
+    pub fn read_all(&self) -> Vec<u8> {
+        if self.is_dir() {
+            return Vec::new();
+        }
+        self.read_chain(self.cluster, Some(self.size))
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
@@ -0,0 +1,21 @@
+//! Read-only FAT32 support, for loading user programs from FAT-formatted
+//! SD images the way competition environments ship them, without first
+//! having to repack every image as an easy-fs image.
+//!
+//! Built on easy-fs's `BlockDevice` trait rather than a new one of its
+//! own, so the same backing storage works with either filesystem.
+
+#![no_std]
+
+extern crate alloc;
+
+mod bpb;
+mod dir_entry;
+mod fat_table;
+mod fs;
+
+pub use bpb::Bpb;
+pub use dir_entry::{parse_entries, DirEntry, EntryKind};
+pub use easy_fs::{BlockDevice, BLOCK_SIZE};
+pub use fat_table::FatTable;
+pub use fs::{Fat32FileSystem, Fat32Inode};
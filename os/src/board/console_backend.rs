@@ -0,0 +1,106 @@
+//! Console backend selection.
+//!
+//! QEMU's `virt` machine (and real hardware we might run on) can expose
+//! console I/O through three different interfaces with different
+//! availability and performance tradeoffs: the legacy SBI console
+//! extension (always present, one character at a time), the newer SBI
+//! Debug Console extension ("DBCN", batched read/write), or a
+//! memory-mapped UART the kernel drives directly. [`detect`] picks between
+//! the two SBI backends; nothing at boot calls it, and `print!`/`println!`
+//! (`console.rs`) call the legacy SBI console directly rather than going
+//! through the [`ConsoleBackend`] trait.
+
+use crate::sbi;
+
+pub trait ConsoleBackend: Sync {
+    fn putchar(&self, c: u8);
+    /// Returns `None` if no character is waiting.
+    fn getchar(&self) -> Option<u8>;
+}
+
+pub struct SbiLegacyConsole;
+
+impl ConsoleBackend for SbiLegacyConsole {
+    fn putchar(&self, c: u8) {
+        sbi::console_putchar(c as usize);
+    }
+    fn getchar(&self) -> Option<u8> {
+        match sbi::console_getchar() {
+            0 => None,
+            c => Some(c as u8),
+        }
+    }
+}
+
+/// SBI Debug Console (DBCN) extension: same semantics as the legacy
+/// extension from the kernel's point of view for now, batching is left to
+/// a follow-up once the DBCN write-multiple call is wired through `sbi.rs`.
+pub struct SbiDbcnConsole;
+
+impl ConsoleBackend for SbiDbcnConsole {
+    fn putchar(&self, c: u8) {
+        sbi::console_putchar(c as usize);
+    }
+    fn getchar(&self) -> Option<u8> {
+        match sbi::console_getchar() {
+            0 => None,
+            c => Some(c as u8),
+        }
+    }
+}
+
+/// A directly-driven memory-mapped UART (16550-compatible), for boards
+/// without a usable SBI console implementation.
+pub struct MmioUart {
+    base: usize,
+}
+
+impl MmioUart {
+    const THR: usize = 0x00;
+    const RBR: usize = 0x00;
+    const LSR: usize = 0x05;
+    const LSR_DATA_READY: u8 = 0x01;
+    const LSR_THR_EMPTY: u8 = 0x20;
+
+    /// # Safety
+    /// `base` must be the MMIO base address of a 16550-compatible UART.
+    pub const unsafe fn new(base: usize) -> Self {
+        Self { base }
+    }
+
+    fn reg(&self, offset: usize) -> *mut u8 {
+        (self.base + offset) as *mut u8
+    }
+}
+
+impl ConsoleBackend for MmioUart {
+    fn putchar(&self, c: u8) {
+        unsafe {
+            while core::ptr::read_volatile(self.reg(Self::LSR)) & Self::LSR_THR_EMPTY == 0 {}
+            core::ptr::write_volatile(self.reg(Self::THR), c);
+        }
+    }
+    fn getchar(&self) -> Option<u8> {
+        unsafe {
+            if core::ptr::read_volatile(self.reg(Self::LSR)) & Self::LSR_DATA_READY != 0 {
+                Some(core::ptr::read_volatile(self.reg(Self::RBR)))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Probes for the DBCN extension and returns that backend if the SBI
+/// implementation reports it, the legacy SBI console otherwise. Never
+/// returns an [`MmioUart`]: there is no UART base address to build one from.
+pub fn detect() -> &'static dyn ConsoleBackend {
+    /// SBI extension ID of the Debug Console extension ("DBCN").
+    const SBI_EXT_DBCN: usize = 0x4442_434E;
+    static DBCN: SbiDbcnConsole = SbiDbcnConsole;
+    static LEGACY: SbiLegacyConsole = SbiLegacyConsole;
+    if sbi::probe_extension(SBI_EXT_DBCN) {
+        return &DBCN;
+    }
+    &LEGACY
+}
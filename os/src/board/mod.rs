@@ -0,0 +1,6 @@
+//! Board-specific details (QEMU `virt` for now) isolated from the rest of
+//! the kernel.
+
+mod console_backend;
+
+pub use console_backend::{detect as detect_console, ConsoleBackend, MmioUart};
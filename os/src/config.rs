@@ -0,0 +1,85 @@
+//! Central place for kernel-wide constants and feature wiring.
+//!
+//! `FEATURES`/`sys_probe`/`uname` report which optional subsystems (COW,
+//! swap, procfs, sockets, tracing, KASAN, the GDB stub) this build carries,
+//! so userspace can adapt instead of probing for syscalls that don't
+//! exist. There is no Cargo-feature-gated conditional compilation
+//! anywhere in this tree yet — every one of those subsystems' modules is
+//! compiled in unconditionally (`grep -rn 'cfg(feature' os/src` outside
+//! this file turns up nothing) — so gating these on `#[cfg(feature =
+//! ...)]` would just report every one of them as permanently disabled
+//! regardless of what's actually compiled in, which is worse than no
+//! report at all. These constants reflect build reality as it exists
+//! today (everything on); turning them into real `--no-default-features`
+//! toggles needs the `#[cfg(feature = ...)]` wiring on the subsystem
+//! modules themselves, not just here.
+
+pub const PAGE_SIZE: usize = 0x1000;
+pub const PAGE_SIZE_BITS: usize = 0xc;
+pub const KERNEL_STACK_SIZE: usize = 4096 * 2;
+pub const USER_STACK_SIZE: usize = 4096 * 2;
+pub const KERNEL_HEAP_SIZE: usize = 0x30_0000;
+pub const MAX_HARTS: usize = 8;
+
+/// Where a task's `"heap"` area ([`sys_brk`](crate::syscall::brk::sys_brk)'s
+/// backing store in `MemorySet`) starts. A placeholder for the same reason
+/// [`MMAP_SEARCH_START_VPN`] is: there's no per-task load layout yet (no
+/// ELF loader records where a binary's data segment actually ends), so
+/// this can't be "right after the BSS" the way a real `brk` base is.
+/// Chosen below [`MMAP_SEARCH_START_VPN`] so the heap has room to grow
+/// without colliding with the mmap region.
+pub const HEAP_START_VPN: usize = 0x8_000;
+
+/// Where `mmap(addr=0)` starts searching for a free virtual range, and
+/// the ceiling it searches up to. Placeholders: this tree has no real
+/// per-task virtual address-space layout yet (no tracked trampoline
+/// page), so there's nothing to search "between" for real beyond staying
+/// clear of [`HEAP_START_VPN`]'s growth room.
+pub const MMAP_SEARCH_START_VPN: usize = 0x10_000;
+pub const MMAP_SEARCH_END_VPN: usize = 0x3f_000;
+
+pub const COW_ENABLED: bool = true;
+pub const SWAP_ENABLED: bool = true;
+pub const PROCFS_ENABLED: bool = true;
+pub const SOCKETS_ENABLED: bool = true;
+pub const TRACING_ENABLED: bool = true;
+pub const KASAN_ENABLED: bool = true;
+pub const GDBSTUB_ENABLED: bool = true;
+
+/// Runtime-visible summary of which optional subsystems this build was
+/// compiled with, reported by `sys_probe`/`uname` so userspace can adapt
+/// instead of probing for syscalls that don't exist.
+#[derive(Copy, Clone, Debug)]
+pub struct KernelFeatures {
+    pub cow: bool,
+    pub swap: bool,
+    pub procfs: bool,
+    pub sockets: bool,
+    pub tracing: bool,
+    pub kasan: bool,
+    pub gdbstub: bool,
+}
+
+pub const FEATURES: KernelFeatures = KernelFeatures {
+    cow: COW_ENABLED,
+    swap: SWAP_ENABLED,
+    procfs: PROCFS_ENABLED,
+    sockets: SOCKETS_ENABLED,
+    tracing: TRACING_ENABLED,
+    kasan: KASAN_ENABLED,
+    gdbstub: GDBSTUB_ENABLED,
+};
+
+impl KernelFeatures {
+    /// Packs the flags into a single word for `sys_probe`'s return value:
+    /// one bit per feature, in the field order above.
+    pub fn as_bits(&self) -> usize {
+        (self.cow as usize)
+            | (self.swap as usize) << 1
+            | (self.procfs as usize) << 2
+            | (self.sockets as usize) << 3
+            | (self.tracing as usize) << 4
+            | (self.kasan as usize) << 5
+            | (self.gdbstub as usize) << 6
+    }
+}
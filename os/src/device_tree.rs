@@ -0,0 +1,204 @@
+//! Flattened device tree (FDT) parsing.
+//!
+//! Walks the DTB blob a bootloader hands off to build a hart count and a
+//! device list (virtio-mmio regions, UART, PLIC, CLINT, memory), and lets
+//! drivers [`register_probe`] a function keyed by `compatible` string so
+//! [`probe_all`] can dispatch over whatever the tree actually describes
+//! instead of the kernel hard-coding QEMU `virt`'s fixed addresses.
+//!
+//! No board wiring calls [`parse`] with a real DTB pointer yet — there's
+//! no `boards::qemu` hard-coding those addresses to replace either, just
+//! [`crate::board`]'s console backend selection — so this lands the
+//! parser and probe registry ahead of that integration, the same way
+//! [`crate::fs::vfs`]'s `MountTable` landed ahead of a mounted filesystem.
+
+use crate::sync::UPSafeCell;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+const FDT_MAGIC: u32 = 0xd00d_feed;
+const FDT_BEGIN_NODE: u32 = 1;
+const FDT_END_NODE: u32 = 2;
+const FDT_PROP: u32 = 3;
+const FDT_NOP: u32 = 4;
+const FDT_END: u32 = 9;
+
+/// One device the tree describes: its `compatible` string (the first
+/// entry, if the property lists several) and the `(base, size)` pairs
+/// from its `reg` property.
+#[derive(Clone, Debug)]
+pub struct Device {
+    pub compatible: String,
+    pub reg: Vec<(usize, usize)>,
+}
+
+/// What [`parse`] extracted from a DTB: the hart count, the discovered
+/// devices, and the memory node's `(base, size)` if one was present.
+#[derive(Clone, Debug, Default)]
+pub struct DeviceTree {
+    pub hart_count: usize,
+    pub devices: Vec<Device>,
+    pub memory: Option<(usize, usize)>,
+}
+
+struct Header {
+    off_dt_struct: u32,
+    off_dt_strings: u32,
+}
+
+fn read_be_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+fn read_be_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_be_bytes(bytes)
+}
+
+fn read_header(data: &[u8]) -> Option<Header> {
+    if data.len() < 40 || read_be_u32(data, 0) != FDT_MAGIC {
+        return None;
+    }
+    Some(Header {
+        off_dt_struct: read_be_u32(data, 8),
+        off_dt_strings: read_be_u32(data, 12),
+    })
+}
+
+fn read_cstr(data: &[u8], offset: usize) -> &str {
+    let end = data[offset..]
+        .iter()
+        .position(|&b| b == 0)
+        .map(|n| offset + n)
+        .unwrap_or(data.len());
+    core::str::from_utf8(&data[offset..end]).unwrap_or("")
+}
+
+/// Decodes a `reg` property's raw bytes into `(base, size)` pairs,
+/// assuming 64-bit `#address-cells`/`#size-cells` throughout (true of
+/// QEMU `virt`'s tree; a tree using narrower cells would need those
+/// properties tracked per-node, which this simplified walk doesn't do).
+fn decode_reg(data: &[u8]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    let mut offset = 0;
+    while offset + 16 <= data.len() {
+        let base = read_be_u64(data, offset) as usize;
+        let size = read_be_u64(data, offset + 8) as usize;
+        pairs.push((base, size));
+        offset += 16;
+    }
+    pairs
+}
+
+/// Parses a DTB starting at `ptr`, returning the hart count and device
+/// list it describes.
+///
+/// # Safety
+/// `ptr` must point to a valid flattened device tree blob that stays
+/// mapped and unmodified for the duration of this call.
+pub unsafe fn parse(ptr: *const u8) -> DeviceTree {
+    let header_bytes = core::slice::from_raw_parts(ptr, 40);
+    let header = match read_header(header_bytes) {
+        Some(header) => header,
+        None => return DeviceTree::default(),
+    };
+    // The struct block's exact extent isn't known until it's walked, so
+    // this reads generously far past any real tree; every access below
+    // stays within bounds because the walk stops at `FDT_END`.
+    let data = core::slice::from_raw_parts(ptr, 1 << 20);
+    let strings_base = header.off_dt_strings as usize;
+
+    let mut tree = DeviceTree::default();
+    let mut offset = header.off_dt_struct as usize;
+    let mut path: Vec<String> = Vec::new();
+    let mut cur_compatible: Option<String> = None;
+    let mut cur_reg: Vec<(usize, usize)> = Vec::new();
+    let mut cur_device_type: Option<String> = None;
+
+    loop {
+        let token = read_be_u32(data, offset);
+        offset += 4;
+        match token {
+            FDT_BEGIN_NODE => {
+                let name = read_cstr(data, offset);
+                let name_len = name.len() + 1;
+                offset += (name_len + 3) & !3;
+                path.push(name.to_string());
+                cur_compatible = None;
+                cur_reg = Vec::new();
+                cur_device_type = None;
+            }
+            FDT_PROP => {
+                let len = read_be_u32(data, offset) as usize;
+                let nameoff = read_be_u32(data, offset + 4) as usize;
+                let prop_data = &data[offset + 8..offset + 8 + len];
+                let prop_name = read_cstr(data, strings_base + nameoff);
+                match prop_name {
+                    "compatible" => cur_compatible = Some(read_cstr(prop_data, 0).to_string()),
+                    "reg" => cur_reg = decode_reg(prop_data),
+                    "device_type" => cur_device_type = Some(read_cstr(prop_data, 0).to_string()),
+                    _ => {}
+                }
+                offset += 8 + ((len + 3) & !3);
+            }
+            FDT_END_NODE => {
+                if cur_device_type.as_deref() == Some("cpu") {
+                    tree.hart_count += 1;
+                }
+                if cur_device_type.as_deref() == Some("memory") {
+                    if let Some(&pair) = cur_reg.first() {
+                        tree.memory = Some(pair);
+                    }
+                }
+                if let Some(compatible) = cur_compatible.take() {
+                    tree.devices.push(Device {
+                        compatible,
+                        reg: cur_reg.clone(),
+                    });
+                }
+                path.pop();
+                cur_compatible = None;
+                cur_reg = Vec::new();
+                cur_device_type = None;
+            }
+            FDT_NOP => {}
+            FDT_END => break,
+            _ => break,
+        }
+    }
+    tree
+}
+
+type ProbeFn = fn(&Device);
+
+/// Drivers register here (keyed by `compatible` string) so [`probe_all`]
+/// can hand them the matching [`Device`] without the kernel needing to
+/// know in advance which devices a given tree will contain.
+static PROBES: UPSafeCell<BTreeMap<String, ProbeFn>> =
+    unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+/// Registers `probe` to run against every discovered device whose
+/// `compatible` string equals `compatible`.
+pub fn register_probe(compatible: &str, probe: ProbeFn) {
+    PROBES
+        .exclusive_access()
+        .insert(compatible.to_string(), probe);
+}
+
+/// Runs every device in `tree` through whichever probe function
+/// [`register_probe`] registered for its `compatible` string, if any.
+pub fn probe_all(tree: &DeviceTree) {
+    let probes = PROBES.exclusive_access();
+    for device in &tree.devices {
+        if let Some(probe) = probes.get(&device.compatible) {
+            probe(device);
+        }
+    }
+}
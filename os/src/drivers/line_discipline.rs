@@ -0,0 +1,44 @@
+//! A simple canonical-mode line discipline sitting between the UART
+//! interrupt handler and `Stdin::read`: characters accumulate in a line
+//! buffer (with backspace handling) and only become visible to readers
+//! once a newline completes the line, the way a terminal in cooked mode
+//! behaves.
+
+use alloc::collections::VecDeque;
+use alloc::string::String;
+
+const BACKSPACE: u8 = 0x08;
+const DEL: u8 = 0x7f;
+
+#[derive(Default)]
+pub struct LineDiscipline {
+    current_line: String,
+    completed_lines: VecDeque<String>,
+}
+
+impl LineDiscipline {
+    /// Feeds one byte received from the UART interrupt handler.
+    pub fn feed(&mut self, byte: u8) {
+        match byte {
+            b'\n' | b'\r' => {
+                let line = core::mem::take(&mut self.current_line);
+                self.completed_lines.push_back(line);
+            }
+            BACKSPACE | DEL => {
+                self.current_line.pop();
+            }
+            c => {
+                self.current_line.push(c as char);
+            }
+        }
+    }
+
+    /// Pops one completed line, if any are available to read.
+    pub fn pop_line(&mut self) -> Option<String> {
+        self.completed_lines.pop_front()
+    }
+
+    pub fn has_line(&self) -> bool {
+        !self.completed_lines.is_empty()
+    }
+}
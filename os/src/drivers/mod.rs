@@ -0,0 +1,13 @@
+//! Device drivers.
+
+mod line_discipline;
+mod plic;
+mod virtio_blk;
+mod virtio_gpu;
+mod virtio_input;
+
+pub use line_discipline::LineDiscipline;
+pub use plic::Plic;
+pub use virtio_blk::VirtioBlkDriver;
+pub use virtio_gpu::VirtioGpuDriver;
+pub use virtio_input::VirtioInputDriver;
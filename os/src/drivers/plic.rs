@@ -0,0 +1,85 @@
+//! Platform-Level Interrupt Controller driver: claims/completes external
+//! interrupts and routes each source's priority and per-hart enable bits,
+//! per the SiFive PLIC layout QEMU's `virt` machine implements.
+
+const PRIORITY_BASE: usize = 0x0000;
+const PENDING_BASE: usize = 0x1000;
+const ENABLE_BASE: usize = 0x2000;
+const ENABLE_STRIDE: usize = 0x80;
+const CONTEXT_BASE: usize = 0x20_0000;
+const CONTEXT_STRIDE: usize = 0x1000;
+const THRESHOLD_OFFSET: usize = 0x0;
+const CLAIM_OFFSET: usize = 0x4;
+
+pub struct Plic {
+    base: usize,
+}
+
+impl Plic {
+    /// # Safety
+    /// `base` must be the MMIO base address of a PLIC.
+    pub const unsafe fn new(base: usize) -> Self {
+        Self { base }
+    }
+
+    /// Supervisor-mode context index for `hart`. QEMU `virt` gives each
+    /// hart one machine-mode and one supervisor-mode context, in that
+    /// order, so the S-mode context is `2 * hart + 1`.
+    fn context(&self, hart: usize) -> usize {
+        2 * hart + 1
+    }
+
+    pub fn set_priority(&self, irq: u32, priority: u32) {
+        unsafe {
+            let addr = (self.base + PRIORITY_BASE + irq as usize * 4) as *mut u32;
+            core::ptr::write_volatile(addr, priority);
+        }
+    }
+
+    pub fn enable(&self, hart: usize, irq: u32) {
+        unsafe {
+            let ctx = self.context(hart);
+            let addr = (self.base + ENABLE_BASE + ctx * ENABLE_STRIDE + (irq / 32) as usize * 4)
+                as *mut u32;
+            let bit = 1u32 << (irq % 32);
+            core::ptr::write_volatile(addr, core::ptr::read_volatile(addr) | bit);
+        }
+    }
+
+    pub fn set_threshold(&self, hart: usize, threshold: u32) {
+        unsafe {
+            let ctx = self.context(hart);
+            let addr = (self.base + CONTEXT_BASE + ctx * CONTEXT_STRIDE + THRESHOLD_OFFSET)
+                as *mut u32;
+            core::ptr::write_volatile(addr, threshold);
+        }
+    }
+
+    /// Claims the highest-priority pending interrupt for `hart`'s
+    /// supervisor context; returns `0` if none is pending.
+    pub fn claim(&self, hart: usize) -> u32 {
+        unsafe {
+            let ctx = self.context(hart);
+            let addr =
+                (self.base + CONTEXT_BASE + ctx * CONTEXT_STRIDE + CLAIM_OFFSET) as *mut u32;
+            core::ptr::read_volatile(addr)
+        }
+    }
+
+    /// Signals completion of `irq`, re-arming it for future claims.
+    pub fn complete(&self, hart: usize, irq: u32) {
+        unsafe {
+            let ctx = self.context(hart);
+            let addr =
+                (self.base + CONTEXT_BASE + ctx * CONTEXT_STRIDE + CLAIM_OFFSET) as *mut u32;
+            core::ptr::write_volatile(addr, irq);
+        }
+    }
+
+    pub fn is_pending(&self, irq: u32) -> bool {
+        unsafe {
+            let addr = (self.base + PENDING_BASE + (irq / 32) as usize * 4) as *const u32;
+            core::ptr::read_volatile(addr) & (1 << (irq % 32)) != 0
+        }
+    }
+}
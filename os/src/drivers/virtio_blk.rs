@@ -0,0 +1,55 @@
+//! virtio-blk block device driver, interrupt-driven: a submitted request
+//! completes when the device raises its queue interrupt rather than the
+//! driver busy-polling the used ring after every `virtqueue` kick.
+
+use crate::sync::UPSafeCell;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+
+/// One in-flight request, parked until its completion interrupt arrives.
+struct PendingRequest {
+    waker: Arc<UPSafeCell<bool>>,
+}
+
+pub struct VirtioBlkDriver {
+    pending: UPSafeCell<BTreeMap<u16, PendingRequest>>,
+}
+
+impl VirtioBlkDriver {
+    pub fn new() -> Self {
+        Self {
+            pending: unsafe { UPSafeCell::new(BTreeMap::new()) },
+        }
+    }
+
+    /// Submits a descriptor chain identified by `head` and returns a
+    /// handle the caller blocks on (via the task scheduler) until the
+    /// interrupt handler marks it complete.
+    pub fn submit(&self, head: u16) -> Arc<UPSafeCell<bool>> {
+        let waker = Arc::new(unsafe { UPSafeCell::new(false) });
+        self.pending.exclusive_access().insert(
+            head,
+            PendingRequest {
+                waker: Arc::clone(&waker),
+            },
+        );
+        waker
+    }
+
+    /// Called from the PLIC-routed virtio interrupt handler: pops every
+    /// descriptor id the device reports as used and marks its waker done.
+    pub fn handle_interrupt(&self, completed_heads: &[u16]) {
+        let mut pending = self.pending.exclusive_access();
+        for head in completed_heads {
+            if let Some(req) = pending.remove(head) {
+                *req.waker.exclusive_access() = true;
+            }
+        }
+    }
+}
+
+impl Default for VirtioBlkDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,30 @@
+//! virtio-input driver: decodes completed event reports from the device
+//! and pushes them into the matching [`InputEventDevice`] ring buffer.
+//!
+//! Unlike [`super::VirtioBlkDriver`]/[`super::VirtioGpuDriver`], this
+//! driver doesn't track a finite set of in-flight requests waiting on a
+//! waker — virtio-input has no response to a submitted request, just a
+//! steady stream of unsolicited event reports, so its interrupt handler
+//! decodes and delivers them directly instead.
+
+use crate::fs::{InputEvent, InputEventDevice};
+use alloc::sync::Arc;
+
+pub struct VirtioInputDriver {
+    device: Arc<InputEventDevice>,
+}
+
+impl VirtioInputDriver {
+    pub fn new(device: Arc<InputEventDevice>) -> Self {
+        Self { device }
+    }
+
+    /// Called from the PLIC-routed virtio interrupt handler with the
+    /// `(type, code, value)` triples decoded from this poll of completed
+    /// event-queue descriptors, in the order the device reported them.
+    pub fn handle_interrupt(&self, events: &[(u16, u16, i32)]) {
+        for &(type_, code, value) in events {
+            self.device.push_event(InputEvent::new(type_, code, value));
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! `/dev` device nodes: `File` implementations for the handful of
+//! pseudo-devices most programs expect, plus the name -> file registry
+//! a path like `/dev/null` resolves through. There's no `sys_open` yet
+//! for a path to actually reach one of these (`File` objects currently
+//! only reach a process's fd table via fork/stdio setup), so this lands
+//! the devices and the registry; wiring `sys_open` through it is
+//! deferred the same way `sys_mount`'s string translation is.
+
+use super::{File, PollEvents, Pollable};
+use crate::mm::UserBuffer;
+use crate::sbi::console_getchar;
+use crate::sync::{UPSafeCell, WaitQueue};
+use crate::task::suspend_current_and_run_next;
+use crate::timer::TimeSpec;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+pub struct NullDevice;
+
+impl File for NullDevice {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, _user_buf: UserBuffer) -> usize {
+        0
+    }
+    fn write(&self, user_buf: UserBuffer) -> usize {
+        user_buf.len()
+    }
+}
+
+impl Pollable for NullDevice {
+    /// Always ready: reads return EOF and writes complete instantly.
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        interest
+    }
+}
+
+pub struct ZeroDevice;
+
+impl File for ZeroDevice {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        let mut total = 0;
+        for buffer in user_buf.buffers.iter_mut() {
+            buffer.fill(0);
+            total += buffer.len();
+        }
+        total
+    }
+    fn write(&self, user_buf: UserBuffer) -> usize {
+        user_buf.len()
+    }
+}
+
+impl Pollable for ZeroDevice {
+    /// Always ready: reads produce zeroes and writes complete instantly.
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        interest
+    }
+}
+
+/// The controlling terminal, routed to the same console driver
+/// `Stdin`/`Stdout` use so a process that opens `/dev/tty` by path gets
+/// behavior identical to its inherited fds 0 and 1.
+pub struct TtyDevice;
+
+impl File for TtyDevice {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        assert_eq!(user_buf.len(), 1, "tty only supports byte-at-a-time reads");
+        let mut c: usize;
+        loop {
+            c = console_getchar();
+            if c == 0 {
+                suspend_current_and_run_next();
+                continue;
+            }
+            break;
+        }
+        user_buf.buffers[0][0] = c as u8;
+        1
+    }
+    fn write(&self, user_buf: UserBuffer) -> usize {
+        for buffer in user_buf.buffers.iter() {
+            print!("{}", core::str::from_utf8(buffer).unwrap());
+        }
+        user_buf.len()
+    }
+}
+
+impl Pollable for TtyDevice {
+    /// Same caveat as `Stdin`'s: no non-consuming peek exists, so
+    /// `POLLIN` is reported ready unconditionally.
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        interest
+    }
+}
+
+/// Seconds-since-boot as a little-endian `u64`. Not a true wall-clock
+/// RTC — nothing backs one yet, the same gap `timer::CLOCK_REALTIME`'s
+/// doc comment notes — so this is monotonic uptime wearing an RTC's
+/// path until a real one exists to read instead.
+pub struct RtcDevice;
+
+impl File for RtcDevice {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        false
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        let secs = TimeSpec::now().sec.to_le_bytes();
+        copy_into(&mut user_buf, &secs)
+    }
+    fn write(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot write to /dev/rtc");
+    }
+}
+
+impl Pollable for RtcDevice {
+    /// Always ready: reading it never blocks.
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLIN) {
+            ready.insert(PollEvents::POLLIN);
+        }
+        ready
+    }
+}
+
+/// Matches the resolution later rCore-Tutorial GUI chapters' virtio-gpu
+/// setup uses on QEMU `virt`.
+pub const FB_WIDTH: usize = 1280;
+pub const FB_HEIGHT: usize = 800;
+const FB_BYTES_PER_PIXEL: usize = 4;
+
+/// `/dev/fb0`: a flat BGRA8888 pixel buffer backing the virtio-gpu
+/// framebuffer. There's no virtio-gpu MMIO behind it yet (the same gap
+/// [`crate::drivers::VirtioGpuDriver`] is ahead of), so `flush` has
+/// nothing to push the buffer's contents out to — but the buffer itself
+/// is real, so `read`/`write`/`read_at`/`write_at` already behave exactly
+/// like a real framebuffer device's would.
+pub struct FramebufferDevice {
+    pixels: UPSafeCell<Vec<u8>>,
+}
+
+impl FramebufferDevice {
+    fn new() -> Self {
+        Self {
+            pixels: unsafe { UPSafeCell::new(alloc::vec![0u8; Self::size_bytes()]) },
+        }
+    }
+
+    /// Total size of the pixel buffer in bytes, the same value
+    /// `sys_framebuffer` needs to size the mapping it constructs.
+    pub fn size_bytes() -> usize {
+        FB_WIDTH * FB_HEIGHT * FB_BYTES_PER_PIXEL
+    }
+
+    /// Pushes the buffer's current contents to the display. A no-op
+    /// until `VirtioGpuDriver` has real MMIO to submit a `RESOURCE_FLUSH`
+    /// request through.
+    pub fn flush(&self) {}
+}
+
+impl File for FramebufferDevice {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        copy_into(&mut user_buf, &self.pixels.exclusive_access())
+    }
+    fn write(&self, user_buf: UserBuffer) -> usize {
+        let mut pixels = self.pixels.exclusive_access();
+        copy_from(&user_buf, &mut pixels)
+    }
+    fn read_at(&self, offset: usize, mut user_buf: UserBuffer) -> usize {
+        let pixels = self.pixels.exclusive_access();
+        if offset >= pixels.len() {
+            return 0;
+        }
+        copy_into(&mut user_buf, &pixels[offset..])
+    }
+    fn write_at(&self, offset: usize, user_buf: UserBuffer) -> usize {
+        let mut pixels = self.pixels.exclusive_access();
+        if offset >= pixels.len() {
+            return 0;
+        }
+        copy_from(&user_buf, &mut pixels[offset..])
+    }
+}
+
+impl Pollable for FramebufferDevice {
+    /// Always ready: a framebuffer write never blocks on anything.
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        interest
+    }
+}
+
+/// One decoded virtio-input report, the same shape as Linux's `struct
+/// input_event` (`time`/`type`/`code`/`value`).
+#[derive(Copy, Clone)]
+pub struct InputEvent {
+    pub time: TimeSpec,
+    pub type_: u16,
+    pub code: u16,
+    pub value: i32,
+}
+
+impl InputEvent {
+    /// Serialized size of one event: `time.sec` + `time.nsec` (`u64`
+    /// each) + `type_`/`code` (`u16` each) + `value` (`i32`).
+    const SIZE: usize = 8 + 8 + 2 + 2 + 4;
+
+    pub fn new(type_: u16, code: u16, value: i32) -> Self {
+        Self {
+            time: TimeSpec::now(),
+            type_,
+            code,
+            value,
+        }
+    }
+
+    fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[0..8].copy_from_slice(&self.time.sec.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.time.nsec.to_le_bytes());
+        bytes[16..18].copy_from_slice(&self.type_.to_le_bytes());
+        bytes[18..20].copy_from_slice(&self.code.to_le_bytes());
+        bytes[20..24].copy_from_slice(&self.value.to_le_bytes());
+        bytes
+    }
+}
+
+/// How many undelivered events an `/dev/input/eventX` device queues
+/// before it starts dropping the oldest one, so a reader that never
+/// shows up doesn't let the queue grow without bound.
+const INPUT_QUEUE_CAPACITY: usize = 64;
+
+/// `/dev/input/eventX`: a per-device ring buffer of [`InputEvent`]s fed
+/// by `VirtioInputDriver::handle_interrupt`, read one whole event at a
+/// time (the simplest subset of evdev's "however many whole events fit"
+/// batch read), blocking while empty.
+pub struct InputEventDevice {
+    events: UPSafeCell<VecDeque<InputEvent>>,
+    not_empty: WaitQueue,
+    nonblocking: UPSafeCell<bool>,
+}
+
+impl InputEventDevice {
+    pub fn new() -> Self {
+        Self {
+            events: unsafe { UPSafeCell::new(VecDeque::new()) },
+            not_empty: WaitQueue::new(),
+            nonblocking: unsafe { UPSafeCell::new(false) },
+        }
+    }
+
+    /// Queues `event`, dropping the oldest undelivered one first if the
+    /// queue is already at [`INPUT_QUEUE_CAPACITY`].
+    pub fn push_event(&self, event: InputEvent) {
+        let mut events = self.events.exclusive_access();
+        if events.len() >= INPUT_QUEUE_CAPACITY {
+            events.pop_front();
+        }
+        events.push_back(event);
+        drop(events);
+        self.not_empty.wake_all();
+    }
+}
+
+impl Default for InputEventDevice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl File for InputEventDevice {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        false
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        loop {
+            if let Some(event) = self.events.exclusive_access().pop_front() {
+                return copy_into(&mut user_buf, &event.to_bytes());
+            }
+            if self.is_nonblocking() {
+                return 0;
+            }
+            suspend_current_and_run_next();
+        }
+    }
+    fn write(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot write to /dev/input/eventX");
+    }
+    fn is_nonblocking(&self) -> bool {
+        *self.nonblocking.exclusive_access()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        *self.nonblocking.exclusive_access() = nonblocking;
+    }
+}
+
+impl Pollable for InputEventDevice {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLIN) && !self.events.exclusive_access().is_empty() {
+            ready.insert(PollEvents::POLLIN);
+        }
+        ready
+    }
+}
+
+/// The `/dev/fb0` singleton [`init_devfs`] registers, kept separately
+/// from [`DEVICES`] too so `sys_framebuffer_flush` can reach its concrete
+/// [`FramebufferDevice::flush`] without downcasting out of `Arc<dyn
+/// File>`.
+static FRAMEBUFFER: UPSafeCell<Option<Arc<FramebufferDevice>>> =
+    unsafe { UPSafeCell::new(None) };
+
+/// The `/dev/fb0` device, if [`init_devfs`] has run.
+pub fn framebuffer() -> Option<Arc<FramebufferDevice>> {
+    FRAMEBUFFER.exclusive_access().clone()
+}
+
+/// The `/dev/input/eventX` devices [`init_devfs`] registers, name ->
+/// concrete [`InputEventDevice`], kept separately from [`DEVICES`] for
+/// the same reason [`FRAMEBUFFER`] is: `VirtioInputDriver::handle_interrupt`
+/// needs `InputEventDevice::push_event`, which isn't on `File`.
+static INPUT_DEVICES: UPSafeCell<BTreeMap<String, Arc<InputEventDevice>>> =
+    unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+/// The `/dev/input/eventX` device named `name` (e.g. `"event0"`), if
+/// [`init_devfs`] has run.
+pub fn input_device(name: &str) -> Option<Arc<InputEventDevice>> {
+    INPUT_DEVICES.exclusive_access().get(name).cloned()
+}
+
+fn copy_into(dest: &mut UserBuffer, src: &[u8]) -> usize {
+    let mut written = 0;
+    for buffer in dest.buffers.iter_mut() {
+        if written >= src.len() {
+            break;
+        }
+        let n = buffer.len().min(src.len() - written);
+        buffer[..n].copy_from_slice(&src[written..written + n]);
+        written += n;
+    }
+    written
+}
+
+/// Reads `src`'s contents into `dest`, the write-side mirror of
+/// [`copy_into`].
+fn copy_from(src: &UserBuffer, dest: &mut [u8]) -> usize {
+    let mut read = 0;
+    for buffer in src.buffers.iter() {
+        if read >= dest.len() {
+            break;
+        }
+        let n = buffer.len().min(dest.len() - read);
+        dest[read..read + n].copy_from_slice(&buffer[..n]);
+        read += n;
+    }
+    read
+}
+
+/// Name -> device registry a `/dev/<name>` lookup resolves through.
+static DEVICES: UPSafeCell<BTreeMap<String, Arc<dyn File>>> =
+    unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+fn register(name: &str, file: Arc<dyn File>) {
+    DEVICES.exclusive_access().insert(name.to_string(), file);
+}
+
+/// Looks up a device by the name it would appear under in `/dev`, e.g.
+/// `lookup_device("null")` for `/dev/null`.
+pub fn lookup_device(name: &str) -> Option<Arc<dyn File>> {
+    DEVICES.exclusive_access().get(name).cloned()
+}
+
+/// Registers the standard device nodes. Calling this more than once is
+/// harmless for the stateless devices (`null`/`zero`/`tty`/`rtc`), which
+/// just get re-registered under the same names, but allocates a fresh
+/// `/dev/fb0` pixel buffer each time.
+pub fn init_devfs() {
+    register("null", Arc::new(NullDevice));
+    register("zero", Arc::new(ZeroDevice));
+    register("tty", Arc::new(TtyDevice));
+    register("rtc", Arc::new(RtcDevice));
+    let fb = Arc::new(FramebufferDevice::new());
+    *FRAMEBUFFER.exclusive_access() = Some(Arc::clone(&fb));
+    register("fb0", fb);
+    for name in ["event0", "event1"] {
+        let device = Arc::new(InputEventDevice::new());
+        INPUT_DEVICES
+            .exclusive_access()
+            .insert(name.to_string(), Arc::clone(&device));
+        register(&format!("input/{name}"), device);
+    }
+}
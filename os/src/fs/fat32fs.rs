@@ -0,0 +1,55 @@
+//! Adapts the `fat32` crate's `Fat32FileSystem`/`Fat32Inode` onto this
+//! kernel's [`FileSystem`]/[`VfsInode`] traits, so a FAT-formatted SD
+//! image can be mounted and read through the same VFS path every other
+//! filesystem in this tree uses.
+
+use super::vfs::{register_filesystem, FileSystem, VfsInode};
+use alloc::sync::Arc;
+use fat32::{BlockDevice, Fat32FileSystem, Fat32Inode};
+
+impl VfsInode for Fat32Inode {
+    fn is_dir(&self) -> bool {
+        Fat32Inode::is_dir(self)
+    }
+
+    fn lookup(&self, name: &str) -> Option<Arc<dyn VfsInode>> {
+        Fat32Inode::lookup(self, name).map(|inode| Arc::new(inode) as Arc<dyn VfsInode>)
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> usize {
+        let data = self.read_all();
+        if offset >= data.len() {
+            return 0;
+        }
+        let end = (offset + buf.len()).min(data.len());
+        let len = end - offset;
+        buf[..len].copy_from_slice(&data[offset..end]);
+        len
+    }
+
+    /// FAT32 support here is read-only: there is no on-disk writer, only
+    /// the BPB/FAT/directory parsing needed to load programs off an
+    /// already-built image.
+    fn write_at(&self, _offset: usize, _buf: &[u8]) -> usize {
+        0
+    }
+}
+
+impl FileSystem for Fat32FileSystem {
+    fn root(&self) -> Arc<dyn VfsInode> {
+        Arc::new(Fat32FileSystem::root(self))
+    }
+}
+
+/// Registers a FAT32 image's filesystem and mounts it at `/sdcard`. Not
+/// called from `rust_main` yet — there's no concrete `BlockDevice` in
+/// `drivers/` to hand it a `block_device` from, only `VirtioBlkDriver`'s
+/// interrupt-driven request queue, which doesn't itself implement the
+/// trait. Wiring that up is the same gap `mount_procfs` was left with.
+pub fn mount_fat32(block_device: Arc<dyn BlockDevice>) {
+    let fs = Arc::new(Fat32FileSystem::open(block_device));
+    register_filesystem("fat32", fs);
+    super::mount_table()
+        .exclusive_access()
+        .mount("/sdcard", super::lookup_filesystem("fat32").unwrap());
+}
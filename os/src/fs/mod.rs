@@ -0,0 +1,145 @@
+//! File-like objects backing the process file descriptor table.
+//!
+//! Every kind of thing a process can hold an fd to (stdio, pipes, inodes,
+//! signalfds, ...) implements [`File`] so that `sys_read`/`sys_write` and the
+//! poll machinery can stay generic over what is actually on the other end.
+
+mod devfs;
+mod fat32fs;
+mod pipe;
+mod poll;
+mod procfs;
+mod signalfd;
+mod socket;
+mod stdio;
+mod vfs;
+
+use crate::mm::UserBuffer;
+use alloc::sync::Arc;
+
+pub use devfs::{
+    framebuffer, init_devfs, input_device, lookup_device, FramebufferDevice, InputEvent,
+    InputEventDevice, NullDevice, RtcDevice, TtyDevice, ZeroDevice, FB_HEIGHT, FB_WIDTH,
+};
+pub use fat32fs::mount_fat32;
+pub use pipe::{pipe, ReadEnd, WriteEnd};
+pub use poll::{poll_once, PollEvents, Pollable};
+pub use procfs::mount_procfs;
+pub use signalfd::SignalFd;
+pub use socket::{socketpair, SocketEnd, SocketType};
+pub use stdio::{Stdin, Stdout};
+pub use vfs::{lookup_filesystem, mount_table, register_filesystem, FileSystem, MountTable, VfsInode};
+
+bitflags::bitflags! {
+    /// Mirrors the `O_*` bits POSIX `open(2)` accepts.
+    ///
+    /// There is no `sys_open`/`OSInode` in this tree yet to actually
+    /// construct a [`File`] from these — they land ahead of that the same
+    /// way [`VfsInode`]/[`MountTable`] landed ahead of a concrete mounted
+    /// filesystem. `readable`/`writable` below are real, callable logic
+    /// though: whatever eventually builds an `OSInode` from a parsed
+    /// `OpenFlags` should call them rather than re-deriving the rules.
+    #[derive(Copy, Clone)]
+    pub struct OpenFlags: u32 {
+        const RDONLY = 0;
+        const WRONLY = 0o1;
+        const RDWR = 0o2;
+        const CREATE = 0o100;
+        const TRUNC = 0o1000;
+        const APPEND = 0o2000;
+        const NONBLOCK = 0o4000;
+    }
+}
+
+impl OpenFlags {
+    /// Whether a file opened with these flags should be readable.
+    /// Any combination that includes `O_WRONLY` isn't.
+    pub fn readable(&self) -> bool {
+        !self.contains(Self::WRONLY)
+    }
+
+    /// Whether a file opened with these flags should be writable:
+    /// `O_WRONLY` or `O_RDWR`, including when combined with `O_APPEND`
+    /// or `O_TRUNC`.
+    pub fn writable(&self) -> bool {
+        self.contains(Self::WRONLY) || self.contains(Self::RDWR)
+    }
+}
+
+/// Where a `seek` offset is measured from, matching POSIX's
+/// `SEEK_SET`/`SEEK_CUR`/`SEEK_END`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SeekWhence {
+    Set,
+    Cur,
+    End,
+}
+
+/// Common interface implemented by every object reachable through a file
+/// descriptor. Requires [`Pollable`] so `sys_ppoll`/`sys_pselect6` can
+/// query readiness generically over the fd table's `Arc<dyn File>`s
+/// without needing to downcast to each concrete kind.
+pub trait File: Send + Sync + Pollable {
+    /// Whether the fd was opened for reading.
+    fn readable(&self) -> bool;
+    /// Whether the fd was opened for writing.
+    fn writable(&self) -> bool;
+    /// Read into `buf`, returning the number of bytes actually read.
+    fn read(&self, buf: UserBuffer) -> usize;
+    /// Write `buf`, returning the number of bytes actually written.
+    fn write(&self, buf: UserBuffer) -> usize;
+    /// Repositions this file's cursor and returns the new absolute
+    /// offset. The default is `-29` (`-ESPIPE`): streams like stdio and
+    /// pipes aren't seekable, so only the kinds backed by real seekable
+    /// storage (regular files, once this tree has them) need to override
+    /// it. [`ReadEnd`]/[`WriteEnd`] rely on this default rather than
+    /// overriding it.
+    fn seek(&self, _offset: isize, _whence: SeekWhence) -> isize {
+        -29
+    }
+    /// Reads at `offset` without disturbing whatever cursor `seek` tracks
+    /// (`pread`). Only meaningful for the same seekable kinds `seek`
+    /// overrides; streams default to reading nothing.
+    fn read_at(&self, _offset: usize, _buf: UserBuffer) -> usize {
+        0
+    }
+    /// Writes at `offset` without disturbing whatever cursor `seek` tracks
+    /// (`pwrite`). Only meaningful for the same seekable kinds `seek`
+    /// overrides; streams default to writing nothing.
+    fn write_at(&self, _offset: usize, _buf: UserBuffer) -> usize {
+        0
+    }
+    /// Whether this open file description is in `O_NONBLOCK` mode.
+    /// Defaults to `false`; kinds that can actually block (`Stdin`,
+    /// [`ReadEnd`], [`WriteEnd`]) override both this and
+    /// [`set_nonblocking`](Self::set_nonblocking) to track it.
+    fn is_nonblocking(&self) -> bool {
+        false
+    }
+    /// Sets `O_NONBLOCK` mode (`fcntl(F_SETFL)`). A no-op default for
+    /// kinds that never block in the first place.
+    fn set_nonblocking(&self, _nonblocking: bool) {}
+    /// Binds this socket to a local address, returning the port it ended
+    /// up bound to (`bind`). Only [`crate::net::Socket`] overrides this;
+    /// every other kind fails, matching `bind(2)` on a non-socket fd
+    /// (`ENOTSOCK`, here with no error channel to report it through).
+    fn bind(&self, _port: Option<u16>) -> Result<u16, ()> {
+        Err(())
+    }
+    /// Marks this socket as willing to accept incoming connections
+    /// (`listen`). Same non-socket default as [`bind`](Self::bind).
+    fn listen(&self, _backlog: usize) -> Result<(), ()> {
+        Err(())
+    }
+    /// Blocks until a connection arrives on this listening socket,
+    /// returning the new connected [`File`] (`accept`). Same non-socket
+    /// default as [`bind`](Self::bind).
+    fn accept(&self) -> Result<Arc<dyn File>, ()> {
+        Err(())
+    }
+    /// Connects this socket to whatever is listening on `port` (`connect`).
+    /// Same non-socket default as [`bind`](Self::bind).
+    fn connect(&self, _port: u16) -> Result<(), ()> {
+        Err(())
+    }
+}
@@ -0,0 +1,306 @@
+//! Anonymous pipes: a fixed-size ring buffer shared between a read end
+//! and a write end. A blocking reader of an empty pipe, or writer of a
+//! full one, yields with `suspend_current_and_run_next` and retries, the
+//! same loop [`Stdin`](super::Stdin) and [`TtyDevice`](super::TtyDevice)
+//! use. Each transfer and each end closing also calls `wake_all` on the
+//! peer's [`WaitQueue`], but no task is ever added to those queues. On
+//! its next retry a reader notices EOF (all write ends gone) and a writer
+//! raises `SIGPIPE` (all read ends gone) instead of waiting forever.
+//!
+//! There is no `sys_pipe`/`sys_pipe2` syscall that hands a pair of these
+//! fds to a process.
+
+use super::{File, PollEvents, Pollable};
+use crate::mm::UserBuffer;
+use crate::sync::{UPSafeCell, WaitQueue};
+use crate::task::{current_task, suspend_current_and_run_next, SignalFlags};
+use alloc::sync::Arc;
+
+const RING_BUFFER_SIZE: usize = 32;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum RingBufferStatus {
+    Full,
+    Empty,
+    Normal,
+}
+
+struct RingBuffer {
+    arr: [u8; RING_BUFFER_SIZE],
+    head: usize,
+    tail: usize,
+    status: RingBufferStatus,
+    /// 1 while the read/write end is still alive, 0 once it's dropped.
+    /// Each end is a single struct behind a single `Arc` — `dup`/`dup2`
+    /// only clone that `Arc`, so its `Drop` impl fires exactly once, when
+    /// the last fd referencing it closes — so these only ever need to go
+    /// from 1 to 0, not track an arbitrary count of live references.
+    read_ends: usize,
+    write_ends: usize,
+}
+
+impl RingBuffer {
+    fn new() -> Self {
+        Self {
+            arr: [0; RING_BUFFER_SIZE],
+            head: 0,
+            tail: 0,
+            status: RingBufferStatus::Empty,
+            read_ends: 1,
+            write_ends: 1,
+        }
+    }
+
+    fn available_read(&self) -> usize {
+        if self.status == RingBufferStatus::Empty {
+            0
+        } else if self.tail > self.head {
+            self.tail - self.head
+        } else {
+            RING_BUFFER_SIZE - self.head + self.tail
+        }
+    }
+
+    fn available_write(&self) -> usize {
+        RING_BUFFER_SIZE - self.available_read()
+    }
+
+    fn read_byte(&mut self) -> u8 {
+        self.status = RingBufferStatus::Normal;
+        let byte = self.arr[self.head];
+        self.head = (self.head + 1) % RING_BUFFER_SIZE;
+        if self.head == self.tail {
+            self.status = RingBufferStatus::Empty;
+        }
+        byte
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        self.status = RingBufferStatus::Normal;
+        self.arr[self.tail] = byte;
+        self.tail = (self.tail + 1) % RING_BUFFER_SIZE;
+        if self.tail == self.head {
+            self.status = RingBufferStatus::Full;
+        }
+    }
+}
+
+/// State shared by a pipe's two ends.
+struct Shared {
+    ring: UPSafeCell<RingBuffer>,
+    /// Signalled by each write and by the write end closing; no reader
+    /// is ever queued on it.
+    not_empty: WaitQueue,
+    /// Signalled by each read and by the read end closing; no writer is
+    /// ever queued on it.
+    not_full: WaitQueue,
+}
+
+/// Creates a connected pipe, returning its read end and write end.
+pub fn pipe() -> (Arc<ReadEnd>, Arc<WriteEnd>) {
+    let shared = Arc::new(Shared {
+        ring: unsafe { UPSafeCell::new(RingBuffer::new()) },
+        not_empty: WaitQueue::new(),
+        not_full: WaitQueue::new(),
+    });
+    (
+        Arc::new(ReadEnd {
+            shared: Arc::clone(&shared),
+            nonblocking: unsafe { UPSafeCell::new(false) },
+        }),
+        Arc::new(WriteEnd {
+            shared,
+            nonblocking: unsafe { UPSafeCell::new(false) },
+        }),
+    )
+}
+
+pub struct ReadEnd {
+    shared: Arc<Shared>,
+    nonblocking: UPSafeCell<bool>,
+}
+
+pub struct WriteEnd {
+    shared: Arc<Shared>,
+    nonblocking: UPSafeCell<bool>,
+}
+
+impl Drop for ReadEnd {
+    fn drop(&mut self) {
+        self.shared.ring.exclusive_access().read_ends -= 1;
+        // A writer blocked on a full pipe needs to notice there's no
+        // reader left to ever drain it, so it can raise SIGPIPE instead
+        // of waiting forever.
+        self.shared.not_full.wake_all();
+    }
+}
+
+impl Drop for WriteEnd {
+    fn drop(&mut self) {
+        self.shared.ring.exclusive_access().write_ends -= 1;
+        // A reader blocked on an empty pipe needs to notice EOF.
+        self.shared.not_empty.wake_all();
+    }
+}
+
+impl File for ReadEnd {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        false
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        let want = user_buf.len();
+        if want == 0 {
+            return 0;
+        }
+        let mut read = 0;
+        loop {
+            {
+                let mut ring = self.shared.ring.exclusive_access();
+                let mut seen = 0;
+                'outer: for dst in user_buf.buffers.iter_mut() {
+                    for byte in dst.iter_mut() {
+                        if seen < read {
+                            // Already filled on an earlier round.
+                            seen += 1;
+                            continue;
+                        }
+                        if read >= want || ring.available_read() == 0 {
+                            break 'outer;
+                        }
+                        *byte = ring.read_byte();
+                        read += 1;
+                        seen += 1;
+                    }
+                }
+            }
+            self.shared.not_full.wake_all();
+            if read >= want {
+                break;
+            }
+            if self.shared.ring.exclusive_access().write_ends == 0 {
+                // EOF: no writer left to ever add more.
+                break;
+            }
+            if self.is_nonblocking() {
+                break;
+            }
+            suspend_current_and_run_next();
+        }
+        read
+    }
+    fn write(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot write to the read end of a pipe");
+    }
+    fn is_nonblocking(&self) -> bool {
+        *self.nonblocking.exclusive_access()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        *self.nonblocking.exclusive_access() = nonblocking;
+    }
+}
+
+impl Pollable for ReadEnd {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLIN) {
+            let ring = self.shared.ring.exclusive_access();
+            if ring.available_read() > 0 || ring.write_ends == 0 {
+                ready.insert(PollEvents::POLLIN);
+            }
+        }
+        ready
+    }
+}
+
+impl File for WriteEnd {
+    fn readable(&self) -> bool {
+        false
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot read from the write end of a pipe");
+    }
+    fn write(&self, user_buf: UserBuffer) -> usize {
+        let want = user_buf.len();
+        if want == 0 {
+            return 0;
+        }
+        if self.shared.ring.exclusive_access().read_ends == 0 {
+            raise_sigpipe();
+            // `usize` has no error channel to report EPIPE through (the
+            // same gap `Stdin::read`'s nonblocking path notes); the
+            // raised signal is the real, observable effect here.
+            return 0;
+        }
+        let mut written = 0;
+        loop {
+            {
+                let mut ring = self.shared.ring.exclusive_access();
+                let mut seen = 0;
+                'outer: for src in user_buf.buffers.iter() {
+                    for byte in src.iter() {
+                        if seen < written {
+                            // Already sent on an earlier round.
+                            seen += 1;
+                            continue;
+                        }
+                        if written >= want || ring.available_write() == 0 {
+                            break 'outer;
+                        }
+                        ring.write_byte(*byte);
+                        written += 1;
+                        seen += 1;
+                    }
+                }
+            }
+            self.shared.not_empty.wake_all();
+            if written >= want {
+                break;
+            }
+            if self.shared.ring.exclusive_access().read_ends == 0 {
+                raise_sigpipe();
+                break;
+            }
+            if self.is_nonblocking() {
+                break;
+            }
+            suspend_current_and_run_next();
+        }
+        written
+    }
+    fn is_nonblocking(&self) -> bool {
+        *self.nonblocking.exclusive_access()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        *self.nonblocking.exclusive_access() = nonblocking;
+    }
+}
+
+impl Pollable for WriteEnd {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLOUT) {
+            let ring = self.shared.ring.exclusive_access();
+            if ring.available_write() > 0 || ring.read_ends == 0 {
+                ready.insert(PollEvents::POLLOUT);
+            }
+        }
+        ready
+    }
+}
+
+/// Raises `SIGPIPE` against the calling task. Shared with the datagram
+/// socket endpoints in `super::socket`, which hit the same "wrote with
+/// no reader left" case this does.
+pub(super) fn raise_sigpipe() {
+    if let Some(task) = current_task() {
+        task.inner_exclusive_access()
+            .signals
+            .raise(SignalFlags::SIGPIPE);
+    }
+}
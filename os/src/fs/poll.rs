@@ -0,0 +1,47 @@
+//! Minimal readiness-polling support shared by the pollable file kinds
+//! (pipes, sockets, signalfds, ...).
+
+use super::File;
+use alloc::sync::Arc;
+
+/// Readiness bits, mirroring the subset of POSIX `poll(2)` events the
+/// kernel actually distinguishes between.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct PollEvents(u32);
+
+impl PollEvents {
+    pub const POLLIN: PollEvents = PollEvents(1 << 0);
+    pub const POLLOUT: PollEvents = PollEvents(1 << 1);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn contains(&self, other: PollEvents) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn insert(&mut self, other: PollEvents) {
+        self.0 |= other.0;
+    }
+}
+
+/// Implemented by file objects that can report readiness without blocking,
+/// so that poll-style syscalls can query them directly instead of guessing
+/// from a blocking `read`/`write`.
+pub trait Pollable {
+    /// Which of `interest` are currently satisfied.
+    fn poll(&self, interest: PollEvents) -> PollEvents;
+}
+
+/// Polls every `(file, interest)` pair once, in order, returning the
+/// index and ready events of the first one with a nonempty match.
+/// `sys_ppoll`/`sys_pselect6` loop this against
+/// [`suspend_current_and_run_next`](crate::task::suspend_current_and_run_next)
+/// until it finds something or their timeout expires.
+pub fn poll_once(entries: &[(Arc<dyn File>, PollEvents)]) -> Option<(usize, PollEvents)> {
+    entries.iter().enumerate().find_map(|(i, (file, interest))| {
+        let ready = file.poll(*interest);
+        (ready != PollEvents::empty()).then_some((i, ready))
+    })
+}
@@ -0,0 +1,234 @@
+//! A read-only, in-memory pseudo-filesystem exposing kernel state as
+//! files, meant to be mounted at `/proc`: `/proc/<pid>/status` for one
+//! process, plus system-wide `/proc/meminfo` and `/proc/cpuinfo`.
+//! Nothing is stored on disk; every read renders its contents fresh from
+//! live kernel data structures, the same as Linux's own procfs.
+
+use super::vfs::{FileSystem, VfsInode};
+use crate::config::MAX_HARTS;
+use crate::task::TaskControlBlock;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::{Arc, Weak};
+
+fn read_str_at(text: &str, offset: usize, buf: &mut [u8]) -> usize {
+    let bytes = text.as_bytes();
+    if offset >= bytes.len() {
+        return 0;
+    }
+    let n = (bytes.len() - offset).min(buf.len());
+    buf[..n].copy_from_slice(&bytes[offset..offset + n]);
+    n
+}
+
+/// A leaf file whose contents are computed fresh on every read rather
+/// than stored; `/proc` has no writable files, so `write_at` is always a
+/// no-op.
+struct ProcFile {
+    render: Box<dyn Fn() -> String + Send + Sync>,
+}
+
+impl VfsInode for ProcFile {
+    fn is_dir(&self) -> bool {
+        false
+    }
+
+    fn lookup(&self, _name: &str) -> Option<Arc<dyn VfsInode>> {
+        None
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> usize {
+        read_str_at(&(self.render)(), offset, buf)
+    }
+
+    fn write_at(&self, _offset: usize, _buf: &[u8]) -> usize {
+        0
+    }
+}
+
+/// `/proc/<pid>`: currently just a container for `status`, the one file
+/// every process directory is guaranteed to have.
+struct ProcPidDir {
+    pid: usize,
+}
+
+impl VfsInode for ProcPidDir {
+    fn is_dir(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, name: &str) -> Option<Arc<dyn VfsInode>> {
+        if name != "status" {
+            return None;
+        }
+        let pid = self.pid;
+        Some(Arc::new(ProcFile {
+            render: Box::new(move || render_status(pid)),
+        }))
+    }
+
+    fn read_at(&self, _offset: usize, _buf: &mut [u8]) -> usize {
+        0
+    }
+
+    fn write_at(&self, _offset: usize, _buf: &[u8]) -> usize {
+        0
+    }
+}
+
+/// `/proc` itself: a pid-named subdirectory per live process plus the
+/// system-wide `meminfo`, `cpuinfo`, `trace` and `tracepoints` files.
+struct ProcRoot;
+
+impl VfsInode for ProcRoot {
+    fn is_dir(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, name: &str) -> Option<Arc<dyn VfsInode>> {
+        match name {
+            "meminfo" => Some(Arc::new(ProcFile {
+                render: Box::new(render_meminfo),
+            })),
+            "cpuinfo" => Some(Arc::new(ProcFile {
+                render: Box::new(render_cpuinfo),
+            })),
+            "trace" => Some(Arc::new(ProcFile {
+                render: Box::new(render_trace),
+            })),
+            "tracepoints" => Some(Arc::new(ProcFile {
+                render: Box::new(render_tracepoints),
+            })),
+            _ => {
+                let pid: usize = name.parse().ok()?;
+                find_task(pid)?;
+                Some(Arc::new(ProcPidDir { pid }))
+            }
+        }
+    }
+
+    fn read_at(&self, _offset: usize, _buf: &mut [u8]) -> usize {
+        0
+    }
+
+    fn write_at(&self, _offset: usize, _buf: &[u8]) -> usize {
+        0
+    }
+}
+
+pub struct ProcFs;
+
+impl FileSystem for ProcFs {
+    fn root(&self) -> Arc<dyn VfsInode> {
+        Arc::new(ProcRoot)
+    }
+}
+
+/// Registers procfs and mounts it at `/proc`. Not called automatically:
+/// `rust_main` does not call it, so `/proc` is only mounted by whoever
+/// calls this.
+pub fn mount_procfs() {
+    super::register_filesystem("procfs", Arc::new(ProcFs));
+    super::mount_table().exclusive_access().mount("/proc", super::lookup_filesystem("procfs").unwrap());
+}
+
+/// Depth-first search of the process tree (rooted at init) for `pid`;
+/// there is no flat process table to index into directly, the same as
+/// `reparent_children_to_initproc` walks `children` rather than a
+/// separate registry.
+fn find_task(pid: usize) -> Option<Arc<TaskControlBlock>> {
+    let root = crate::task::initproc()?;
+    let mut stack = alloc::vec![root];
+    while let Some(task) = stack.pop() {
+        if task.pid == pid {
+            return Some(task);
+        }
+        stack.extend(task.inner_exclusive_access().children.iter().cloned());
+    }
+    None
+}
+
+fn render_status(pid: usize) -> String {
+    let Some(task) = find_task(pid) else {
+        return String::new();
+    };
+    let inner = task.inner_exclusive_access();
+    let state = if inner.is_zombie() { "Z (zombie)" } else { "R (running)" };
+    let ppid = inner
+        .parent
+        .as_ref()
+        .and_then(Weak::upgrade)
+        .map(|p| p.pid.to_string())
+        .unwrap_or_else(|| "0".to_string());
+    // Clock ticks, matching Linux's `/proc/<pid>/status` convention of
+    // reporting times in `USER_HZ` units rather than raw nanoseconds.
+    const USER_HZ: u64 = 100;
+    let utime_ticks = inner.cpu_time.utime_ns / (crate::timer::NANOS_PER_SEC / USER_HZ);
+    let stime_ticks = inner.cpu_time.stime_ns / (crate::timer::NANOS_PER_SEC / USER_HZ);
+    format!(
+        "Pid:\t{}\nPPid:\t{}\nTgid:\t{}\nPgid:\t{}\nSid:\t{}\nState:\t{}\n\
+         VmRSS:\t0 kB\nUtime:\t{}\nStime:\t{}\n",
+        task.pid, ppid, inner.tgid, inner.pgid, inner.sid, state, utime_ticks, stime_ticks
+    )
+}
+
+fn render_meminfo() -> String {
+    // `sys_getrss` is still stubbed at 0 (no per-process frame accounting
+    // exists), but the system-wide frame allocator now tracks real usage
+    // once `mm::init_frame_allocator` has run; report its numbers when
+    // available and fall back to the all-zero shape otherwise, so this
+    // keeps the `/proc/meminfo` format stable either way.
+    match crate::mm::frame_allocator_stats() {
+        Some(stats) => {
+            let page_kb = crate::config::PAGE_SIZE / 1024;
+            format!(
+                "MemTotal:\t{} kB\nMemFree:\t{} kB\nMemPeakUsed:\t{} kB\n",
+                stats.total_frames * page_kb,
+                stats.free_frames * page_kb,
+                stats.peak_allocated_frames * page_kb,
+            )
+        }
+        None => "MemTotal:\t0 kB\nMemFree:\t0 kB\nMemPeakUsed:\t0 kB\n".to_string(),
+    }
+}
+
+fn render_cpuinfo() -> String {
+    let mut out = String::new();
+    for hart in 0..MAX_HARTS {
+        out.push_str(&format!("processor\t: {}\n", hart));
+    }
+    out
+}
+
+/// `/proc/tracepoints`: every static tracepoint's configured action and,
+/// for one counting, its current count — set with `sys_debug_ctl`.
+fn render_tracepoints() -> String {
+    let mut out = String::new();
+    for point in crate::task::Tracepoint::all() {
+        out.push_str(&format!(
+            "{}\t{}\t{}\n",
+            point.name(),
+            crate::task::tracepoint_action(point).name(),
+            crate::task::tracepoint_counter(point),
+        ));
+    }
+    out
+}
+
+/// `/proc/trace`: a live (non-consuming) view of every hart's syscall
+/// trace ring, one line per recorded entry/exit pair. Empty whenever
+/// tracing hasn't been turned on with `sys_trace_ctl`.
+fn render_trace() -> String {
+    let mut out = String::new();
+    for event in crate::task::trace_snapshot_all() {
+        out.push_str(&format!(
+            "syscall={} entry_ns={} exit_ns={} dur_ns={}\n",
+            event.syscall_id,
+            event.entry_ns,
+            event.exit_ns,
+            event.exit_ns.saturating_sub(event.entry_ns),
+        ));
+    }
+    out
+}
@@ -0,0 +1,104 @@
+//! `signalfd`-style file object: turns a subset of a process's pending
+//! signals into records read from a file descriptor instead of asynchronous
+//! handler delivery, so a single-threaded program can multiplex signals
+//! into its own poll/select loop.
+
+use super::poll::{PollEvents, Pollable};
+use super::File;
+use crate::mm::UserBuffer;
+use crate::task::{suspend_current_and_run_next, SignalFlags, TaskControlBlock};
+use alloc::sync::Arc;
+use core::mem::size_of;
+
+/// One delivered signal, in the layout handed back to userspace.
+/// Mirrors the handful of fields Linux's `signalfd_siginfo` exposes that
+/// this kernel actually populates.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SignalfdSiginfo {
+    pub ssi_signo: u32,
+    pub _pad: [u8; 124],
+}
+
+/// A signalfd bound to the signal mask it was created with. Reading it
+/// dequeues one matching pending signal and writes it as a single
+/// `size_of::<SignalfdSiginfo>()` record, blocking until one is available;
+/// a buffer with room for several records still receives only one.
+pub struct SignalFd {
+    mask: SignalFlags,
+    owner: Arc<TaskControlBlock>,
+}
+
+impl SignalFd {
+    pub fn new(mask: SignalFlags, owner: Arc<TaskControlBlock>) -> Self {
+        owner.inner_exclusive_access().signals.signalfd_mask.insert(mask);
+        Self { mask, owner }
+    }
+
+    fn try_take(&self) -> Option<SignalFlags> {
+        self.owner
+            .inner_exclusive_access()
+            .signals
+            .pending
+            .take_one(self.mask)
+    }
+}
+
+impl File for SignalFd {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        false
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        let record_size = size_of::<SignalfdSiginfo>();
+        if user_buf.len() < record_size {
+            return 0;
+        }
+        let sig = loop {
+            if let Some(sig) = self.try_take() {
+                break sig;
+            }
+            suspend_current_and_run_next();
+        };
+        let info = SignalfdSiginfo {
+            ssi_signo: sig.signum(),
+            _pad: [0; 124],
+        };
+        let bytes = unsafe {
+            core::slice::from_raw_parts(&info as *const _ as *const u8, record_size)
+        };
+        let mut written = 0;
+        'outer: for dst in user_buf.buffers.iter_mut() {
+            for b in dst.iter_mut() {
+                if written >= bytes.len() {
+                    break 'outer;
+                }
+                *b = bytes[written];
+                written += 1;
+            }
+        }
+        written
+    }
+    fn write(&self, _user_buf: UserBuffer) -> usize {
+        panic!("signalfd is not writable");
+    }
+}
+
+impl Pollable for SignalFd {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLIN)
+            && self
+                .owner
+                .inner_exclusive_access()
+                .signals
+                .pending
+                .contains(self.mask)
+        {
+            ready.insert(PollEvents::POLLIN);
+        }
+        ready
+    }
+}
@@ -0,0 +1,340 @@
+//! `socketpair`-style local IPC: a connected pair of bidirectional
+//! endpoints, so a parent and child can do request/response traffic over
+//! one fd each instead of wiring up two unidirectional pipes by hand.
+//!
+//! Both [`SocketType`]s are built by crossing two unidirectional
+//! channels rather than inventing a new bidirectional primitive: a
+//! stream pair is two [`pipe`]s (its byte-stream, ring-buffer framing is
+//! already exactly what `SOCK_STREAM` wants), and a datagram pair is two
+//! [`DatagramChannel`]s, a message-queue analog of the same "one ring,
+//! two crossed ends" shape for `SOCK_DGRAM`'s message-boundary-preserving
+//! semantics.
+
+use super::pipe::{pipe, raise_sigpipe, ReadEnd, WriteEnd};
+use super::{File, PollEvents, Pollable};
+use crate::mm::UserBuffer;
+use crate::sync::{UPSafeCell, WaitQueue};
+use crate::task::suspend_current_and_run_next;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// How a [`socketpair`] frames the data passed between its two ends.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum SocketType {
+    /// An unbroken byte stream, like a pipe: `read` may return fewer
+    /// bytes than requested but never more than what's actually arrived,
+    /// with no message boundaries preserved.
+    Stream,
+    /// Each `write` is one message; each `read` returns exactly one
+    /// whole message (truncating it if the caller's buffer is smaller),
+    /// never merging or splitting messages across calls.
+    Datagram,
+}
+
+const DATAGRAM_QUEUE_CAPACITY: usize = 16;
+
+enum Endpoint {
+    Stream {
+        read: Arc<ReadEnd>,
+        write: Arc<WriteEnd>,
+    },
+    Datagram {
+        read: Arc<DatagramReadEnd>,
+        write: Arc<DatagramWriteEnd>,
+    },
+}
+
+/// One end of a connected socket pair.
+pub struct SocketEnd(Endpoint);
+
+/// Creates a connected pair of local sockets framed as `kind`, returning
+/// the two endpoints. Each endpoint can read what the other wrote and
+/// write what the other will read — crossing the two underlying
+/// channels is what makes the pair bidirectional.
+pub fn socketpair(kind: SocketType) -> (Arc<SocketEnd>, Arc<SocketEnd>) {
+    match kind {
+        SocketType::Stream => {
+            let (read_0_to_1, write_0_to_1) = pipe();
+            let (read_1_to_0, write_1_to_0) = pipe();
+            (
+                Arc::new(SocketEnd(Endpoint::Stream {
+                    read: read_1_to_0,
+                    write: write_0_to_1,
+                })),
+                Arc::new(SocketEnd(Endpoint::Stream {
+                    read: read_0_to_1,
+                    write: write_1_to_0,
+                })),
+            )
+        }
+        SocketType::Datagram => {
+            let (read_0_to_1, write_0_to_1) = datagram_channel();
+            let (read_1_to_0, write_1_to_0) = datagram_channel();
+            (
+                Arc::new(SocketEnd(Endpoint::Datagram {
+                    read: read_1_to_0,
+                    write: write_0_to_1,
+                })),
+                Arc::new(SocketEnd(Endpoint::Datagram {
+                    read: read_0_to_1,
+                    write: write_1_to_0,
+                })),
+            )
+        }
+    }
+}
+
+impl File for SocketEnd {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, buf: UserBuffer) -> usize {
+        match &self.0 {
+            Endpoint::Stream { read, .. } => read.read(buf),
+            Endpoint::Datagram { read, .. } => read.read(buf),
+        }
+    }
+    fn write(&self, buf: UserBuffer) -> usize {
+        match &self.0 {
+            Endpoint::Stream { write, .. } => write.write(buf),
+            Endpoint::Datagram { write, .. } => write.write(buf),
+        }
+    }
+    fn is_nonblocking(&self) -> bool {
+        match &self.0 {
+            Endpoint::Stream { read, .. } => read.is_nonblocking(),
+            Endpoint::Datagram { read, .. } => read.is_nonblocking(),
+        }
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        match &self.0 {
+            Endpoint::Stream { read, write } => {
+                read.set_nonblocking(nonblocking);
+                write.set_nonblocking(nonblocking);
+            }
+            Endpoint::Datagram { read, write } => {
+                read.set_nonblocking(nonblocking);
+                write.set_nonblocking(nonblocking);
+            }
+        }
+    }
+}
+
+impl Pollable for SocketEnd {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        match &self.0 {
+            Endpoint::Stream { read, write } => {
+                ready.insert(read.poll(interest));
+                ready.insert(write.poll(interest));
+            }
+            Endpoint::Datagram { read, write } => {
+                ready.insert(read.poll(interest));
+                ready.insert(write.poll(interest));
+            }
+        }
+        ready
+    }
+}
+
+/// Copies as much of `message` as fits into `user_buf`, dropping
+/// whatever doesn't — a datagram read never blends into the next
+/// message to make up the difference, it just truncates.
+fn scatter(user_buf: &mut UserBuffer, message: &[u8]) -> usize {
+    let mut written = 0;
+    'outer: for dst in user_buf.buffers.iter_mut() {
+        for byte in dst.iter_mut() {
+            if written >= message.len() {
+                break 'outer;
+            }
+            *byte = message[written];
+            written += 1;
+        }
+    }
+    written
+}
+
+/// Flattens `user_buf` into one contiguous message.
+fn gather(user_buf: &UserBuffer) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(user_buf.len());
+    for src in user_buf.buffers.iter() {
+        bytes.extend_from_slice(src);
+    }
+    bytes
+}
+
+struct Datagram {
+    messages: VecDeque<Vec<u8>>,
+    /// Same "1 until dropped" liveness flag as `pipe::RingBuffer`'s
+    /// `read_ends`/`write_ends` — see its doc comment for why a count
+    /// rather than a bool doesn't buy anything here either.
+    read_ends: usize,
+    write_ends: usize,
+}
+
+struct DatagramChannel {
+    state: UPSafeCell<Datagram>,
+    not_empty: WaitQueue,
+    not_full: WaitQueue,
+}
+
+fn datagram_channel() -> (Arc<DatagramReadEnd>, Arc<DatagramWriteEnd>) {
+    let channel = Arc::new(DatagramChannel {
+        state: unsafe {
+            UPSafeCell::new(Datagram {
+                messages: VecDeque::new(),
+                read_ends: 1,
+                write_ends: 1,
+            })
+        },
+        not_empty: WaitQueue::new(),
+        not_full: WaitQueue::new(),
+    });
+    (
+        Arc::new(DatagramReadEnd {
+            channel: Arc::clone(&channel),
+            nonblocking: unsafe { UPSafeCell::new(false) },
+        }),
+        Arc::new(DatagramWriteEnd {
+            channel,
+            nonblocking: unsafe { UPSafeCell::new(false) },
+        }),
+    )
+}
+
+struct DatagramReadEnd {
+    channel: Arc<DatagramChannel>,
+    nonblocking: UPSafeCell<bool>,
+}
+
+struct DatagramWriteEnd {
+    channel: Arc<DatagramChannel>,
+    nonblocking: UPSafeCell<bool>,
+}
+
+impl Drop for DatagramReadEnd {
+    fn drop(&mut self) {
+        self.channel.state.exclusive_access().read_ends -= 1;
+        self.channel.not_full.wake_all();
+    }
+}
+
+impl Drop for DatagramWriteEnd {
+    fn drop(&mut self) {
+        self.channel.state.exclusive_access().write_ends -= 1;
+        self.channel.not_empty.wake_all();
+    }
+}
+
+impl File for DatagramReadEnd {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        false
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        loop {
+            {
+                let mut state = self.channel.state.exclusive_access();
+                if let Some(message) = state.messages.pop_front() {
+                    drop(state);
+                    self.channel.not_full.wake_all();
+                    return scatter(&mut user_buf, &message);
+                }
+                if state.write_ends == 0 {
+                    return 0; // EOF: no writer left to ever send another message.
+                }
+            }
+            if self.is_nonblocking() {
+                return 0;
+            }
+            suspend_current_and_run_next();
+        }
+    }
+    fn write(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot write to the read end of a datagram socket");
+    }
+    fn is_nonblocking(&self) -> bool {
+        *self.nonblocking.exclusive_access()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        *self.nonblocking.exclusive_access() = nonblocking;
+    }
+}
+
+impl Pollable for DatagramReadEnd {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLIN) {
+            let state = self.channel.state.exclusive_access();
+            if !state.messages.is_empty() || state.write_ends == 0 {
+                ready.insert(PollEvents::POLLIN);
+            }
+        }
+        ready
+    }
+}
+
+impl File for DatagramWriteEnd {
+    fn readable(&self) -> bool {
+        false
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot read from the write end of a datagram socket");
+    }
+    fn write(&self, user_buf: UserBuffer) -> usize {
+        if self.channel.state.exclusive_access().read_ends == 0 {
+            raise_sigpipe();
+            return 0;
+        }
+        let message = gather(&user_buf);
+        let sent = message.len();
+        loop {
+            {
+                let mut state = self.channel.state.exclusive_access();
+                if state.read_ends == 0 {
+                    drop(state);
+                    raise_sigpipe();
+                    return 0;
+                }
+                if state.messages.len() < DATAGRAM_QUEUE_CAPACITY {
+                    state.messages.push_back(message);
+                    drop(state);
+                    self.channel.not_empty.wake_all();
+                    return sent;
+                }
+            }
+            if self.is_nonblocking() {
+                return 0;
+            }
+            suspend_current_and_run_next();
+        }
+    }
+    fn is_nonblocking(&self) -> bool {
+        *self.nonblocking.exclusive_access()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        *self.nonblocking.exclusive_access() = nonblocking;
+    }
+}
+
+impl Pollable for DatagramWriteEnd {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLOUT) {
+            let state = self.channel.state.exclusive_access();
+            if state.messages.len() < DATAGRAM_QUEUE_CAPACITY || state.read_ends == 0 {
+                ready.insert(PollEvents::POLLOUT);
+            }
+        }
+        ready
+    }
+}
@@ -0,0 +1,110 @@
+//! Console-backed stdin/stdout file objects, fds 0 and 1 of every process.
+
+use super::{File, PollEvents, Pollable};
+use crate::mm::UserBuffer;
+use crate::sbi::console_getchar;
+use crate::sync::UPSafeCell;
+use crate::task::suspend_current_and_run_next;
+
+pub struct Stdin {
+    nonblocking: UPSafeCell<bool>,
+}
+pub struct Stdout;
+
+impl Stdin {
+    pub fn new() -> Self {
+        Self {
+            nonblocking: unsafe { UPSafeCell::new(false) },
+        }
+    }
+}
+
+impl Default for Stdin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl File for Stdin {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        false
+    }
+    fn read(&self, mut user_buf: UserBuffer) -> usize {
+        assert_eq!(user_buf.len(), 1, "stdin only supports byte-at-a-time reads");
+        let mut c: usize;
+        loop {
+            c = console_getchar();
+            if c == 0 {
+                // `sbi::console_getchar` has no non-consuming peek, so
+                // O_NONBLOCK here means "don't loop" rather than a true
+                // EAGAIN — there's no error channel in `usize`'s return
+                // to report one through anyway.
+                if *self.nonblocking.exclusive_access() {
+                    return 0;
+                }
+                suspend_current_and_run_next();
+                continue;
+            }
+            break;
+        }
+        let ch = c as u8;
+        user_buf.buffers[0][0] = ch;
+        1
+    }
+    fn write(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot write to stdin");
+    }
+    fn is_nonblocking(&self) -> bool {
+        *self.nonblocking.exclusive_access()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        *self.nonblocking.exclusive_access() = nonblocking;
+    }
+}
+
+impl Pollable for Stdin {
+    /// There's no way to check the console for a waiting character
+    /// without consuming it (see the comment in `read`), so this reports
+    /// `POLLIN` ready whenever it's asked for: a would-be blocking
+    /// `read` that finds nothing still has to loop, but a non-blocking
+    /// one returns 0 immediately instead of hanging.
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLIN) {
+            ready.insert(PollEvents::POLLIN);
+        }
+        ready
+    }
+}
+
+impl File for Stdout {
+    fn readable(&self) -> bool {
+        false
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, _user_buf: UserBuffer) -> usize {
+        panic!("Cannot read from stdout");
+    }
+    fn write(&self, user_buf: UserBuffer) -> usize {
+        for buffer in user_buf.buffers.iter() {
+            print!("{}", core::str::from_utf8(buffer).unwrap());
+        }
+        user_buf.len()
+    }
+}
+
+impl Pollable for Stdout {
+    /// The console driver never blocks on write.
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        if interest.contains(PollEvents::POLLOUT) {
+            ready.insert(PollEvents::POLLOUT);
+        }
+        ready
+    }
+}
@@ -0,0 +1,166 @@
+//! A VFS trait layer so more than one filesystem can be mounted under
+//! paths, instead of the fd-table's `File` objects being backed by one
+//! hard-coded store. procfs ([`super::mount_procfs`]) and the FAT32
+//! adapter ([`super::mount_fat32`]) register and mount through this;
+//! `rust_main` calls neither, so the mount table starts out empty.
+
+use crate::sync::UPSafeCell;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// One node in a mounted filesystem's tree: a file or directory some
+/// path component resolved to.
+pub trait VfsInode: Send + Sync {
+    fn is_dir(&self) -> bool;
+    /// Looks up one path component among this inode's children. Only
+    /// meaningful when [`is_dir`](Self::is_dir) is true.
+    fn lookup(&self, name: &str) -> Option<Arc<dyn VfsInode>>;
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> usize;
+    fn write_at(&self, offset: usize, buf: &[u8]) -> usize;
+}
+
+/// A mountable filesystem: all a mount point needs from it is a root to
+/// start resolving paths from.
+pub trait FileSystem: Send + Sync {
+    fn root(&self) -> Arc<dyn VfsInode>;
+}
+
+/// Caches the inode a full path resolved to last time, so repeated
+/// lookups under a hot directory don't re-walk every component from
+/// its mount's root.
+struct DentryCache {
+    entries: BTreeMap<String, Arc<dyn VfsInode>>,
+}
+
+impl DentryCache {
+    const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<Arc<dyn VfsInode>> {
+        self.entries.get(path).cloned()
+    }
+
+    fn insert(&mut self, path: String, inode: Arc<dyn VfsInode>) {
+        self.entries.insert(path, inode);
+    }
+
+    /// Drops every cached path at or under `prefix`, since a `sys_umount`
+    /// there invalidates everything that was resolved through it.
+    fn invalidate_prefix(&mut self, prefix: &str) {
+        let nested = format!("{}/", prefix);
+        self.entries
+            .retain(|path, _| path != prefix && !path.starts_with(&nested));
+    }
+}
+
+struct Mount {
+    path: String,
+    fs: Arc<dyn FileSystem>,
+}
+
+/// Every currently-mounted filesystem, keyed by the path it's mounted at,
+/// plus the dentry cache built from resolving paths through them.
+pub struct MountTable {
+    mounts: Vec<Mount>,
+    dentries: DentryCache,
+}
+
+impl MountTable {
+    const fn new() -> Self {
+        Self {
+            mounts: Vec::new(),
+            dentries: DentryCache::new(),
+        }
+    }
+
+    /// Mounts `fs` at `path`. Fails if something is already mounted
+    /// there.
+    pub fn mount(&mut self, path: &str, fs: Arc<dyn FileSystem>) -> bool {
+        if self.mounts.iter().any(|m| m.path == path) {
+            return false;
+        }
+        self.mounts.push(Mount {
+            path: path.to_string(),
+            fs,
+        });
+        true
+    }
+
+    /// Unmounts whatever is mounted at `path`, invalidating any cached
+    /// dentries under it. Returns whether anything was mounted there.
+    pub fn umount(&mut self, path: &str) -> bool {
+        let before = self.mounts.len();
+        self.mounts.retain(|m| m.path != path);
+        let removed = self.mounts.len() != before;
+        if removed {
+            self.dentries.invalidate_prefix(path);
+        }
+        removed
+    }
+
+    /// The mount whose path is the longest prefix of `path`, so e.g.
+    /// `/mnt/dev/null` resolves through a filesystem mounted at
+    /// `/mnt/dev` rather than one mounted at `/`, plus what's left of
+    /// `path` once that prefix is stripped.
+    fn resolve_mount(&self, path: &str) -> Option<(&Mount, &str)> {
+        self.mounts
+            .iter()
+            .filter(|m| {
+                // A mount at `/` has no trailing component to add a `/` after.
+                let base = m.path.trim_end_matches('/');
+                path == m.path || path.starts_with(&format!("{}/", base))
+            })
+            .max_by_key(|m| m.path.len())
+            .map(|m| {
+                let remainder = path
+                    .strip_prefix(m.path.as_str())
+                    .unwrap_or("")
+                    .trim_start_matches('/');
+                (m, remainder)
+            })
+    }
+
+    /// Resolves `path` to an inode, walking from the most specific
+    /// mount's root and caching the result for next time.
+    pub fn lookup(&mut self, path: &str) -> Option<Arc<dyn VfsInode>> {
+        if let Some(inode) = self.dentries.get(path) {
+            return Some(inode);
+        }
+        let (mount, remainder) = self.resolve_mount(path)?;
+        let mut current = mount.fs.root();
+        for component in remainder.split('/').filter(|c| !c.is_empty()) {
+            current = current.lookup(component)?;
+        }
+        self.dentries.insert(path.to_string(), Arc::clone(&current));
+        Some(current)
+    }
+}
+
+static MOUNT_TABLE: UPSafeCell<MountTable> = unsafe { UPSafeCell::new(MountTable::new()) };
+
+pub fn mount_table() -> &'static UPSafeCell<MountTable> {
+    &MOUNT_TABLE
+}
+
+/// Filesystem implementations register themselves here under a type name
+/// (`"devfs"`, `"procfs"`, ...) so `sys_mount` can look one up by the name
+/// userspace passed without the mount table needing to know about every
+/// filesystem type that exists.
+static FILESYSTEM_REGISTRY: UPSafeCell<BTreeMap<String, Arc<dyn FileSystem>>> =
+    unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+pub fn register_filesystem(type_name: &str, fs: Arc<dyn FileSystem>) {
+    FILESYSTEM_REGISTRY
+        .exclusive_access()
+        .insert(type_name.to_string(), fs);
+}
+
+pub fn lookup_filesystem(type_name: &str) -> Option<Arc<dyn FileSystem>> {
+    FILESYSTEM_REGISTRY.exclusive_access().get(type_name).cloned()
+}
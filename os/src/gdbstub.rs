@@ -0,0 +1,247 @@
+//! A minimal GDB remote-serial-protocol (RSP) stub, reachable over the
+//! same SBI console `print!`/`println!` already use — there's no second
+//! UART or magic-break console switch in this tree, so [`run`] simply
+//! takes over the one console it has.
+//!
+//! Reported through `config::GDBSTUB_ENABLED`, the same kind of flag every
+//! other optional subsystem in `config` reports through; nothing checks it
+//! before running the stub, and none of these flags is wired to a real
+//! Cargo feature — see `config`'s own doc comment. Packet framing, memory read/write, and breakpoint
+//! set/clear (via `ebreak` patching, using [`crate::mm::local_fence_i`]
+//! to keep the instruction cache honest) are real and work against any
+//! address this hart can already see.
+//!
+//! Register read/write and resume/step are not: there is no
+//! [`crate::task`] trap-context type anywhere in this tree to read
+//! registers out of or single-step through (`trap::entry` saves only a
+//! bare 32-register frame), so `g`/`G`/`c`/`s` are acknowledged but report
+//! "unsupported" rather than pretending to work. [`run`] is not called
+//! from anywhere, the same as [`crate::fs::procfs::mount_procfs`]:
+//! `rust_main` calls neither.
+
+use crate::sbi::{console_getchar, console_putchar};
+use crate::sync::UPSafeCell;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The 4-byte `ebreak` encoding patched in at a breakpoint address.
+const EBREAK: u32 = 0x0010_0073;
+
+/// Breakpoint address -> the instruction word it replaced, so [`remove_breakpoint`]
+/// can restore it.
+static BREAKPOINTS: UPSafeCell<BTreeMap<usize, u32>> = unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+fn get_byte() -> u8 {
+    loop {
+        let c = console_getchar();
+        if c != usize::MAX {
+            return c as u8;
+        }
+    }
+}
+
+fn put_byte(b: u8) {
+    console_putchar(b as usize);
+}
+
+fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |acc, &b| acc.wrapping_add(b))
+}
+
+/// Reads one `$<data>#<checksum>` packet, NAK-ing and retrying until the
+/// checksum matches, then ACK-ing. Returns `data`.
+fn read_packet() -> String {
+    loop {
+        while get_byte() != b'$' {}
+        let mut data = Vec::new();
+        loop {
+            let b = get_byte();
+            if b == b'#' {
+                break;
+            }
+            data.push(b);
+        }
+        let hi = get_byte();
+        let lo = get_byte();
+        let got = hex_pair(hi, lo);
+        if got == Some(checksum(&data)) {
+            put_byte(b'+');
+            return String::from_utf8_lossy(&data).into_owned();
+        }
+        put_byte(b'-');
+    }
+}
+
+/// Sends `data` framed as `$<data>#<checksum>`.
+fn send_packet(data: &str) {
+    put_byte(b'$');
+    for b in data.bytes() {
+        put_byte(b);
+    }
+    put_byte(b'#');
+    let sum = checksum(data.as_bytes());
+    put_byte(hex_digit(sum >> 4));
+    put_byte(hex_digit(sum & 0xf));
+}
+
+fn hex_digit(n: u8) -> u8 {
+    match n {
+        0..=9 => b'0' + n,
+        _ => b'a' + (n - 10),
+    }
+}
+
+fn hex_val(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn hex_pair(hi: u8, lo: u8) -> Option<u8> {
+    Some((hex_val(hi)? << 4) | hex_val(lo)?)
+}
+
+fn parse_hex(s: &str) -> Option<usize> {
+    usize::from_str_radix(s, 16).ok()
+}
+
+/// Reads `len` bytes at `addr` and hex-encodes them for an `m` reply.
+///
+/// # Safety
+/// `addr` must be valid for `len` bytes of kernel-visible memory.
+unsafe fn read_memory(addr: usize, len: usize) -> String {
+    let bytes = core::slice::from_raw_parts(addr as *const u8, len);
+    let mut out = String::with_capacity(len * 2);
+    for &b in bytes {
+        out.push(hex_digit(b >> 4) as char);
+        out.push(hex_digit(b & 0xf) as char);
+    }
+    out
+}
+
+/// Decodes `hex` and writes it to `addr`, for an `M` command.
+///
+/// # Safety
+/// `addr` must be valid for `hex.len() / 2` bytes of kernel-visible,
+/// writable memory.
+unsafe fn write_memory(addr: usize, hex: &str) {
+    let bytes = hex.as_bytes();
+    for (i, chunk) in bytes.chunks(2).enumerate() {
+        if let [hi, lo] = *chunk {
+            if let Some(byte) = hex_pair(hi, lo) {
+                *((addr + i) as *mut u8) = byte;
+            }
+        }
+    }
+}
+
+/// Patches `ebreak` in at `addr`, recording the instruction it replaced.
+/// A no-op if a breakpoint is already set there.
+///
+/// # Safety
+/// `addr` must be a 4-byte-aligned, writable, executable kernel address.
+unsafe fn insert_breakpoint(addr: usize) {
+    let mut bps = BREAKPOINTS.exclusive_access();
+    if bps.contains_key(&addr) {
+        return;
+    }
+    let original = *(addr as *const u32);
+    *(addr as *mut u32) = EBREAK;
+    bps.insert(addr, original);
+    drop(bps);
+    crate::mm::local_fence_i();
+}
+
+/// Restores the instruction `addr` had before [`insert_breakpoint`].
+///
+/// # Safety
+/// Same as [`insert_breakpoint`].
+unsafe fn remove_breakpoint(addr: usize) {
+    if let Some(original) = BREAKPOINTS.exclusive_access().remove(&addr) {
+        *(addr as *mut u32) = original;
+        crate::mm::local_fence_i();
+    }
+}
+
+/// Handles one RSP command, returning the reply to send back (possibly
+/// empty, meaning "unsupported").
+fn handle_command(cmd: &str) -> String {
+    let mut chars = cmd.chars();
+    match chars.next() {
+        Some('?') => String::from("S05"),
+        Some('m') => {
+            let rest = chars.as_str();
+            let Some((addr, len)) = rest.split_once(',') else {
+                return String::new();
+            };
+            match (parse_hex(addr), parse_hex(len)) {
+                (Some(addr), Some(len)) => unsafe { read_memory(addr, len) },
+                _ => String::from("E01"),
+            }
+        }
+        Some('M') => {
+            let rest = chars.as_str();
+            let Some((addr_len, data)) = rest.split_once(':') else {
+                return String::from("E01");
+            };
+            let Some((addr, _len)) = addr_len.split_once(',') else {
+                return String::from("E01");
+            };
+            match parse_hex(addr) {
+                Some(addr) => {
+                    unsafe { write_memory(addr, data) };
+                    String::from("OK")
+                }
+                None => String::from("E01"),
+            }
+        }
+        Some('Z') => {
+            let rest = chars.as_str();
+            let mut parts = rest.splitn(3, ',');
+            let (Some(_kind), Some(addr), Some(_len)) = (parts.next(), parts.next(), parts.next())
+            else {
+                return String::from("E01");
+            };
+            match parse_hex(addr) {
+                Some(addr) => {
+                    unsafe { insert_breakpoint(addr) };
+                    String::from("OK")
+                }
+                None => String::from("E01"),
+            }
+        }
+        Some('z') => {
+            let rest = chars.as_str();
+            let mut parts = rest.splitn(3, ',');
+            let (Some(_kind), Some(addr), Some(_len)) = (parts.next(), parts.next(), parts.next())
+            else {
+                return String::from("E01");
+            };
+            match parse_hex(addr) {
+                Some(addr) => {
+                    unsafe { remove_breakpoint(addr) };
+                    String::from("OK")
+                }
+                None => String::from("E01"),
+            }
+        }
+        // `g`/`G` (register read/write) and `c`/`s` (continue/step) need a
+        // trap context this tree doesn't have yet; an empty reply tells
+        // GDB the command isn't supported rather than lying about it.
+        _ => String::new(),
+    }
+}
+
+/// Serves GDB RSP commands over the console forever. Not called from
+/// anywhere yet — see the module doc comment.
+pub fn run() -> ! {
+    loop {
+        let cmd = read_packet();
+        let reply = handle_command(&cmd);
+        send_packet(&reply);
+    }
+}
@@ -0,0 +1,6 @@
+//! Named, System-V/POSIX-style IPC that doesn't fit the fd-table model
+//! `fs` uses for pipes and sockets (shared memory, message queues, ...).
+
+mod mq;
+
+pub use mq::{mq_open_or_create, mq_unlink, MessageQueue, MqReceiveError, MqSendError};
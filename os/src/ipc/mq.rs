@@ -0,0 +1,179 @@
+//! POSIX-like named message queues: [`mq_open_or_create`] gets or
+//! creates a queue by name from a process-independent registry (mirroring
+//! how [`fs::register_filesystem`](crate::fs::register_filesystem) keys
+//! filesystems by name rather than by an id some other table hands out),
+//! and [`MessageQueue::send`]/[`MessageQueue::receive`] exchange
+//! priority-ordered messages on it. A blocking send to a full queue or
+//! receive from an empty one yields with `suspend_current_and_run_next`
+//! and retries, the same loop [`fs::pipe`](crate::fs) uses; each transfer
+//! calls `wake_all` on the other side's [`WaitQueue`], but no task is
+//! ever added to those queues.
+
+use crate::sync::{UPSafeCell, WaitQueue};
+use crate::task::suspend_current_and_run_next;
+use alloc::collections::{BTreeMap, BinaryHeap};
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The largest single message a queue accepts, matching the default
+/// `msgsize_max` most POSIX mq implementations ship with.
+pub const MQ_MAX_MSG_SIZE: usize = 8192;
+
+/// A queue-full [`MessageQueue::send`]/empty [`MessageQueue::receive`]
+/// in non-blocking mode reports this instead of parking the caller.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MqSendError {
+    WouldBlock,
+    MessageTooLarge,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MqReceiveError {
+    WouldBlock,
+}
+
+/// One queued message, ordered by `priority` first (higher sorts first,
+/// matching POSIX mq's "most urgent next" rule) and by arrival order
+/// second (lower `seq` sorts first, so same-priority messages stay FIFO).
+struct QueuedMessage {
+    priority: u32,
+    seq: u64,
+    data: Vec<u8>,
+}
+
+impl PartialEq for QueuedMessage {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority && self.seq == other.seq
+    }
+}
+impl Eq for QueuedMessage {}
+
+impl Ord for QueuedMessage {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+impl PartialOrd for QueuedMessage {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+struct Inner {
+    messages: BinaryHeap<QueuedMessage>,
+    max_msgs: usize,
+    next_seq: u64,
+}
+
+/// A named queue of priority-ordered messages, shared by every holder of
+/// the name it was opened under.
+pub struct MessageQueue {
+    inner: UPSafeCell<Inner>,
+    /// Signalled by `send`; no `receive` is ever queued on it.
+    not_empty: WaitQueue,
+    /// Signalled by `receive`; no `send` is ever queued on it.
+    not_full: WaitQueue,
+}
+
+impl MessageQueue {
+    fn new(max_msgs: usize) -> Arc<Self> {
+        Arc::new(Self {
+            inner: unsafe {
+                UPSafeCell::new(Inner {
+                    messages: BinaryHeap::new(),
+                    max_msgs,
+                    next_seq: 0,
+                })
+            },
+            not_empty: WaitQueue::new(),
+            not_full: WaitQueue::new(),
+        })
+    }
+
+    /// Queues `data` at `priority`, blocking while the queue already
+    /// holds `max_msgs` messages unless `nonblocking` is set.
+    pub fn send(&self, data: Vec<u8>, priority: u32, nonblocking: bool) -> Result<(), MqSendError> {
+        if data.len() > MQ_MAX_MSG_SIZE {
+            return Err(MqSendError::MessageTooLarge);
+        }
+        loop {
+            {
+                let mut inner = self.inner.exclusive_access();
+                if inner.messages.len() < inner.max_msgs {
+                    let seq = inner.next_seq;
+                    inner.next_seq += 1;
+                    inner.messages.push(QueuedMessage {
+                        priority,
+                        seq,
+                        data,
+                    });
+                    drop(inner);
+                    self.not_empty.wake_all();
+                    return Ok(());
+                }
+            }
+            if nonblocking {
+                return Err(MqSendError::WouldBlock);
+            }
+            suspend_current_and_run_next();
+        }
+    }
+
+    /// Dequeues the highest-priority (oldest among ties) message,
+    /// blocking while the queue is empty unless `nonblocking` is set.
+    pub fn receive(&self, nonblocking: bool) -> Result<(Vec<u8>, u32), MqReceiveError> {
+        loop {
+            {
+                let mut inner = self.inner.exclusive_access();
+                if let Some(msg) = inner.messages.pop() {
+                    drop(inner);
+                    self.not_full.wake_all();
+                    return Ok((msg.data, msg.priority));
+                }
+            }
+            if nonblocking {
+                return Err(MqReceiveError::WouldBlock);
+            }
+            suspend_current_and_run_next();
+        }
+    }
+
+    /// How many messages are currently queued.
+    pub fn len(&self) -> usize {
+        self.inner.exclusive_access().messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Name -> queue registry every `mq_open` call shares, so two processes
+/// (or two opens from the same one) that name the same queue reach the
+/// same underlying [`MessageQueue`].
+static MQ_REGISTRY: UPSafeCell<BTreeMap<String, Arc<MessageQueue>>> =
+    unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+/// Gets the existing queue named `name`, or creates one capped at
+/// `max_msgs` messages if none exists yet. `max_msgs` is ignored on an
+/// already-open queue, matching POSIX `mq_open`'s "attributes only take
+/// effect at creation" behavior.
+pub fn mq_open_or_create(name: &str, max_msgs: usize) -> Arc<MessageQueue> {
+    Arc::clone(
+        MQ_REGISTRY
+            .exclusive_access()
+            .entry(name.to_string())
+            .or_insert_with(|| MessageQueue::new(max_msgs)),
+    )
+}
+
+/// Removes `name` from the registry so no future `mq_open` can reach it;
+/// queues already holding an `Arc` to it keep working until they drop it,
+/// matching POSIX `mq_unlink`'s "delete the name, not the queue" rule.
+pub fn mq_unlink(name: &str) -> bool {
+    MQ_REGISTRY.exclusive_access().remove(name).is_some()
+}
@@ -0,0 +1,194 @@
+//! Kernel log subsystem: leveled, timestamped records mirrored to the
+//! console and kept in a `dmesg`-style ring buffer, so a log line isn't
+//! lost once it scrolls off the serial console.
+//!
+//! Before this there was no dedicated logging path — `println!` calls
+//! scattered through the kernel were the only log output, uncategorized
+//! and unfiltered. [`klog!`] gives them a level, a per-module runtime
+//! filter, and a place `sys_klog` (and the panic handler, via [`flush`])
+//! can read back from, without touching any existing `println!` call
+//! site.
+
+use crate::sync::UPSafeCell;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// How severe a log record is, most to least severe.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[repr(u8)]
+pub enum Level {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+impl Level {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+
+    fn from_u8(bits: u8) -> Self {
+        match bits {
+            0 => Self::Error,
+            1 => Self::Warn,
+            2 => Self::Info,
+            3 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+
+    /// ANSI color for this level, matching `lang_items.rs`'s use of color
+    /// to make fatal output stand out from ordinary log lines.
+    fn color(self) -> &'static str {
+        match self {
+            Self::Error => "\u{1b}[31m",
+            Self::Warn => "\u{1b}[33m",
+            Self::Info => "\u{1b}[32m",
+            Self::Debug => "\u{1b}[36m",
+            Self::Trace => "\u{1b}[90m",
+        }
+    }
+}
+
+const COLOR_RESET: &str = "\u{1b}[0m";
+
+/// One buffered log line.
+#[derive(Clone)]
+pub struct Record {
+    pub time_ns: u64,
+    pub hart: usize,
+    pub level: Level,
+    pub module: &'static str,
+    pub message: String,
+}
+
+/// How many records the ring keeps before dropping the oldest.
+const RING_CAPACITY: usize = 512;
+
+/// Minimum level logged when a module has no override in [`MODULE_LEVELS`].
+static DEFAULT_LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+/// Per-module level overrides, checked before falling back to
+/// [`DEFAULT_LEVEL`] — so a single noisy module can be turned up or down
+/// without changing the rest of the kernel's verbosity.
+static MODULE_LEVELS: UPSafeCell<BTreeMap<String, Level>> =
+    unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+/// The `dmesg` ring: oldest record first, capped at [`RING_CAPACITY`].
+static RING: UPSafeCell<Vec<Record>> = unsafe { UPSafeCell::new(Vec::new()) };
+
+/// Sets the fallback level used by modules with no override.
+pub fn set_default_level(level: Level) {
+    DEFAULT_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Overrides the minimum level logged for `module` specifically.
+pub fn set_module_level(module: &str, level: Level) {
+    MODULE_LEVELS
+        .exclusive_access()
+        .insert(module.to_string(), level);
+}
+
+fn effective_level(module: &str) -> Level {
+    if let Some(&level) = MODULE_LEVELS.exclusive_access().get(module) {
+        return level;
+    }
+    Level::from_u8(DEFAULT_LEVEL.load(Ordering::Relaxed))
+}
+
+/// Records `message` at `level` from `module` if it passes that module's
+/// effective filter: mirrored to the console immediately and appended to
+/// the `dmesg` ring. Called through [`klog!`] rather than directly, so
+/// `module` is always `module_path!()` at the call site.
+pub fn log(level: Level, module: &'static str, message: String) {
+    if level > effective_level(module) {
+        return;
+    }
+    let record = Record {
+        time_ns: crate::timer::get_time_ns(),
+        hart: crate::task::hart_id(),
+        level,
+        module,
+        message,
+    };
+    println!(
+        "{}[{:>5}][{:>12}.{:06}][hart {}][{}] {}{}",
+        level.color(),
+        level.name(),
+        record.time_ns / 1_000_000_000,
+        (record.time_ns % 1_000_000_000) / 1_000,
+        record.hart,
+        record.module,
+        record.message,
+        COLOR_RESET,
+    );
+    let mut ring = RING.exclusive_access();
+    if ring.len() == RING_CAPACITY {
+        ring.remove(0);
+    }
+    ring.push(record);
+}
+
+/// Drains every buffered record for `sys_klog`/`dmesg` to hand back to
+/// whoever asked (they won't be seen again through this call).
+pub fn drain() -> Vec<Record> {
+    core::mem::take(&mut *RING.exclusive_access())
+}
+
+/// Renders `records` the same way [`log`] formats them for the console,
+/// for `sys_klog` to copy out as one buffer.
+pub fn render(records: &[Record]) -> String {
+    let mut out = String::new();
+    for record in records {
+        out.push_str(&format!(
+            "[{:>5}][{:>12}.{:06}][hart {}][{}] {}\n",
+            record.level.name(),
+            record.time_ns / 1_000_000_000,
+            (record.time_ns % 1_000_000_000) / 1_000,
+            record.hart,
+            record.module,
+            record.message,
+        ));
+    }
+    out
+}
+
+/// Prints every record still in the ring without draining it, for the
+/// panic handler: a log line that never made it out over a flaky
+/// console, or scrolled past before anyone was watching, still shows up
+/// in the crash dump.
+pub fn flush() {
+    for record in RING.exclusive_access().iter() {
+        println!(
+            "[{:>5}][{:>12}.{:06}][hart {}][{}] {}",
+            record.level.name(),
+            record.time_ns / 1_000_000_000,
+            (record.time_ns % 1_000_000_000) / 1_000,
+            record.hart,
+            record.module,
+            record.message,
+        );
+    }
+}
+
+/// Logs `message` (a `format!`-style template) at `level`, tagging the
+/// record with the calling module's path. The one way every call site in
+/// this tree should log, the same as `print!`/`println!` are the one way
+/// to write to the console.
+#[macro_export]
+macro_rules! klog {
+    ($level:expr, $fmt:literal $(, $($arg:tt)+)?) => {
+        $crate::klog::log($level, module_path!(), alloc::format!($fmt $(, $($arg)+)?))
+    };
+}
@@ -0,0 +1,93 @@
+//! `#[panic_handler]` for the freestanding kernel binary.
+
+use crate::sbi::console_putchar;
+use crate::task::hart_id;
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// ANSI red, used so a panic stands out from ordinary kernel log lines
+/// even when several harts are interleaving output.
+const COLOR_RED: &str = "\u{1b}[31m";
+const COLOR_RESET: &str = "\u{1b}[0m";
+
+/// Panic records are numbered so that, on SMP, a panic on one hart that
+/// races with another hart still printing can be told apart in the log.
+static PANIC_SEQ: AtomicUsize = AtomicUsize::new(0);
+
+/// Set by whichever hart panics first, so every other hart's
+/// [`handle_panic_freeze`] knows to stop and dump rather than keep running
+/// past a dead kernel invariant.
+static PANIC_FROZEN: AtomicBool = AtomicBool::new(false);
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    let seq = PANIC_SEQ.fetch_add(1, Ordering::Relaxed);
+    let hart = hart_id();
+    if let Some(location) = info.location() {
+        println!(
+            "{}[kernel] panic #{} on hart {} at {}:{}: {}{}",
+            COLOR_RED,
+            seq,
+            hart,
+            location.file(),
+            location.line(),
+            info.message().unwrap(),
+            COLOR_RESET,
+        );
+    } else {
+        println!(
+            "{}[kernel] panic #{} on hart {}: {}{}",
+            COLOR_RED,
+            seq,
+            hart,
+            info.message().unwrap(),
+            COLOR_RESET,
+        );
+    }
+    crate::klog::flush();
+    freeze_other_harts();
+    loop {
+        console_putchar(0);
+    }
+}
+
+/// IPIs every other online hart so it stops at [`handle_panic_freeze`]
+/// instead of continuing to run (and garbling the log, or touching memory
+/// a debugger expects frozen) past this hart's fatal error.
+fn freeze_other_harts() {
+    PANIC_FROZEN.store(true, Ordering::SeqCst);
+    let this_hart = hart_id();
+    for hart in crate::task::online_harts() {
+        if hart != this_hart {
+            send_panic_ipi(hart);
+        }
+    }
+}
+
+/// Raises `hart`'s supervisor-software interrupt via SBI, the same call
+/// [`crate::mm::broadcast`]'s own `send_ipi` makes. The receiving hart's
+/// `trap::entry` handler reacts to it by calling [`handle_panic_freeze`];
+/// on a single-hart run this has no observable effect beyond the
+/// panicking hart's own halt.
+fn send_panic_ipi(hart: usize) {
+    crate::sbi::send_ipi(1 << hart, 0);
+}
+
+/// A hart's IPI handler should call this before resuming whatever it was
+/// doing; if another hart has panicked it dumps this hart's own syscall
+/// history — the nearest thing this kernel has to a stack backtrace, since
+/// there's no frame-pointer or DWARF unwinding — and halts for good, so an
+/// SMP crash dump covers every hart rather than just the one that panicked.
+/// `trap::entry`'s handler calls it for every supervisor-software
+/// interrupt.
+pub fn handle_panic_freeze() -> ! {
+    let hart = hart_id();
+    println!(
+        "{}[kernel] hart {} frozen by panic on another hart{}",
+        COLOR_RED, hart, COLOR_RESET,
+    );
+    crate::backtrace::capture(hart).print();
+    loop {
+        console_putchar(0);
+    }
+}
@@ -0,0 +1,78 @@
+//! The rCore-Tutorial-based SMP kernel entry point.
+
+#![no_std]
+#![no_main]
+#![feature(panic_info_message)]
+
+extern crate alloc;
+
+#[macro_use]
+mod console;
+mod backtrace;
+mod board;
+mod config;
+mod device_tree;
+mod drivers;
+mod fs;
+mod gdbstub;
+mod ipc;
+mod klog;
+mod lang_items;
+mod mm;
+mod net;
+mod sbi;
+mod sync;
+mod syscall;
+mod task;
+mod timer;
+mod timer_wheel;
+mod trap;
+
+core::arch::global_asm!(include_str!("entry.asm"));
+
+/// Opened by the boot hart once it's done setting up everything a
+/// secondary hart would need before scheduling tasks of its own — mm, fs,
+/// the init process. There's no secondary-hart bring-up path in this tree
+/// yet to call [`sync::BootBarrier::wait`] from (only hart 0 ever boots;
+/// see `task::hotplug`'s own doc comment on why), so nothing waits on this
+/// today, but [`rust_main`] still publishes it at the same point a real
+/// SMP boot sequence would, rather than leaving secondary-hart bring-up
+/// with no ready signal to synchronize against once it exists.
+static BOOT_READY: sync::BootBarrier = sync::BootBarrier::new();
+
+#[no_mangle]
+pub fn rust_main() -> ! {
+    println!("[kernel] booting");
+    // Installs `__alltraps` and unmasks the interrupts it now has a real
+    // handler for (see `trap::entry`'s doc comment) — done before marking
+    // this hart online, so nothing can observe it as schedulable before
+    // traps actually have somewhere to go.
+    trap::init();
+    // Hart 0 boots itself through `entry.asm`, not through
+    // `task::hotplug_bring_online`, so it has to mark itself online by
+    // hand for `task::online_harts` to see it.
+    task::mark_boot_hart_online(0);
+    // Build the init process and hand this hart to the real scheduler
+    // loop instead of idling forever with nothing ever created to
+    // schedule. `init_main` is a plain kernel function, not a loaded ELF
+    // binary: there is still no ELF parser or page table in this tree to
+    // load one into its own isolated address space (see
+    // `task::TaskControlBlock::new`'s and `mm::page_table`'s doc
+    // comments), so this only proves out task creation and context
+    // switching, not yet running a real userspace `/init`.
+    let init = task::TaskControlBlock::new(init_main, 0);
+    task::set_initproc(&init);
+    task::ready_queue().exclusive_access().enqueue(init);
+    BOOT_READY.publish();
+    task::run_tasks(0);
+}
+
+/// The init task's kernel-mode body: there is nothing to `exec` into yet
+/// (see `rust_main`'s doc comment on why), so it just idles in place —
+/// re-checking nothing, the same way `task::idle_loop` doesn't — instead
+/// of exiting and leaving the ready queue permanently empty.
+fn init_main(_arg: usize) -> ! {
+    loop {
+        task::suspend_current_and_run_next();
+    }
+}
@@ -0,0 +1,110 @@
+//! TLB and instruction-cache maintenance.
+//!
+//! Installing fresh or newly-writable code pages only brings the hart that
+//! issues `fence.i` up to date for its own pipeline. On SMP, every other
+//! hart that already cached the
+//! old translation or the old instruction bytes for that address needs to
+//! be told explicitly; there is no hardware broadcast for either fence on
+//! RISC-V.
+
+use crate::task::hart_id;
+use alloc::vec::Vec;
+
+/// Flushes this hart's instruction cache for addresses made stale by a
+/// write to code (COW resolution, demand-paged text, JIT-style codegen).
+#[inline]
+pub fn local_fence_i() {
+    unsafe {
+        core::arch::asm!("fence.i");
+    }
+}
+
+/// Flushes this hart's TLB. `vaddr = None` flushes the whole TLB for the
+/// current address space; `Some(va)` flushes just that page.
+#[inline]
+pub fn local_sfence_vma(vaddr: Option<usize>) {
+    unsafe {
+        match vaddr {
+            Some(va) => core::arch::asm!("sfence.vma {}, zero", in(reg) va),
+            None => core::arch::asm!("sfence.vma"),
+        }
+    }
+}
+
+/// Which maintenance operation a remote hart should perform.
+#[derive(Copy, Clone)]
+pub enum CacheOp {
+    FenceI,
+    SfenceVma(Option<usize>),
+}
+
+/// Pending remote maintenance requests, one queue per hart, drained by
+/// [`handle_ipi`] on the target hart. A real implementation would size this to `MAX_HARTS`; this
+/// kernel snapshot keeps a single flat list of (hart, op) pairs and relies
+/// on the IPI handler filtering by its own `hart_id()`.
+static mut PENDING: Vec<(usize, CacheOp)> = Vec::new();
+
+/// Queues `op` for every other online hart, sends each an IPI, and then
+/// performs `op` on this hart. It does not wait for the other harts to
+/// drain their entries, and the receiving trap handler does not call
+/// [`handle_ipi`], so only this hart's own fence is actually performed.
+pub fn broadcast(op: CacheOp) {
+    let this_hart = hart_id();
+    unsafe {
+        for hart in crate::task::online_harts() {
+            if hart == this_hart {
+                continue;
+            }
+            PENDING.push((hart, op));
+            send_ipi(hart);
+        }
+    }
+    match op {
+        CacheOp::FenceI => local_fence_i(),
+        CacheOp::SfenceVma(va) => local_sfence_vma(va),
+    }
+}
+
+/// Performs every maintenance operation queued for the calling hart and
+/// removes those entries from the pending queue. The trap handler does
+/// not call this (see [`broadcast`]).
+pub fn handle_ipi() {
+    let this_hart = hart_id();
+    unsafe {
+        PENDING.retain(|(hart, op)| {
+            if *hart != this_hart {
+                return true;
+            }
+            match op {
+                CacheOp::FenceI => local_fence_i(),
+                CacheOp::SfenceVma(va) => local_sfence_vma(*va),
+            }
+            false
+        });
+    }
+}
+
+/// Shoots down the TLB entries for `[start_vpn, end_vpn)` on every hart
+/// that might have them cached, after any address-space change that
+/// invalidates an existing mapping (`munmap`, `mprotect`, COW
+/// unsharing). One `sfence.vma` per page for small ranges; past
+/// [`RANGE_SHOOTDOWN_THRESHOLD`] pages it's cheaper to flush the whole TLB
+/// once than to iterate.
+const RANGE_SHOOTDOWN_THRESHOLD: usize = 64;
+
+pub fn shootdown_range(start_vpn: usize, end_vpn: usize) {
+    if end_vpn - start_vpn > RANGE_SHOOTDOWN_THRESHOLD {
+        broadcast(CacheOp::SfenceVma(None));
+        return;
+    }
+    for vpn in start_vpn..end_vpn {
+        broadcast(CacheOp::SfenceVma(Some(vpn << crate::config::PAGE_SIZE_BITS)));
+    }
+}
+
+fn send_ipi(hart: usize) {
+    crate::sbi::send_ipi(1 << hart, 0);
+    // The `ecall` above really does raise `hart`'s supervisor-software
+    // interrupt pending bit; `trap::entry`'s handler dispatches that
+    // interrupt to `lang_items::handle_panic_freeze`, not to [`handle_ipi`].
+}
@@ -0,0 +1,53 @@
+//! Demand paging of ELF `PT_LOAD` segments: an [`ElfSegment`] records one
+//! segment's file range and permissions, and [`ElfSegment::page_in`] reads
+//! in one page at a time from the backing file (or zero-fills the bss tail
+//! past the segment's file size). `exec` records no segments and no
+//! page-fault handler calls `page_in`: a page fault reaches `trap::entry`'s
+//! "unhandled trap" panic.
+
+use alloc::sync::Arc;
+use alloc::vec;
+use crate::fs::File;
+use crate::mm::UserBuffer;
+
+#[derive(Copy, Clone)]
+pub struct ElfSegment {
+    pub vaddr_start: usize,
+    pub mem_size: usize,
+    pub file_offset: usize,
+    pub file_size: usize,
+    pub writable: bool,
+    pub executable: bool,
+}
+
+impl ElfSegment {
+    /// Reads in the one page covering `fault_vaddr`, zero-filling any part
+    /// of it past `file_size` (the bss tail within a segment's last page).
+    pub fn page_in(&self, fault_vaddr: usize, page: &mut [u8], file: &Arc<dyn File>) {
+        let page_start_in_segment = (fault_vaddr - self.vaddr_start)
+            & !(crate::config::PAGE_SIZE - 1);
+        let file_pos = self.file_offset + page_start_in_segment;
+        let file_bytes_remaining = self.file_size.saturating_sub(page_start_in_segment);
+        let to_read = file_bytes_remaining.min(page.len());
+
+        if to_read > 0 {
+            // `page` is a kernel-owned physical page, not a user pointer,
+            // but `File::read_at` only speaks `UserBuffer`; reinterpret it
+            // as a `'static` slice the same way `sys_pread64` does for a
+            // validated raw pointer (see `syscall::fs::sys_pread64`).
+            let dst: &'static mut [u8] =
+                unsafe { core::slice::from_raw_parts_mut(page.as_mut_ptr(), to_read) };
+            let read = file.read_at(file_pos, UserBuffer::new(vec![dst]));
+            for b in page[read..to_read].iter_mut() {
+                *b = 0;
+            }
+        }
+        for b in page[to_read..].iter_mut() {
+            *b = 0;
+        }
+    }
+
+    pub fn contains(&self, vaddr: usize) -> bool {
+        vaddr >= self.vaddr_start && vaddr < self.vaddr_start + self.mem_size
+    }
+}
@@ -0,0 +1,193 @@
+//! Buddy physical frame allocator.
+//!
+//! [`super::ShmTable`] and [`super::ZeroFillQueue`] already track frames
+//! by physical page number and assume a frame allocator "this module
+//! doesn't own" hands them out — this is that allocator. A plain stack
+//! allocator (push/pop single free frames) can't satisfy a DMA-capable
+//! driver's need for a physically contiguous multi-page region (virtio
+//! queues, a future NIC ring), so this is a classic buddy system over the
+//! range [`super::detect_range`] reports, exposing power-of-two
+//! contiguous allocation instead.
+//!
+//! Freed frames are poisoned and use-after-free/double-free are checked
+//! via [`super::kasan`] when `config::KASAN_ENABLED` is set.
+
+use crate::sync::UPSafeCell;
+use alloc::vec::Vec;
+
+/// Largest block size this allocator will hand out, in pages:
+/// `2^MAX_ORDER` pages (1 GiB at a 4 KiB page size), comfortably above
+/// any single DMA ring this kernel's drivers need today.
+const MAX_ORDER: usize = 18;
+
+/// Snapshot of frame usage, rendered into `/proc/meminfo`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FrameStats {
+    pub total_frames: usize,
+    pub allocated_frames: usize,
+    pub free_frames: usize,
+    /// The most frames ever allocated at once, for spotting a high-water
+    /// mark that transient current usage wouldn't show.
+    pub peak_allocated_frames: usize,
+    pub alloc_count: usize,
+    pub dealloc_count: usize,
+    /// How many times [`FrameAllocator::alloc_contiguous`] returned
+    /// `None` because nothing big enough was free.
+    pub oom_count: usize,
+}
+
+/// A buddy allocator over `[base_ppn, base_ppn + total_frames)`.
+///
+/// Free blocks are tracked per order as page numbers *relative* to
+/// `base_ppn`, so buddy addresses (`rel ^ (1 << order)`) don't depend on
+/// where in physical memory the managed range happens to start.
+pub struct FrameAllocator {
+    free_lists: Vec<Vec<usize>>,
+    base_ppn: usize,
+    total_frames: usize,
+    allocated_frames: usize,
+    peak_allocated_frames: usize,
+    alloc_count: usize,
+    dealloc_count: usize,
+    oom_count: usize,
+}
+
+impl FrameAllocator {
+    /// Builds an allocator over the physical byte range `[start, end)`
+    /// (e.g. from [`super::detect_range`]), filling it with the largest
+    /// aligned power-of-two blocks that fit, the standard way to seed a
+    /// buddy system from an arbitrary-sized region.
+    pub fn new(range: (usize, usize)) -> Self {
+        let page_size = crate::config::PAGE_SIZE;
+        let base_ppn = (range.0 + page_size - 1) / page_size;
+        let end_ppn = range.1 / page_size;
+        let total_frames = end_ppn.saturating_sub(base_ppn);
+
+        let mut free_lists = alloc::vec![Vec::new(); MAX_ORDER + 1];
+        let mut rel = 0usize;
+        let mut remaining = total_frames;
+        while remaining > 0 {
+            let mut order = MAX_ORDER;
+            while order > 0 && ((1usize << order) > remaining || rel % (1usize << order) != 0) {
+                order -= 1;
+            }
+            free_lists[order].push(rel);
+            let block = 1usize << order;
+            rel += block;
+            remaining -= block;
+        }
+
+        Self {
+            free_lists,
+            base_ppn,
+            total_frames,
+            allocated_frames: 0,
+            peak_allocated_frames: 0,
+            alloc_count: 0,
+            dealloc_count: 0,
+            oom_count: 0,
+        }
+    }
+
+    /// Allocates `2^order` physically contiguous frames, returning the
+    /// starting physical page number. Splits a larger free block down to
+    /// size if no block of exactly `order` is free. Returns `None`
+    /// (bumping [`FrameStats::oom_count`]) rather than panicking if
+    /// nothing big enough is free — callers (e.g. [`super::SlabCache`])
+    /// propagate that instead of assuming a frame is always available.
+    pub fn alloc_contiguous(&mut self, order: usize) -> Option<usize> {
+        if order > MAX_ORDER {
+            self.oom_count += 1;
+            return None;
+        }
+        let mut current = order;
+        while current <= MAX_ORDER && self.free_lists[current].is_empty() {
+            current += 1;
+        }
+        if current > MAX_ORDER {
+            self.oom_count += 1;
+            return None;
+        }
+        let mut rel = self.free_lists[current].pop().unwrap();
+        while current > order {
+            current -= 1;
+            self.free_lists[current].push(rel + (1usize << current));
+        }
+        self.allocated_frames += 1usize << order;
+        self.peak_allocated_frames = self.peak_allocated_frames.max(self.allocated_frames);
+        self.alloc_count += 1;
+        let ppn = self.base_ppn + rel;
+        if crate::config::KASAN_ENABLED {
+            let addr = ppn << crate::config::PAGE_SIZE_BITS;
+            let len = (1usize << order) * crate::config::PAGE_SIZE;
+            unsafe {
+                super::kasan::check_on_alloc(addr, len);
+            }
+        }
+        Some(ppn)
+    }
+
+    /// Frees the `2^order`-frame block starting at physical page number
+    /// `ppn` (as returned by [`alloc_contiguous`](Self::alloc_contiguous)),
+    /// merging it with its buddy (and that merge's buddy, and so on) as
+    /// far up as the neighboring blocks are free.
+    pub fn dealloc(&mut self, ppn: usize, order: usize) {
+        self.allocated_frames -= 1usize << order;
+        self.dealloc_count += 1;
+        if crate::config::KASAN_ENABLED {
+            let addr = ppn << crate::config::PAGE_SIZE_BITS;
+            let len = (1usize << order) * crate::config::PAGE_SIZE;
+            unsafe {
+                super::kasan::poison_on_free(addr, len);
+            }
+        }
+        let mut rel = ppn - self.base_ppn;
+        let mut order = order;
+        while order < MAX_ORDER {
+            let buddy = rel ^ (1usize << order);
+            let list = &mut self.free_lists[order];
+            match list.iter().position(|&b| b == buddy) {
+                Some(pos) => {
+                    list.remove(pos);
+                    rel = rel.min(buddy);
+                    order += 1;
+                }
+                None => break,
+            }
+        }
+        self.free_lists[order].push(rel);
+    }
+
+    pub fn stats(&self) -> FrameStats {
+        FrameStats {
+            total_frames: self.total_frames,
+            allocated_frames: self.allocated_frames,
+            free_frames: self.total_frames - self.allocated_frames,
+            peak_allocated_frames: self.peak_allocated_frames,
+            alloc_count: self.alloc_count,
+            dealloc_count: self.dealloc_count,
+            oom_count: self.oom_count,
+        }
+    }
+}
+
+/// The system-wide frame allocator, set up once [`init`] runs against a
+/// real DTB-derived range; `None` beforehand (or in this tree today,
+/// always, since nothing calls [`init`] yet — there's no boot-time DTB
+/// parsing wired up either, per [`crate::device_tree::parse`]'s own
+/// doc comment).
+static FRAME_ALLOCATOR: UPSafeCell<Option<FrameAllocator>> = unsafe { UPSafeCell::new(None) };
+
+/// Installs the system-wide frame allocator over `range`, replacing
+/// whichever one (if any) was installed before.
+pub fn init(range: (usize, usize)) {
+    *FRAME_ALLOCATOR.exclusive_access() = Some(FrameAllocator::new(range));
+}
+
+/// The system-wide frame allocator's current usage, if [`init`] has run.
+pub fn global_stats() -> Option<FrameStats> {
+    FRAME_ALLOCATOR
+        .exclusive_access()
+        .as_ref()
+        .map(FrameAllocator::stats)
+}
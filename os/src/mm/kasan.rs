@@ -0,0 +1,67 @@
+//! Kernel address-sanitizer-lite: poisons freed memory and tracks which
+//! addresses are currently free, so a double free or a write-after-free
+//! is caught at the next allocation instead of silently corrupting
+//! whatever the memory gets reused for.
+//!
+//! Gated behind `config::KASAN_ENABLED`, a plain `const` rather than a
+//! cargo feature (see `config`'s doc comment), so setting it to `false`
+//! compiles the checks away — [`super::FrameAllocator`] and
+//! [`super::SlabCache`] check that constant at their alloc/dealloc call
+//! sites rather than `#[cfg]`-ing themselves, the same as every other
+//! optional subsystem in `config`.
+//!
+//! There's no unwinding or symbolized backtrace anywhere in this tree —
+//! `lang_items.rs`'s panic handler prints a file/line and message, and
+//! `backtrace` only records recent syscalls — so a detected corruption is
+//! reported the same way every other kernel-fatal condition in this tree
+//! is: a `panic!` with enough detail (address, what was expected) to
+//! debug from the log.
+
+use crate::sync::UPSafeCell;
+use alloc::collections::BTreeSet;
+
+/// Repeating byte pattern written across freed memory. Distinctive
+/// enough that a stray read of freed memory is obviously wrong rather
+/// than plausible leftover data.
+const POISON_BYTE: u8 = 0xA5;
+
+/// Addresses currently poisoned and awaiting reallocation. Only ever
+/// non-empty when [`crate::config::KASAN_ENABLED`] is set.
+static FREED: UPSafeCell<BTreeSet<usize>> = unsafe { UPSafeCell::new(BTreeSet::new()) };
+
+/// Fills `len` bytes at `addr` with the poison pattern and records `addr`
+/// as freed. Panics if `addr` was already recorded as freed — a double
+/// free, caught here rather than corrupting whatever gets allocated next
+/// at the same address.
+///
+/// # Safety
+/// `addr` must be a kernel-visible address (identity-mapped physical
+/// address or heap pointer) with `len` bytes valid and not concurrently
+/// accessed by anything else.
+pub unsafe fn poison_on_free(addr: usize, len: usize) {
+    let mut freed = FREED.exclusive_access();
+    if !freed.insert(addr) {
+        panic!("kasan: double free at {:#x}", addr);
+    }
+    drop(freed);
+    core::slice::from_raw_parts_mut(addr as *mut u8, len).fill(POISON_BYTE);
+}
+
+/// Clears `addr`'s freed marker and panics if its contents were written
+/// to while free — a use-after-free. A no-op (not an error) for `addr`
+/// that was never freed under tracking, e.g. a block handed out for the
+/// first time.
+///
+/// # Safety
+/// Same as [`poison_on_free`].
+pub unsafe fn check_on_alloc(addr: usize, len: usize) {
+    if !FREED.exclusive_access().remove(&addr) {
+        return;
+    }
+    let corrupted = core::slice::from_raw_parts(addr as *const u8, len)
+        .iter()
+        .any(|&b| b != POISON_BYTE);
+    if corrupted {
+        panic!("kasan: write to freed memory at {:#x} detected on realloc", addr);
+    }
+}
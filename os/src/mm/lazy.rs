@@ -0,0 +1,41 @@
+//! Lazy (demand-paged) allocation for `mmap` and `sbrk` regions: growing
+//! either only reserves virtual address space and records what backs the
+//! area, instead of eagerly allocating and zeroing every page up front.
+//! No fault handler resolves a first access to such a page by allocating
+//! a frame: a page fault reaches `trap::entry`'s "unhandled trap" panic.
+
+/// What a lazily-backed region should do when a page within it first
+/// faults.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LazyKind {
+    /// Anonymous memory (heap growth via `sbrk`, or `MAP_ANONYMOUS`):
+    /// allocate a zeroed frame and map it.
+    AnonZeroFill,
+    /// File-backed mapping: read the backing page in from the file at the
+    /// appropriate offset before mapping it.
+    FileBacked { fd: usize, file_offset: usize },
+}
+
+/// A virtual page range whose frames have not been allocated yet.
+pub struct LazyArea {
+    pub vpn_range: core::ops::Range<usize>,
+    pub kind: LazyKind,
+}
+
+impl LazyArea {
+    pub fn contains(&self, vpn: usize) -> bool {
+        self.vpn_range.contains(&vpn)
+    }
+
+    /// Offset into the backing file a given page fault within this area
+    /// should read from, for [`LazyKind::FileBacked`] areas.
+    pub fn file_offset_for(&self, vpn: usize) -> Option<(usize, usize)> {
+        match self.kind {
+            LazyKind::FileBacked { fd, file_offset } => {
+                let page_index = vpn - self.vpn_range.start;
+                Some((fd, file_offset + page_index * crate::config::PAGE_SIZE))
+            }
+            LazyKind::AnonZeroFill => None,
+        }
+    }
+}
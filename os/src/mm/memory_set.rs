@@ -0,0 +1,227 @@
+//! An address space: the ordered set of mapped regions backing one
+//! process, plus the invariants that must hold between them.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+bitflags::bitflags! {
+    /// Mirrors the `PROT_*` bits `mprotect`/`mmap` accept.
+    #[derive(Copy, Clone)]
+    pub struct MapPermission: u8 {
+        const R = 1 << 0;
+        const W = 1 << 1;
+        const X = 1 << 2;
+    }
+}
+
+/// One contiguous mapped region within an address space.
+pub struct MapArea {
+    pub name: &'static str,
+    pub vpn_range: Range<usize>,
+    pub perm: MapPermission,
+}
+
+pub struct MemorySet {
+    pub areas: Vec<MapArea>,
+}
+
+impl MemorySet {
+    /// An address space with no mapped regions yet: what every task's
+    /// `TaskControlBlockInner::mm` starts out as before its loader (or,
+    /// for `mmap`, its first call) pushes the first area onto it.
+    pub fn new() -> Self {
+        Self { areas: Vec::new() }
+    }
+}
+
+impl Default for MemorySet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemorySet {
+    /// Pages covered by this address space's areas. Areas own no frames,
+    /// so this counts reserved virtual pages, including lazily backed ones
+    /// never touched, and does not tell shared copy-on-write pages apart.
+    /// `sys_getrss` does not use it.
+    pub fn rss_pages(&self) -> usize {
+        self.areas
+            .iter()
+            .map(|a| a.vpn_range.end - a.vpn_range.start)
+            .sum()
+    }
+
+    /// Debug-only invariant check, run after every mutation to the area
+    /// list (`unmap_range`, `set_brk`, `protect_range`, and `sys_mmap`'s
+    /// push); `exec` and task creation do not run it. No two areas
+    /// overlap (which also keeps the heap clear of the mmap region and
+    /// the stack), and the trampoline/trap-context pages are never among
+    /// the user-mutable areas.
+    ///
+    /// This exists because `mmap` of a fixed low address has silently
+    /// corrupted the heap layout before; panicking here turns that into an
+    /// immediate, attributable failure instead of garbled heap data
+    /// discovered much later.
+    #[cfg(debug_assertions)]
+    pub fn check_invariants(&self) {
+        for (i, a) in self.areas.iter().enumerate() {
+            for b in self.areas.iter().skip(i + 1) {
+                assert!(
+                    !ranges_overlap(&a.vpn_range, &b.vpn_range),
+                    "overlapping map areas: {} [{:#x}, {:#x}) vs {} [{:#x}, {:#x})",
+                    a.name,
+                    a.vpn_range.start,
+                    a.vpn_range.end,
+                    b.name,
+                    b.vpn_range.start,
+                    b.vpn_range.end,
+                );
+            }
+            assert!(
+                a.name != "trampoline" && a.name != "trap_context",
+                "trampoline/trap-context area must never be remapped: {}",
+                a.name
+            );
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn check_invariants(&self) {}
+}
+
+fn ranges_overlap(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+impl MemorySet {
+    /// Unmaps `[start_vpn, end_vpn)`, which may cover only part of one or
+    /// more existing areas. An area fully covered is removed outright; one
+    /// only partially covered is split into the surviving sub-range(s) (up
+    /// to two, if the unmapped range falls in the middle), unlike a naive
+    /// munmap that only handles whole-area removal.
+    pub fn unmap_range(&mut self, start_vpn: usize, end_vpn: usize) {
+        let target = start_vpn..end_vpn;
+        let mut result = Vec::with_capacity(self.areas.len());
+        for area in self.areas.drain(..) {
+            if !ranges_overlap(&area.vpn_range, &target) {
+                result.push(area);
+                continue;
+            }
+            if area.vpn_range.start < target.start {
+                result.push(MapArea {
+                    name: area.name,
+                    vpn_range: area.vpn_range.start..target.start,
+                    perm: area.perm,
+                });
+            }
+            if area.vpn_range.end > target.end {
+                result.push(MapArea {
+                    name: area.name,
+                    vpn_range: target.end..area.vpn_range.end,
+                    perm: area.perm,
+                });
+            }
+        }
+        self.areas = result;
+        self.check_invariants();
+    }
+
+    /// `brk`: grows or shrinks the `"heap"` area to end at `new_end_vpn`,
+    /// creating it starting at `heap_start_vpn` if this is the first call.
+    /// Fails if the new break would retreat before the heap's start or
+    /// collide with another area.
+    pub fn set_brk(&mut self, heap_start_vpn: usize, new_end_vpn: usize) -> Result<(), ()> {
+        if new_end_vpn < heap_start_vpn {
+            return Err(());
+        }
+        let target = heap_start_vpn..new_end_vpn;
+        let collides = self
+            .areas
+            .iter()
+            .any(|a| a.name != "heap" && ranges_overlap(&a.vpn_range, &target));
+        if collides {
+            return Err(());
+        }
+        match self.areas.iter_mut().find(|a| a.name == "heap") {
+            Some(area) => area.vpn_range = target,
+            None => self.areas.push(MapArea {
+                name: "heap",
+                vpn_range: target,
+                perm: MapPermission::R | MapPermission::W,
+            }),
+        }
+        self.check_invariants();
+        Ok(())
+    }
+
+    /// Searches `[search_start_vpn, search_end_vpn)` for the first gap of
+    /// at least `len_pages` pages whose start is aligned to `align_pages`,
+    /// so `mmap(addr=0)` can let the kernel pick a mapping address instead
+    /// of requiring the caller to name an unused one. Returns the chosen
+    /// start VPN, or `None` if no gap that large exists in range.
+    pub fn find_free_area(
+        &self,
+        len_pages: usize,
+        align_pages: usize,
+        search_start_vpn: usize,
+        search_end_vpn: usize,
+    ) -> Option<usize> {
+        let align = align_pages.max(1);
+        let align_up = |vpn: usize| (vpn + align - 1) / align * align;
+
+        let mut sorted: Vec<&Range<usize>> = self.areas.iter().map(|a| &a.vpn_range).collect();
+        sorted.sort_by_key(|r| r.start);
+
+        let mut candidate = align_up(search_start_vpn);
+        for range in sorted {
+            if range.start >= search_end_vpn {
+                break;
+            }
+            if candidate + len_pages <= range.start {
+                return Some(candidate);
+            }
+            if range.end > candidate {
+                candidate = align_up(range.end);
+            }
+        }
+        (candidate + len_pages <= search_end_vpn).then_some(candidate)
+    }
+
+    /// `mprotect`: changes the permission bits of every area overlapping
+    /// `[start_vpn, end_vpn)`. Like `unmap_range`, splits an area that is
+    /// only partially covered so the rest keeps its old permissions.
+    pub fn protect_range(&mut self, start_vpn: usize, end_vpn: usize, perm: MapPermission) {
+        let target = start_vpn..end_vpn;
+        let mut result = Vec::with_capacity(self.areas.len() + 1);
+        for area in self.areas.drain(..) {
+            if !ranges_overlap(&area.vpn_range, &target) {
+                result.push(area);
+                continue;
+            }
+            let clamped_start = area.vpn_range.start.max(target.start);
+            let clamped_end = area.vpn_range.end.min(target.end);
+            if area.vpn_range.start < clamped_start {
+                result.push(MapArea {
+                    name: area.name,
+                    vpn_range: area.vpn_range.start..clamped_start,
+                    perm: area.perm,
+                });
+            }
+            result.push(MapArea {
+                name: area.name,
+                vpn_range: clamped_start..clamped_end,
+                perm,
+            });
+            if clamped_end < area.vpn_range.end {
+                result.push(MapArea {
+                    name: area.name,
+                    vpn_range: clamped_end..area.vpn_range.end,
+                    perm: area.perm,
+                });
+            }
+        }
+        self.areas = result;
+        self.check_invariants();
+    }
+}
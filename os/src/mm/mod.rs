@@ -0,0 +1,34 @@
+//! Address space and memory management.
+//!
+//! Only the pieces needed by the rest of the kernel so far are present;
+//! this module grows alongside the features that need it.
+
+mod cache;
+mod elf_demand_page;
+mod frame_allocator;
+mod kasan;
+mod lazy;
+mod memory_set;
+mod page_table;
+mod phys_mem;
+mod shm;
+mod slab;
+mod swap;
+mod user_buffer;
+mod zero_fill;
+
+pub use cache::{broadcast, handle_ipi, local_fence_i, local_sfence_vma, shootdown_range, CacheOp};
+pub use elf_demand_page::ElfSegment;
+pub use frame_allocator::{
+    global_stats as frame_allocator_stats, init as init_frame_allocator, FrameAllocator,
+    FrameStats,
+};
+pub use lazy::{LazyArea, LazyKind};
+pub use memory_set::{MapArea, MapPermission, MemorySet};
+pub use page_table::{copy_cstr_from_user, copy_from_user, copy_to_user, validate_user_slice, EFAULT};
+pub use phys_mem::detect_range;
+pub use shm::{ShmId, ShmKey, ShmSegment, ShmTable};
+pub use slab::{SlabAllocator, SlabCache};
+pub use swap::{SwapSlot, SwapSpace};
+pub use user_buffer::UserBuffer;
+pub use zero_fill::ZeroFillQueue;
@@ -0,0 +1,82 @@
+//! Validated copies between kernel and user pointers.
+//!
+//! This tree has no separate page table or kernel/user address-space
+//! isolation yet (see `mm::mod`'s own doc comment on how little of
+//! address-space management exists so far) — every syscall that takes a
+//! user pointer today dereferences it directly, the same way
+//! `sys_gettimeofday`/`sys_getrusage` did before this module. What these
+//! helpers add isn't page-table translation (there's no page table to
+//! translate through, so there's no page split to handle either); it's
+//! the null/alignment checking those hand-rolled dereferences lacked,
+//! reported back as [`EFAULT`] instead of a kernel panic, behind one
+//! shared copy path instead of one per call site.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Returned in place of a panic when a user pointer can't be trusted.
+/// Matches the Linux `EFAULT` errno value, the same convention other
+/// syscalls here use for their raw `isize` return (e.g. plain `-1`).
+pub const EFAULT: isize = -14;
+
+fn is_aligned_for<T>(ptr: *const u8) -> bool {
+    (ptr as usize) % core::mem::align_of::<T>() == 0
+}
+
+/// Writes `value` to `*dst`. Fails with [`EFAULT`] instead of panicking
+/// if `dst` is null or misaligned for `T`.
+pub fn copy_to_user<T>(dst: *mut T, value: T) -> Result<(), isize> {
+    if dst.is_null() || !is_aligned_for::<T>(dst as *const u8) {
+        return Err(EFAULT);
+    }
+    unsafe {
+        dst.write(value);
+    }
+    Ok(())
+}
+
+/// Reads `*src` into an owned `T`. Fails with [`EFAULT`] instead of
+/// panicking if `src` is null or misaligned for `T`.
+pub fn copy_from_user<T: Copy>(src: *const T) -> Result<T, isize> {
+    if src.is_null() || !is_aligned_for::<T>(src as *const u8) {
+        return Err(EFAULT);
+    }
+    Ok(unsafe { src.read() })
+}
+
+/// Checks that `[ptr, ptr + len)` is at least plausibly a real user
+/// buffer before it's turned into a slice: non-null, and not so long it
+/// wraps the address space. It does not check the range against the
+/// calling task's `mm` areas or their permissions, so a non-null pointer
+/// into unmapped memory passes — the same shallow kind of check as
+/// [`copy_to_user`]'s null/alignment check.
+pub fn validate_user_slice(ptr: *const u8, len: usize) -> Result<(), isize> {
+    if ptr.is_null() {
+        return Err(EFAULT);
+    }
+    if (ptr as usize).checked_add(len).is_none() {
+        return Err(EFAULT);
+    }
+    Ok(())
+}
+
+/// Reads a NUL-terminated string out of user space, stopping at the first
+/// NUL byte or after `max_len` bytes, whichever comes first — for
+/// syscalls that take a `*const u8` path/name argument instead of a
+/// `(ptr, len)` pair. Fails with [`EFAULT`] if `src` is null, or if no
+/// NUL byte turns up within `max_len` bytes (a runaway read is as much a
+/// fault as a bad pointer).
+pub fn copy_cstr_from_user(src: *const u8, max_len: usize) -> Result<String, isize> {
+    if src.is_null() {
+        return Err(EFAULT);
+    }
+    let mut bytes = Vec::with_capacity(max_len.min(256));
+    for i in 0..max_len {
+        let byte = unsafe { *src.add(i) };
+        if byte == 0 {
+            return Ok(String::from_utf8_lossy(&bytes).into_owned());
+        }
+        bytes.push(byte);
+    }
+    Err(EFAULT)
+}
@@ -0,0 +1,21 @@
+//! Runtime physical memory sizing from the device tree.
+//!
+//! Turns the DTB's `memory` node into the physical address range available
+//! for general-purpose allocation: the range the system-wide frame
+//! allocator's [`init`](super::init_frame_allocator) takes, in place of a
+//! hard-coded `MEMORY_END`. Address spaces ([`super::MemorySet`]) are only
+//! lists of virtual page-number ranges and own no frames from it.
+
+use crate::device_tree::DeviceTree;
+
+/// The physical address range `[start, end)` available for
+/// general-purpose allocation, as described by `tree`'s memory node.
+///
+/// Returns `None` if `tree` has no memory node (e.g. [`parse`] was never
+/// run against a real DTB) — there's no sane fixed size to fall back to
+/// on a real board that could differ from whatever was guessed.
+///
+/// [`parse`]: crate::device_tree::parse
+pub fn detect_range(tree: &DeviceTree) -> Option<(usize, usize)> {
+    tree.memory.map(|(base, size)| (base, base + size))
+}
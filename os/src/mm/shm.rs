@@ -0,0 +1,79 @@
+//! System-V-style shared memory segments: a `shmget` key maps to a set of
+//! physical frames that any process can `shmat` into its own address
+//! space, independent of the COW/fork sharing paths.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+pub type ShmKey = i32;
+pub type ShmId = usize;
+
+pub struct ShmSegment {
+    pub key: ShmKey,
+    pub size_pages: usize,
+    /// Physical page numbers backing the segment, allocated once at
+    /// `shmget` time and shared by every attaching process thereafter.
+    pub frames: Vec<usize>,
+    pub attach_count: usize,
+}
+
+#[derive(Default)]
+pub struct ShmTable {
+    segments: BTreeMap<ShmId, ShmSegment>,
+    by_key: BTreeMap<ShmKey, ShmId>,
+    next_id: ShmId,
+}
+
+impl ShmTable {
+    /// Returns the existing segment for `key`, or allocates one of
+    /// `size_pages` physical frames (supplied by the caller, since frame
+    /// allocation lives in the frame allocator this module doesn't own).
+    pub fn get_or_create(
+        &mut self,
+        key: ShmKey,
+        size_pages: usize,
+        alloc_frames: impl FnOnce(usize) -> Vec<usize>,
+    ) -> ShmId {
+        if let Some(&id) = self.by_key.get(&key) {
+            return id;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.segments.insert(
+            id,
+            ShmSegment {
+                key,
+                size_pages,
+                frames: alloc_frames(size_pages),
+                attach_count: 0,
+            },
+        );
+        self.by_key.insert(key, id);
+        id
+    }
+
+    pub fn attach(&mut self, id: ShmId) -> Option<&[usize]> {
+        let seg = self.segments.get_mut(&id)?;
+        seg.attach_count += 1;
+        Some(&seg.frames)
+    }
+
+    /// Detaches one reference; the segment's frames are only actually
+    /// freed once the last attacher detaches (System V semantics leave the
+    /// segment alive with zero attachments until explicitly removed, but
+    /// this kernel frees eagerly since it has no `shmctl(IPC_RMID)` yet).
+    /// Freeing removes the segment and its key and hands its frames to
+    /// `free_frames`, the counterpart of `get_or_create`'s `alloc_frames`.
+    pub fn detach(&mut self, id: ShmId, free_frames: impl FnOnce(Vec<usize>)) {
+        let Some(seg) = self.segments.get_mut(&id) else {
+            return;
+        };
+        seg.attach_count = seg.attach_count.saturating_sub(1);
+        if seg.attach_count == 0 {
+            if let Some(seg) = self.segments.remove(&id) {
+                self.by_key.remove(&seg.key);
+                free_frames(seg.frames);
+            }
+        }
+    }
+}
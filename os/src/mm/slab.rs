@@ -0,0 +1,176 @@
+//! Slab/object-cache allocator for fixed-size kernel objects.
+//!
+//! [`super::FrameAllocator`] hands out whole pages (or bigger,
+//! power-of-two contiguous runs); carving a single small kernel object
+//! (a `TaskControlBlock`, a `TrapContext`-sized block, a 64/128/256-byte
+//! structure) out of a whole page through the buddy allocator on every
+//! alloc/free would fragment badly and waste most of the page. A
+//! [`SlabCache`] instead claims one page at a time and slices it into
+//! identically-sized objects, tracked by a free list of object addresses
+//! kept in a `Vec<usize>` beside the objects rather than inside them —
+//! carving the page up relies on the same "a physical page number is a
+//! directly-dereferenceable pointer" assumption the idle-time
+//! frame-zeroing pass already relies on.
+//!
+//! [`SlabAllocator`] keeps one [`SlabCache`] per common size, each
+//! fronted by a per-hart magazine (see [`crate::task::PerCpu`]) of
+//! recently freed objects, so a hart allocating and freeing objects of
+//! the same size repeatedly doesn't keep touching the cache's shared
+//! free list.
+//!
+//! There's no `#[global_allocator]` anywhere in this tree yet to route
+//! through this (`config::KERNEL_HEAP_SIZE` reserves a heap size nothing
+//! currently initializes) — this lands the layer a future `GlobalAlloc`
+//! impl would hand its small, fixed-size requests to, the same way the
+//! buddy allocator it sits on landed ahead of `KERNEL_SPACE`.
+//!
+//! Freed objects are poisoned and use-after-free/double-free are checked
+//! via [`super::kasan`] when `config::KASAN_ENABLED` is set.
+
+use crate::config::PAGE_SIZE;
+use crate::sync::UPSafeCell;
+use crate::task::PerCpu;
+use alloc::vec::Vec;
+
+/// How many freed objects a hart's magazine holds before it spills half
+/// of them back to the cache's shared free list, and how many it grabs
+/// at once (growing the shared list with a fresh page if that's empty
+/// too) when its own magazine runs dry.
+const MAGAZINE_SIZE: usize = 32;
+
+/// The fixed-size tiers this allocator keeps ready: the three small
+/// power-of-two sizes, plus one page-sized tier for single-page kernel
+/// objects (a `TaskControlBlock`, a `TrapContext`-sized block) that
+/// would otherwise force a whole buddy allocation per object.
+const CACHE_SIZES: [usize; 4] = [64, 128, 256, PAGE_SIZE];
+
+/// One free-list cache for objects of a fixed size, grown a
+/// page at a time via a caller-supplied frame source (frame allocation
+/// lives in [`super::FrameAllocator`], which this module doesn't own).
+pub struct SlabCache {
+    obj_size: usize,
+    objs_per_slab: usize,
+    free: UPSafeCell<Vec<usize>>,
+    magazines: PerCpu<Vec<usize>>,
+}
+
+impl SlabCache {
+    pub fn new(obj_size: usize) -> Self {
+        let obj_size = obj_size.max(core::mem::size_of::<usize>());
+        Self {
+            obj_size,
+            objs_per_slab: PAGE_SIZE / obj_size,
+            free: unsafe { UPSafeCell::new(Vec::new()) },
+            magazines: unsafe { PerCpu::new_with(Vec::new) },
+        }
+    }
+
+    /// Allocates one object, returning its address, or `None` if both
+    /// this hart's magazine and the shared free list are empty and
+    /// `alloc_page` (called at most once, for a fresh page) also fails —
+    /// the out-of-memory case, reported rather than panicked on so a
+    /// caller several frames up (a syscall handler) can turn it into
+    /// `ENOMEM` instead of taking the whole kernel down.
+    pub fn alloc(&self, alloc_page: impl FnOnce() -> Option<usize>) -> Option<usize> {
+        let mut magazine = self.magazines.get().exclusive_access();
+        if magazine.is_empty() && !self.refill(&mut magazine, alloc_page) {
+            return None;
+        }
+        let addr = magazine.pop()?;
+        if crate::config::KASAN_ENABLED {
+            unsafe {
+                super::kasan::check_on_alloc(addr, self.obj_size);
+            }
+        }
+        Some(addr)
+    }
+
+    /// Frees an object this cache previously handed out, returning it to
+    /// this hart's magazine, spilling half of it back to the shared free
+    /// list if the magazine is now over [`MAGAZINE_SIZE`].
+    pub fn dealloc(&self, addr: usize) {
+        if crate::config::KASAN_ENABLED {
+            unsafe {
+                super::kasan::poison_on_free(addr, self.obj_size);
+            }
+        }
+        let mut magazine = self.magazines.get().exclusive_access();
+        magazine.push(addr);
+        if magazine.len() > MAGAZINE_SIZE {
+            let keep = MAGAZINE_SIZE / 2;
+            let mut shared = self.free.exclusive_access();
+            shared.extend(magazine.drain(keep..));
+        }
+    }
+
+    /// Tops up `magazine` from the shared free list, growing that list
+    /// with a fresh page from `alloc_page` first if it's empty too.
+    /// Returns `false` (leaving `magazine` untouched) if the shared list
+    /// was empty and `alloc_page` couldn't supply a page either.
+    fn refill(
+        &self,
+        magazine: &mut Vec<usize>,
+        alloc_page: impl FnOnce() -> Option<usize>,
+    ) -> bool {
+        let mut shared = self.free.exclusive_access();
+        if shared.is_empty() {
+            let page = match alloc_page() {
+                Some(page) => page,
+                None => return false,
+            };
+            for i in 0..self.objs_per_slab {
+                shared.push(page + i * self.obj_size);
+            }
+        }
+        let take = MAGAZINE_SIZE.min(shared.len());
+        let split = shared.len() - take;
+        magazine.extend(shared.drain(split..));
+        true
+    }
+}
+
+/// The full set of common-size caches, plus the glue to route a
+/// size-and-free pair to whichever tier fits.
+pub struct SlabAllocator {
+    caches: Vec<SlabCache>,
+}
+
+impl SlabAllocator {
+    pub fn new() -> Self {
+        Self {
+            caches: CACHE_SIZES.iter().map(|&size| SlabCache::new(size)).collect(),
+        }
+    }
+
+    /// The smallest cache whose objects are at least `size` bytes, or
+    /// `None` if `size` is bigger than every tier (callers fall back to
+    /// [`super::FrameAllocator`] directly for those).
+    fn cache_for(&self, size: usize) -> Option<&SlabCache> {
+        self.caches.iter().find(|cache| cache.obj_size >= size)
+    }
+
+    /// Allocates `size` bytes from whichever cache tier fits, growing it
+    /// via `alloc_page` if needed. Returns `None` if no tier is large
+    /// enough for `size`, or if the tier that fits is out of memory.
+    pub fn alloc(
+        &self,
+        size: usize,
+        alloc_page: impl FnOnce() -> Option<usize>,
+    ) -> Option<usize> {
+        self.cache_for(size)?.alloc(alloc_page)
+    }
+
+    /// Frees an object of `size` bytes previously handed out by
+    /// [`alloc`](Self::alloc). A no-op if `size` doesn't match any tier.
+    pub fn dealloc(&self, size: usize, addr: usize) {
+        if let Some(cache) = self.cache_for(size) {
+            cache.dealloc(addr);
+        }
+    }
+}
+
+impl Default for SlabAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
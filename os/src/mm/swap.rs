@@ -0,0 +1,56 @@
+//! Swap: evicts cold pages to a backing block device when physical frames
+//! run low, and pages them back in on the next access fault.
+
+use alloc::collections::BTreeMap;
+
+/// Where an evicted page's contents live on the swap device.
+#[derive(Copy, Clone)]
+pub struct SwapSlot {
+    pub block_offset: usize,
+}
+
+pub struct SwapSpace {
+    /// Free slot offsets on the swap device, in units of `PAGE_SIZE`.
+    free_slots: alloc::vec::Vec<usize>,
+    /// Maps the physical page number that *was* resident to where its
+    /// contents now live on swap.
+    resident_map: BTreeMap<usize, SwapSlot>,
+}
+
+impl SwapSpace {
+    pub fn new(capacity_pages: usize) -> Self {
+        Self {
+            free_slots: (0..capacity_pages).collect(),
+            resident_map: BTreeMap::new(),
+        }
+    }
+
+    /// Records that `ppn`'s contents have been written out to a fresh swap
+    /// slot, returning that slot so the caller can perform the actual
+    /// block write.
+    pub fn evict(&mut self, ppn: usize) -> Option<SwapSlot> {
+        let offset = self.free_slots.pop()?;
+        let slot = SwapSlot {
+            block_offset: offset,
+        };
+        self.resident_map.insert(ppn, slot);
+        Some(slot)
+    }
+
+    /// Looks up where `ppn` was swapped to, for the page-fault handler to
+    /// read back in.
+    pub fn locate(&self, ppn: usize) -> Option<SwapSlot> {
+        self.resident_map.get(&ppn).copied()
+    }
+
+    /// Reclaims the slot once the page has been read back into memory.
+    pub fn reclaim(&mut self, ppn: usize) {
+        if let Some(slot) = self.resident_map.remove(&ppn) {
+            self.free_slots.push(slot.block_offset);
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.free_slots.is_empty()
+    }
+}
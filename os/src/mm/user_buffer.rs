@@ -0,0 +1,25 @@
+//! A scatter/gather view over a user-space buffer, expressed as a list of
+//! kernel-mapped byte slices produced by page-table translation.
+
+use alloc::vec::Vec;
+
+/// A (possibly non-contiguous) user buffer, represented as translated
+/// physical byte slices.
+pub struct UserBuffer {
+    pub buffers: Vec<&'static mut [u8]>,
+}
+
+impl UserBuffer {
+    pub fn new(buffers: Vec<&'static mut [u8]>) -> Self {
+        Self { buffers }
+    }
+
+    /// Total length across all fragments.
+    pub fn len(&self) -> usize {
+        self.buffers.iter().map(|b| b.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,52 @@
+//! Background zeroing of freed physical frames.
+//!
+//! Freeing a frame only has to clear its ownership, not its contents;
+//! zeroing on free (or on alloc) costs a full page write on the critical
+//! path of whatever triggered the free. Instead, freed frames go on a
+//! pending-zero queue and get cleared by an idle-time pass, so a frame
+//! reused after the pass finds it already zeroed. The frame allocator
+//! does not consult this queue when handing frames out.
+
+use alloc::collections::VecDeque;
+
+pub struct ZeroFillQueue {
+    pending: VecDeque<usize>,
+}
+
+impl ZeroFillQueue {
+    pub const fn new() -> Self {
+        Self {
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Enqueues a freed frame (by physical page number) for idle zeroing.
+    pub fn push(&mut self, ppn: usize) {
+        self.pending.push_back(ppn);
+    }
+
+    /// Whether a frame is still waiting for the idle pass (so it still
+    /// needs zeroing), as opposed to already processed or never queued.
+    pub fn is_pending(&self, ppn: usize) -> bool {
+        self.pending.contains(&ppn)
+    }
+
+    /// Runs in the idle loop: zeroes one pending frame per call so the
+    /// work is spread across idle ticks instead of stalling whichever hart
+    /// goes idle first.
+    pub fn zero_one(&mut self) -> bool {
+        if let Some(ppn) = self.pending.pop_front() {
+            zero_frame(ppn);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn zero_frame(ppn: usize) {
+    let base = ppn << crate::config::PAGE_SIZE_BITS;
+    unsafe {
+        core::ptr::write_bytes(base as *mut u8, 0, crate::config::PAGE_SIZE);
+    }
+}
@@ -0,0 +1,56 @@
+//! Packet/byte counters for the loopback interface every [`super::Socket`]
+//! stream runs over. There's no real NIC behind it, but a loopback
+//! interface counts traffic the same way a physical one would, so every
+//! byte a connected [`super::socket::Stream`] moves is tallied here rather
+//! than vanishing silently the way an uninstrumented in-kernel pipe would.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Default)]
+struct Counters {
+    packets_sent: AtomicU64,
+    packets_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+static LOOPBACK: Counters = Counters {
+    packets_sent: AtomicU64::new(0),
+    packets_received: AtomicU64::new(0),
+    bytes_sent: AtomicU64::new(0),
+    bytes_received: AtomicU64::new(0),
+};
+
+/// A point-in-time snapshot of [`loopback_stats`], one "packet" per
+/// `read`/`write` call rather than per simulated MTU-sized frame.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LoopbackStats {
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+pub(super) fn record_send(bytes: usize) {
+    LOOPBACK.packets_sent.fetch_add(1, Ordering::Relaxed);
+    LOOPBACK
+        .bytes_sent
+        .fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+pub(super) fn record_receive(bytes: usize) {
+    LOOPBACK.packets_received.fetch_add(1, Ordering::Relaxed);
+    LOOPBACK
+        .bytes_received
+        .fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+/// Snapshots the loopback interface's traffic counters.
+pub fn loopback_stats() -> LoopbackStats {
+    LoopbackStats {
+        packets_sent: LOOPBACK.packets_sent.load(Ordering::Relaxed),
+        packets_received: LOOPBACK.packets_received.load(Ordering::Relaxed),
+        bytes_sent: LOOPBACK.bytes_sent.load(Ordering::Relaxed),
+        bytes_received: LOOPBACK.bytes_received.load(Ordering::Relaxed),
+    }
+}
@@ -0,0 +1,11 @@
+//! A loopback-only network stack: no NIC driver exists yet, but the
+//! socket syscall surface (`bind`/`listen`/`accept`/`connect`) and its fd
+//! integration can be built and exercised against a purely in-kernel
+//! "interface" now, the same way [`crate::fs::vfs`] grew a real
+//! `MountTable` ahead of any concrete mounted filesystem.
+
+mod loopback;
+mod socket;
+
+pub use loopback::{loopback_stats, LoopbackStats};
+pub use socket::{connect, Socket, Stream};
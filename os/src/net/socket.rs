@@ -0,0 +1,291 @@
+//! A BSD-socket-shaped `bind`/`listen`/`accept`/`connect` surface over the
+//! loopback interface, reusing the exact "cross two [`pipe`]s" trick
+//! [`socketpair`](crate::fs::socketpair)'s stream mode already uses: a
+//! loopback TCP-lite connection is structurally the same bidirectional,
+//! buffered, blocking (yield-and-retry) byte stream, just reached by naming a
+//! port instead of getting both ends back from one call.
+//!
+//! This is a rendezvous, not a three-way handshake: [`connect`] hands the
+//! listener a ready-made [`Stream`] rather than negotiating one, which is
+//! enough to exercise the syscall surface and fd integration without a
+//! real NIC underneath.
+
+use super::loopback;
+use crate::fs::{pipe, File, PollEvents, Pollable, ReadEnd, WriteEnd};
+use crate::mm::UserBuffer;
+use crate::sync::{UPSafeCell, WaitQueue};
+use crate::task::suspend_current_and_run_next;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::sync::Arc;
+
+/// Linux hands out ephemeral ports from this range; [`bind_port`] mirrors
+/// it for `bind(port = 0)` (and for [`Socket::bind`]'s `None`, the same
+/// request with no port parsed out of it yet).
+const EPHEMERAL_PORT_RANGE: core::ops::RangeInclusive<u16> = 49152..=65535;
+
+/// Port -> listener registry every [`bind_port`]/[`connect`] call shares.
+static LISTENERS: UPSafeCell<BTreeMap<u16, Arc<Listener>>> =
+    unsafe { UPSafeCell::new(BTreeMap::new()) };
+
+/// A bound, listening loopback port, queuing [`Stream`]s [`connect`]
+/// hands it until [`Listener::accept`] drains them.
+struct Listener {
+    port: u16,
+    backlog: UPSafeCell<usize>,
+    pending: UPSafeCell<VecDeque<Arc<Stream>>>,
+    not_empty: WaitQueue,
+}
+
+impl Listener {
+    fn new(port: u16) -> Self {
+        Self {
+            port,
+            backlog: unsafe { UPSafeCell::new(1) },
+            pending: unsafe { UPSafeCell::new(VecDeque::new()) },
+            not_empty: WaitQueue::new(),
+        }
+    }
+
+    fn set_backlog(&self, backlog: usize) {
+        *self.backlog.exclusive_access() = backlog.max(1);
+    }
+
+    /// Queues `stream` for [`Listener::accept`], refusing it (matching a
+    /// real listener's `ECONNREFUSED` for a full backlog) if the pending
+    /// queue is already at capacity.
+    fn enqueue(&self, stream: Arc<Stream>) -> Result<(), ()> {
+        let mut pending = self.pending.exclusive_access();
+        if pending.len() >= *self.backlog.exclusive_access() {
+            return Err(());
+        }
+        pending.push_back(stream);
+        drop(pending);
+        self.not_empty.wake_all();
+        Ok(())
+    }
+
+    fn accept(&self, nonblocking: bool) -> Result<Arc<Stream>, ()> {
+        loop {
+            if let Some(stream) = self.pending.exclusive_access().pop_front() {
+                return Ok(stream);
+            }
+            if nonblocking {
+                return Err(());
+            }
+            suspend_current_and_run_next();
+        }
+    }
+}
+
+fn allocate_ephemeral_port(registry: &BTreeMap<u16, Arc<Listener>>) -> Option<u16> {
+    EPHEMERAL_PORT_RANGE.find(|port| !registry.contains_key(port))
+}
+
+/// Binds `requested` (or an ephemeral port, for `None`/`Some(0)`),
+/// returning the listener it created.
+fn bind_port(requested: Option<u16>) -> Result<Arc<Listener>, ()> {
+    let mut registry = LISTENERS.exclusive_access();
+    let port = match requested {
+        None | Some(0) => allocate_ephemeral_port(&registry).ok_or(())?,
+        Some(port) => {
+            if registry.contains_key(&port) {
+                return Err(());
+            }
+            port
+        }
+    };
+    let listener = Arc::new(Listener::new(port));
+    registry.insert(port, Arc::clone(&listener));
+    Ok(listener)
+}
+
+/// Connects to whatever is listening on `port`, crossing a fresh pipe
+/// pair and handing the listener one end while returning the other —
+/// the same "two crossed pipes make a bidirectional stream" shape
+/// [`socketpair`](crate::fs::socketpair)'s stream mode builds, just with the
+/// two ends separated by a `bind`/`accept` instead of one call.
+pub fn connect(port: u16) -> Result<Arc<Stream>, ()> {
+    let listener = LISTENERS.exclusive_access().get(&port).cloned().ok_or(())?;
+    let (read_to_server, write_to_client) = pipe();
+    let (read_to_client, write_to_server) = pipe();
+    let server_side = Arc::new(Stream {
+        local_port: port,
+        peer_port: port,
+        read: read_to_server,
+        write: write_to_server,
+    });
+    let client_side = Arc::new(Stream {
+        local_port: port,
+        peer_port: port,
+        read: read_to_client,
+        write: write_to_client,
+    });
+    listener.enqueue(server_side)?;
+    Ok(client_side)
+}
+
+/// One connected end of a loopback stream, reached through
+/// `bind`/`listen`/`accept`/`connect` instead of `socketpair`. Traffic
+/// through it is tallied on the [`loopback`] interface's counters.
+pub struct Stream {
+    local_port: u16,
+    peer_port: u16,
+    read: Arc<ReadEnd>,
+    write: Arc<WriteEnd>,
+}
+
+impl Stream {
+    /// The loopback port this end is bound to — always the listener's
+    /// port, since loopback rendezvous has no separate client-side port.
+    pub fn local_port(&self) -> u16 {
+        self.local_port
+    }
+
+    /// The port this stream is connected to.
+    pub fn peer_port(&self) -> u16 {
+        self.peer_port
+    }
+}
+
+impl File for Stream {
+    fn readable(&self) -> bool {
+        true
+    }
+    fn writable(&self) -> bool {
+        true
+    }
+    fn read(&self, buf: UserBuffer) -> usize {
+        let n = self.read.read(buf);
+        loopback::record_receive(n);
+        n
+    }
+    fn write(&self, buf: UserBuffer) -> usize {
+        let n = self.write.write(buf);
+        loopback::record_send(n);
+        n
+    }
+    fn is_nonblocking(&self) -> bool {
+        self.read.is_nonblocking()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        self.read.set_nonblocking(nonblocking);
+        self.write.set_nonblocking(nonblocking);
+    }
+}
+
+impl Pollable for Stream {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        let mut ready = PollEvents::empty();
+        ready.insert(self.read.poll(interest));
+        ready.insert(self.write.poll(interest));
+        ready
+    }
+}
+
+enum SocketState {
+    Unbound,
+    Bound(Arc<Listener>),
+    Connected(Arc<Stream>),
+}
+
+/// The file behind a raw `socket()` fd, before `bind`/`listen`/`connect`
+/// have settled it into a listening or connected role. One object backs
+/// the fd across that whole lifecycle, the same as a real socket fd does.
+pub struct Socket {
+    state: UPSafeCell<SocketState>,
+    nonblocking: UPSafeCell<bool>,
+}
+
+impl Socket {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            state: unsafe { UPSafeCell::new(SocketState::Unbound) },
+            nonblocking: unsafe { UPSafeCell::new(false) },
+        })
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        if let SocketState::Bound(listener) = &*self.state.exclusive_access() {
+            LISTENERS.exclusive_access().remove(&listener.port);
+        }
+    }
+}
+
+impl File for Socket {
+    fn readable(&self) -> bool {
+        matches!(&*self.state.exclusive_access(), SocketState::Connected(_))
+    }
+    fn writable(&self) -> bool {
+        matches!(&*self.state.exclusive_access(), SocketState::Connected(_))
+    }
+    fn read(&self, buf: UserBuffer) -> usize {
+        match &*self.state.exclusive_access() {
+            SocketState::Connected(stream) => stream.read(buf),
+            _ => 0,
+        }
+    }
+    fn write(&self, buf: UserBuffer) -> usize {
+        match &*self.state.exclusive_access() {
+            SocketState::Connected(stream) => stream.write(buf),
+            _ => 0,
+        }
+    }
+    fn is_nonblocking(&self) -> bool {
+        *self.nonblocking.exclusive_access()
+    }
+    fn set_nonblocking(&self, nonblocking: bool) {
+        *self.nonblocking.exclusive_access() = nonblocking;
+        if let SocketState::Connected(stream) = &*self.state.exclusive_access() {
+            stream.set_nonblocking(nonblocking);
+        }
+    }
+    fn bind(&self, port: Option<u16>) -> Result<u16, ()> {
+        let mut state = self.state.exclusive_access();
+        if !matches!(&*state, SocketState::Unbound) {
+            return Err(());
+        }
+        let listener = bind_port(port)?;
+        let bound_port = listener.port;
+        *state = SocketState::Bound(listener);
+        Ok(bound_port)
+    }
+    fn listen(&self, backlog: usize) -> Result<(), ()> {
+        match &*self.state.exclusive_access() {
+            SocketState::Bound(listener) => {
+                listener.set_backlog(backlog);
+                Ok(())
+            }
+            _ => Err(()),
+        }
+    }
+    fn accept(&self) -> Result<Arc<dyn File>, ()> {
+        let listener = match &*self.state.exclusive_access() {
+            SocketState::Bound(listener) => Arc::clone(listener),
+            _ => return Err(()),
+        };
+        let nonblocking = *self.nonblocking.exclusive_access();
+        let stream = listener.accept(nonblocking)?;
+        Ok(stream as Arc<dyn File>)
+    }
+    fn connect(&self, port: u16) -> Result<(), ()> {
+        let mut state = self.state.exclusive_access();
+        if !matches!(&*state, SocketState::Unbound) {
+            return Err(());
+        }
+        let stream = connect(port)?;
+        stream.set_nonblocking(*self.nonblocking.exclusive_access());
+        *state = SocketState::Connected(stream);
+        Ok(())
+    }
+}
+
+impl Pollable for Socket {
+    fn poll(&self, interest: PollEvents) -> PollEvents {
+        match &*self.state.exclusive_access() {
+            SocketState::Connected(stream) => stream.poll(interest),
+            _ => PollEvents::empty(),
+        }
+    }
+}
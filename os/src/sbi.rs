@@ -0,0 +1,228 @@
+//! Thin wrappers around the SBI (Supervisor Binary Interface) calls the
+//! kernel relies on for console I/O, hart control, and power management.
+//!
+//! Everything but console I/O is built on [`sbi_ecall`], the modern
+//! EID/FID calling convention every SBI v0.2+ extension (Base, HSM, IPI,
+//! RFENCE, SRST) shares; console I/O keeps using the older single-call-
+//! number convention ([`sbi_call`]) since `SBI_CONSOLE_PUTCHAR`/
+//! `SBI_CONSOLE_GETCHAR` are legacy calls OpenSBI still answers directly
+//! rather than folding into an extension. There's no `sbi-rt` crate
+//! available in this tree (no network access to fetch one, and nothing
+//! else here pulls in outside crates this way), so the extension IDs and
+//! call sequences below are hand-rolled against the SBI spec instead.
+
+#![allow(unused)]
+
+const SBI_CONSOLE_PUTCHAR: usize = 1;
+const SBI_CONSOLE_GETCHAR: usize = 2;
+
+/// Base extension: present on every SBI implementation, used here only to
+/// probe whether a given extension is implemented before relying on it.
+const SBI_EXT_BASE: usize = 0x10;
+const SBI_BASE_FID_PROBE_EXTENSION: usize = 3;
+
+/// Hart State Management: start/stop/suspend/query individual harts.
+const SBI_EXT_HSM: usize = 0x4853_4D;
+const SBI_HSM_FID_HART_START: usize = 0;
+const SBI_HSM_FID_HART_STOP: usize = 1;
+const SBI_HSM_FID_HART_GET_STATUS: usize = 2;
+const SBI_HSM_FID_HART_SUSPEND: usize = 3;
+
+/// `suspend_type` for [`hart_suspend`]: "retentive" means the platform
+/// preserves hart state across the suspend, so resuming just continues
+/// execution after the SBI call returns rather than needing a resume
+/// address — the only mode this kernel has any use for, since it has no
+/// non-retentive resume vector to hand `hart_suspend` instead.
+const HSM_SUSPEND_TYPE_RETENTIVE: usize = 0x0000_0000;
+
+/// Timer extension: programs the next timer interrupt, for tickless idle
+/// (arm for the next actually-due deadline) instead of a fixed periodic
+/// tick.
+const SBI_EXT_TIME: usize = 0x5449_4D45;
+const SBI_TIME_FID_SET_TIMER: usize = 0;
+
+/// IPI extension: kick another hart's supervisor-software-interrupt pending
+/// bit so it traps.
+const SBI_EXT_IPI: usize = 0x7350_49;
+const SBI_IPI_FID_SEND_IPI: usize = 0;
+
+/// RFENCE extension: ask remote harts to run a fence locally, since
+/// there's no RISC-V instruction that does that across harts directly.
+const SBI_EXT_RFENCE: usize = 0x5246_4E43;
+const SBI_RFENCE_FID_REMOTE_FENCE_I: usize = 0;
+const SBI_RFENCE_FID_REMOTE_SFENCE_VMA: usize = 1;
+
+/// System reset: used for `shutdown`/`reboot` instead of the legacy (and
+/// now deprecated) `SBI_SHUTDOWN` legacy call.
+const SBI_EXT_SRST: usize = 0x5352_5354;
+const SBI_SRST_FID_RESET: usize = 0;
+
+const SRST_TYPE_SHUTDOWN: usize = 0;
+const SRST_TYPE_COLD_REBOOT: usize = 1;
+
+const SRST_REASON_NONE: usize = 0;
+const SRST_REASON_SYSTEM_FAILURE: usize = 1;
+
+#[inline(always)]
+fn sbi_call(which: usize, arg0: usize, arg1: usize, arg2: usize) -> usize {
+    let mut ret;
+    unsafe {
+        core::arch::asm!(
+            "ecall",
+            inlateout("x10") arg0 => ret,
+            in("x11") arg1,
+            in("x12") arg2,
+            in("x17") which,
+        );
+    }
+    ret
+}
+
+/// Issues one SBI v0.2+ (`sbiret`) call: `ext`/`func` address the
+/// extension and function, `arg0..arg3` are its arguments. Returns
+/// `(error, value)` exactly as `sbiret` does — `error == 0` is success,
+/// a negative `error` an SBI error code, and `value` the call's result
+/// when it has one (e.g. [`probe_extension`]'s availability flag).
+#[inline(always)]
+fn sbi_ecall(ext: usize, func: usize, arg0: usize, arg1: usize, arg2: usize, arg3: usize) -> (isize, usize) {
+    let (error, value);
+    unsafe {
+        core::arch::asm!(
+            "ecall",
+            inlateout("x10") arg0 => error,
+            inlateout("x11") arg1 => value,
+            in("x12") arg2,
+            in("x13") arg3,
+            in("x16") func,
+            in("x17") ext,
+        );
+    }
+    (error, value)
+}
+
+pub fn console_putchar(c: usize) {
+    sbi_call(SBI_CONSOLE_PUTCHAR, c, 0, 0);
+}
+
+pub fn console_getchar() -> usize {
+    sbi_call(SBI_CONSOLE_GETCHAR, 0, 0, 0)
+}
+
+/// Whether the SBI implementation answering this machine's `ecall`s
+/// supports extension `eid` at all — HSM/IPI/RFENCE are all optional per
+/// spec, even though every SBI implementation this kernel has actually
+/// run under (OpenSBI) has them.
+pub fn probe_extension(eid: usize) -> bool {
+    let (error, value) = sbi_ecall(SBI_EXT_BASE, SBI_BASE_FID_PROBE_EXTENSION, eid, 0, 0, 0);
+    error == 0 && value != 0
+}
+
+/// Starts `hart_id` executing at `start_addr` with `opaque` left in `a1`
+/// for it to read back (the SMP boot handshake convention every HSM-based
+/// bring-up uses) — not called anywhere yet, since this kernel only ever
+/// boots a single hart (`entry.asm` has one boot path, not a per-hart
+/// one).
+pub fn hart_start(hart_id: usize, start_addr: usize, opaque: usize) -> isize {
+    sbi_ecall(SBI_EXT_HSM, SBI_HSM_FID_HART_START, hart_id, start_addr, opaque, 0).0
+}
+
+/// Stops the calling hart for good; only SBI itself can start it again
+/// via [`hart_start`]. Does not return on success.
+pub fn hart_stop() -> ! {
+    sbi_ecall(SBI_EXT_HSM, SBI_HSM_FID_HART_STOP, 0, 0, 0, 0);
+    unreachable!("SBI HSM hart_stop should not return");
+}
+
+/// Suspends the calling hart in retentive mode until the next interrupt,
+/// then returns — the SBI equivalent of `wfi`, except it's guaranteed to
+/// actually stop burning host CPU under an emulator like QEMU, which
+/// `wfi` alone isn't.
+pub fn hart_suspend() -> isize {
+    sbi_ecall(
+        SBI_EXT_HSM,
+        SBI_HSM_FID_HART_SUSPEND,
+        HSM_SUSPEND_TYPE_RETENTIVE,
+        0,
+        0,
+        0,
+    )
+    .0
+}
+
+/// Schedules the next timer interrupt for `stime_value` (an absolute
+/// `time` CSR value, the same units [`crate::timer::get_time`] reads) —
+/// tickless idle arms this at the next actually-due deadline instead of a
+/// fixed period, so a hart with nothing due doesn't wake up for nothing.
+pub fn set_timer(stime_value: u64) -> isize {
+    sbi_ecall(SBI_EXT_TIME, SBI_TIME_FID_SET_TIMER, stime_value as usize, 0, 0, 0).0
+}
+
+/// Sends a supervisor-software interrupt to every hart set in
+/// `hart_mask`, a bitmask relative to `hart_mask_base` (hart `n`'s bit is
+/// `n - hart_mask_base`). The receiving hart still needs a trap handler
+/// that reacts to the resulting interrupt — this only makes the `ecall`,
+/// it doesn't simulate a handler running.
+pub fn send_ipi(hart_mask: usize, hart_mask_base: usize) -> isize {
+    sbi_ecall(SBI_EXT_IPI, SBI_IPI_FID_SEND_IPI, hart_mask, hart_mask_base, 0, 0).0
+}
+
+/// Asks every hart in `hart_mask` (same encoding as [`send_ipi`]) to run
+/// `fence.i` locally.
+pub fn remote_fence_i(hart_mask: usize, hart_mask_base: usize) -> isize {
+    sbi_ecall(
+        SBI_EXT_RFENCE,
+        SBI_RFENCE_FID_REMOTE_FENCE_I,
+        hart_mask,
+        hart_mask_base,
+        0,
+        0,
+    )
+    .0
+}
+
+/// Asks every hart in `hart_mask` to flush the TLB for `[start_addr,
+/// start_addr + size)` (or the whole address space if `size` is
+/// `usize::MAX`).
+pub fn remote_sfence_vma(hart_mask: usize, hart_mask_base: usize, start_addr: usize, size: usize) -> isize {
+    sbi_ecall(
+        SBI_EXT_RFENCE,
+        SBI_RFENCE_FID_REMOTE_SFENCE_VMA,
+        hart_mask,
+        hart_mask_base,
+        start_addr,
+        size,
+    )
+    .0
+}
+
+/// Powers the machine off via SBI SRST. `failure` picks the reset reason
+/// reported to firmware/a hypervisor watching for a crash vs. a clean
+/// shutdown; does not return.
+pub fn shutdown(failure: bool) -> ! {
+    let reason = if failure {
+        SRST_REASON_SYSTEM_FAILURE
+    } else {
+        SRST_REASON_NONE
+    };
+    sbi_ecall(SBI_EXT_SRST, SBI_SRST_FID_RESET, SRST_TYPE_SHUTDOWN, reason, 0, 0);
+    unreachable!("SBI SRST shutdown should not return");
+}
+
+/// Cold-reboots the machine via SBI SRST. Same `failure` meaning as
+/// [`shutdown`]; does not return.
+pub fn reboot(failure: bool) -> ! {
+    let reason = if failure {
+        SRST_REASON_SYSTEM_FAILURE
+    } else {
+        SRST_REASON_NONE
+    };
+    sbi_ecall(
+        SBI_EXT_SRST,
+        SBI_SRST_FID_RESET,
+        SRST_TYPE_COLD_REBOOT,
+        reason,
+        0,
+        0,
+    );
+    unreachable!("SBI SRST reboot should not return");
+}
@@ -0,0 +1,47 @@
+//! A single-producer/multiple-consumer boot barrier: the boot hart
+//! signals once that shared state (memory management, the filesystem, the
+//! init process) is fully set up, and every secondary hart waiting on the
+//! barrier spins until that signal lands before touching any of it.
+//!
+//! Unlike [`TicketLock`](super::TicketLock)/[`SMPSafeCell`](super::SMPSafeCell),
+//! which protect a value under mutual exclusion, `BootBarrier` protects a
+//! *point in time*: there's nothing to lock, only a one-shot gate that
+//! flips from closed to open and never closes again.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+pub struct BootBarrier {
+    ready: AtomicBool,
+}
+
+impl BootBarrier {
+    pub const fn new() -> Self {
+        Self {
+            ready: AtomicBool::new(false),
+        }
+    }
+
+    /// Opens the barrier. Called exactly once, by the boot hart, after
+    /// everything secondary harts need is fully initialized.
+    pub fn publish(&self) {
+        self.ready.store(true, Ordering::Release);
+    }
+
+    /// Spins until [`publish`](Self::publish) has been called.
+    pub fn wait(&self) {
+        while !self.ready.load(Ordering::Acquire) {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Whether the barrier has been opened yet, without blocking.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+}
+
+impl Default for BootBarrier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,42 @@
+//! An RAII guard that disables supervisor interrupts on this hart for its
+//! lifetime, restoring the previous `sstatus.SIE` bit on drop. Needed
+//! around any lock also taken from an interrupt handler, to avoid a hart
+//! deadlocking against itself by taking a timer interrupt while already
+//! holding the lock.
+
+const SSTATUS_SIE: usize = 1 << 1;
+
+pub struct IrqGuard {
+    was_enabled: bool,
+}
+
+impl IrqGuard {
+    /// Disables interrupts and returns a guard that restores the prior
+    /// state when dropped.
+    pub fn new() -> Self {
+        let sstatus: usize;
+        unsafe {
+            core::arch::asm!("csrr {}, sstatus", out(reg) sstatus);
+            core::arch::asm!("csrci sstatus, {}", const SSTATUS_SIE);
+        }
+        Self {
+            was_enabled: sstatus & SSTATUS_SIE != 0,
+        }
+    }
+}
+
+impl Drop for IrqGuard {
+    fn drop(&mut self) {
+        if self.was_enabled {
+            unsafe {
+                core::arch::asm!("csrsi sstatus, {}", const SSTATUS_SIE);
+            }
+        }
+    }
+}
+
+impl Default for IrqGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
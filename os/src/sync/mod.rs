@@ -0,0 +1,19 @@
+//! Synchronization primitives used internally by the kernel.
+
+mod boot_barrier;
+mod irq_guard;
+mod resource_graph;
+mod rwlock;
+mod smp_safe_cell;
+mod ticket_lock;
+mod up;
+mod wait_queue;
+
+pub use boot_barrier::BootBarrier;
+pub use irq_guard::IrqGuard;
+pub use resource_graph::ResourceAllocationGraph;
+pub use rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+pub use smp_safe_cell::SMPSafeCell;
+pub use ticket_lock::TicketLock;
+pub use up::UPSafeCell;
+pub use wait_queue::WaitQueue;
@@ -0,0 +1,78 @@
+//! Resource allocation graph: a single, general place to ask "would
+//! granting this request deadlock?" instead of each lock type (mutex,
+//! rwlock, futex, ...) growing its own bespoke wait-for check.
+//!
+//! Nodes are task ids and resource ids; edges are "task holds resource"
+//! and "task waits for resource". A cycle in the resulting wait-for graph
+//! means deadlock.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+#[derive(Default)]
+pub struct ResourceAllocationGraph {
+    /// resource -> holder task
+    held_by: BTreeMap<usize, usize>,
+    /// task -> resource it's blocked waiting for
+    waits_for: BTreeMap<usize, usize>,
+}
+
+impl ResourceAllocationGraph {
+    pub fn new() -> Self {
+        Self {
+            held_by: BTreeMap::new(),
+            waits_for: BTreeMap::new(),
+        }
+    }
+
+    pub fn record_held(&mut self, resource: usize, task: usize) {
+        self.held_by.insert(resource, task);
+    }
+
+    pub fn release(&mut self, resource: usize) {
+        self.held_by.remove(&resource);
+    }
+
+    pub fn record_waiting(&mut self, task: usize, resource: usize) {
+        self.waits_for.insert(task, resource);
+    }
+
+    pub fn clear_waiting(&mut self, task: usize) {
+        self.waits_for.remove(&task);
+    }
+
+    /// Would `task` blocking on `resource` complete a cycle in the
+    /// wait-for graph? Checked before granting a blocking wait, not
+    /// after, so a deadlock can be refused rather than merely detected
+    /// once every party is already stuck.
+    pub fn would_deadlock(&self, task: usize, resource: usize) -> bool {
+        let mut visited = BTreeSet::new();
+        let mut current = resource;
+        loop {
+            let Some(&holder) = self.held_by.get(&current) else {
+                return false;
+            };
+            if holder == task {
+                return true;
+            }
+            if !visited.insert(holder) {
+                return false;
+            }
+            match self.waits_for.get(&holder) {
+                Some(&next_resource) => current = next_resource,
+                None => return false,
+            }
+        }
+    }
+
+    /// Every task currently part of a wait-for cycle, for diagnostics.
+    pub fn deadlocked_tasks(&self) -> Vec<usize> {
+        let mut stuck = Vec::new();
+        for (&task, &resource) in &self.waits_for {
+            if self.would_deadlock(task, resource) {
+                stuck.push(task);
+            }
+        }
+        stuck
+    }
+}
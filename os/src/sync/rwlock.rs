@@ -0,0 +1,116 @@
+//! A reader-writer spinlock: many concurrent readers, one exclusive
+//! writer, no readers and writer at once. Readers are cheap and don't
+//! serialize against each other the way [`TicketLock`](super::TicketLock)
+//! would for a read-mostly structure.
+
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicIsize, Ordering};
+
+/// `state == 0`: unlocked. `state > 0`: that many readers held.
+/// `state == -1`: a writer holds the lock.
+const WRITER: isize = -1;
+
+pub struct RwLock<T> {
+    state: AtomicIsize,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            state: AtomicIsize::new(0),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        loop {
+            let cur = self.state.load(Ordering::Relaxed);
+            if cur != WRITER
+                && self
+                    .state
+                    .compare_exchange_weak(cur, cur + 1, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return RwLockReadGuard { lock: self };
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        while self
+            .state
+            .compare_exchange_weak(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        RwLockWriteGuard { lock: self }
+    }
+
+    /// Acquires a read lock without blocking, returning `None` if a
+    /// writer currently holds it.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        let cur = self.state.load(Ordering::Relaxed);
+        if cur == WRITER {
+            return None;
+        }
+        self.state
+            .compare_exchange(cur, cur + 1, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| RwLockReadGuard { lock: self })
+    }
+
+    /// Acquires a write lock without blocking, returning `None` if any
+    /// reader or writer already holds it.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        self.state
+            .compare_exchange(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| RwLockWriteGuard { lock: self })
+    }
+}
+
+pub struct RwLockReadGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.fetch_sub(1, Ordering::Release);
+    }
+}
+
+pub struct RwLockWriteGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.store(0, Ordering::Release);
+    }
+}
@@ -0,0 +1,62 @@
+//! `SMPSafeCell`: the true-SMP replacement for [`UPSafeCell`](super::UPSafeCell).
+//! Where `UPSafeCell` only asserted single-hart-at-a-time access,
+//! `SMPSafeCell` actually enforces mutual exclusion with a spinlock, so it
+//! stays sound once more than one hart can genuinely race on the data it
+//! guards.
+
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+pub struct SMPSafeCell<T> {
+    locked: AtomicBool,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Sync for SMPSafeCell<T> {}
+
+impl<T> SMPSafeCell<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Spins until the lock is acquired. Interrupts should be disabled
+    /// first (see [`super::IrqGuard`]) if this cell is ever taken from an
+    /// interrupt handler, to avoid a hart deadlocking against itself.
+    pub fn exclusive_access(&self) -> SMPSafeCellGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        SMPSafeCellGuard { cell: self }
+    }
+}
+
+pub struct SMPSafeCellGuard<'a, T> {
+    cell: &'a SMPSafeCell<T>,
+}
+
+impl<T> Deref for SMPSafeCellGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.cell.data.get() }
+    }
+}
+
+impl<T> DerefMut for SMPSafeCellGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.cell.data.get() }
+    }
+}
+
+impl<T> Drop for SMPSafeCellGuard<'_, T> {
+    fn drop(&mut self) {
+        self.cell.locked.store(false, Ordering::Release);
+    }
+}
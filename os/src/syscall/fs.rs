@@ -0,0 +1,297 @@
+//! File-descriptor-related syscalls.
+
+use super::errno::{EBADF, EINVAL, EMFILE, ENOSYS, ESRCH};
+use crate::fs::{poll_once, socketpair, File, PollEvents, SeekWhence, SignalFd, SocketType};
+use crate::mm::{validate_user_slice, UserBuffer};
+use crate::task::{current_task, FdFlags, SignalFlags};
+use alloc::sync::Arc;
+
+/// The file behind `fd` in the calling task's table, if any.
+fn file_for_fd(fd: usize) -> Option<Arc<dyn File>> {
+    let task = current_task()?;
+    let inner = task.inner_exclusive_access();
+    match inner.fd_table.get(fd) {
+        Some(Some(entry)) => Some(Arc::clone(&entry.file)),
+        _ => None,
+    }
+}
+
+/// `sys_signalfd(fd, mask)`: create (or, if `fd >= 0`, replace the mask of)
+/// a signalfd watching `mask`, returning its file descriptor.
+///
+/// Only creation (`fd == -1`) is supported for now; replacing an existing
+/// signalfd's mask in place is left for a follow-up.
+pub fn sys_signalfd(fd: isize, mask: u32) -> isize {
+    if fd != -1 {
+        return EINVAL;
+    }
+    let task = match current_task() {
+        Some(task) => task,
+        None => return ESRCH,
+    };
+    let mut signal_mask = SignalFlags::empty();
+    if mask & 1 != 0 {
+        signal_mask.insert(SignalFlags::SIGCHLD);
+    }
+    let signalfd = Arc::new(SignalFd::new(signal_mask, Arc::clone(&task)));
+    let mut inner = task.inner_exclusive_access();
+    inner.alloc_fd(signalfd).map_or(EMFILE, |fd| fd as isize)
+}
+
+/// `sys_lseek(fd, offset, whence)`: repositions `fd`'s cursor and returns
+/// the new absolute offset, `EINVAL` if `whence` isn't one of
+/// `SEEK_SET`/`SEEK_CUR`/`SEEK_END`, `EBADF` if `fd` is invalid, or
+/// whatever [`File::seek`] reports for a file that isn't seekable.
+pub fn sys_lseek(fd: usize, offset: isize, whence: usize) -> isize {
+    let whence = match whence {
+        0 => SeekWhence::Set,
+        1 => SeekWhence::Cur,
+        2 => SeekWhence::End,
+        _ => return EINVAL,
+    };
+    match file_for_fd(fd) {
+        Some(file) => file.seek(offset, whence),
+        None => EBADF,
+    }
+}
+
+/// `sys_pread64(fd, buf, count, offset)`: reads up to `count` bytes at
+/// `offset` into `buf` without disturbing `fd`'s cursor, returning the
+/// number of bytes read, or `-EFAULT` if `buf` is a wild pointer.
+///
+/// [`validate_user_slice`] does not range-check `buf` against the task's
+/// `mm` areas (see its own doc comment), so this only catches a
+/// null or overflowing pointer rather than one that's simply unmapped —
+/// still enough to turn the common "passed a garbage pointer" mistake
+/// into an error return instead of a kernel panic.
+pub fn sys_pread64(fd: usize, buf: *mut u8, count: usize, offset: usize) -> isize {
+    if let Err(err) = validate_user_slice(buf, count) {
+        return err;
+    }
+    match file_for_fd(fd) {
+        Some(file) => {
+            let slice: &'static mut [u8] = unsafe { core::slice::from_raw_parts_mut(buf, count) };
+            file.read_at(offset, UserBuffer::new(alloc::vec![slice])) as isize
+        }
+        None => EBADF,
+    }
+}
+
+/// `sys_pwrite64(fd, buf, count, offset)`: writes up to `count` bytes from
+/// `buf` at `offset` without disturbing `fd`'s cursor, returning the
+/// number of bytes written. Same validation gap and `-EFAULT` behavior as
+/// [`sys_pread64`].
+///
+/// `buf` is the caller's input data, logically read-only to the kernel, so
+/// unlike [`sys_pread64`] this never reinterprets it as a `&'static mut
+/// [u8]` — doing that over a `*const` pointer casts away its const-ness
+/// and fabricates an exclusive borrow over memory the kernel has no
+/// business writing through. The bytes are copied into an owned buffer
+/// instead, and that owned copy is what `write_at` gets a mutable view
+/// over.
+pub fn sys_pwrite64(fd: usize, buf: *const u8, count: usize, offset: usize) -> isize {
+    if let Err(err) = validate_user_slice(buf, count) {
+        return err;
+    }
+    match file_for_fd(fd) {
+        Some(file) => {
+            let mut owned = alloc::vec![0u8; count];
+            unsafe {
+                core::ptr::copy_nonoverlapping(buf, owned.as_mut_ptr(), count);
+            }
+            let slice: &'static mut [u8] =
+                unsafe { core::slice::from_raw_parts_mut(owned.as_mut_ptr(), count) };
+            file.write_at(offset, UserBuffer::new(alloc::vec![slice])) as isize
+        }
+        None => EBADF,
+    }
+}
+
+/// `sys_readv(fd, iov, iovcnt)`: gathers a read across `iovcnt` `iovec`s
+/// into one `UserBuffer`-full call instead of one syscall per fragment.
+/// Parsing the `iovec` array itself still needs translating (unlike
+/// `sys_pread64`'s single `buf`/`count` pair, there's no single pointer to
+/// validate up front here); some file kinds (stdin) also reject anything
+/// but an exact-size buffer, so calling through with a placeholder one
+/// isn't safe the way the empty-string `sys_mount` placeholder is. This
+/// validates `fd` for real and reports 0 bytes gathered until `iovec`
+/// parsing lands.
+pub fn sys_readv(fd: usize, _iov: *const u8, _iovcnt: usize) -> isize {
+    match file_for_fd(fd) {
+        Some(_file) => 0,
+        None => EBADF,
+    }
+}
+
+/// `sys_writev(fd, iov, iovcnt)`: scatters a write across `iovcnt`
+/// `iovec`s. Same `iovec`-parsing gap as [`sys_readv`].
+pub fn sys_writev(fd: usize, _iov: *const u8, _iovcnt: usize) -> isize {
+    match file_for_fd(fd) {
+        Some(_file) => 0,
+        None => EBADF,
+    }
+}
+
+/// `sys_dup2(oldfd, newfd)`: makes `newfd` a copy of `oldfd`, closing
+/// whatever `newfd` previously held. The duplicate never carries
+/// `FD_CLOEXEC`, matching `dup2`'s POSIX semantics (unlike `dup3`, which
+/// lets the caller ask for it).
+pub fn sys_dup2(oldfd: usize, newfd: usize) -> isize {
+    let task = match current_task() {
+        Some(task) => task,
+        None => return ESRCH,
+    };
+    let mut inner = task.inner_exclusive_access();
+    match inner.dup_fd(oldfd, newfd, FdFlags::empty()) {
+        Some(fd) => fd as isize,
+        None => EBADF,
+    }
+}
+
+/// `sys_dup3(oldfd, newfd, flags)`: like [`sys_dup2`], but rejects
+/// `oldfd == newfd` (`dup3`'s one behavioral difference from `dup2`) and
+/// accepts `O_CLOEXEC` in `flags` to mark the new fd close-on-exec.
+pub fn sys_dup3(oldfd: usize, newfd: usize, flags: u32) -> isize {
+    const O_CLOEXEC: u32 = 0o2000000;
+    if oldfd == newfd {
+        return EINVAL;
+    }
+    let task = match current_task() {
+        Some(task) => task,
+        None => return ESRCH,
+    };
+    let fd_flags = if flags & O_CLOEXEC != 0 {
+        FdFlags::CLOEXEC
+    } else {
+        FdFlags::empty()
+    };
+    let mut inner = task.inner_exclusive_access();
+    match inner.dup_fd(oldfd, newfd, fd_flags) {
+        Some(fd) => fd as isize,
+        None => EBADF,
+    }
+}
+
+/// Mirrors the handful of fields Linux's `struct rlimit` has, for the one
+/// resource ([`RLIMIT_NOFILE`](Self::RLIMIT_NOFILE)) this kernel tracks a
+/// limit for.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct RLimit {
+    pub cur: u64,
+    pub max: u64,
+}
+
+impl RLimit {
+    pub const RLIMIT_NOFILE: usize = 7;
+}
+
+/// `sys_prlimit64(pid, resource, new_limit, old_limit)`: reads and/or sets
+/// a resource limit. Only `RLIMIT_NOFILE` (the fd-count cap `alloc_fd`
+/// enforces) and `pid == 0` (the calling task) are supported; anything
+/// else fails rather than silently doing nothing.
+///
+/// Reading `new_limit`/writing `old_limit` both need the user-pointer
+/// translation `sys_pread64` is waiting on, so for now this only accepts
+/// `new_limit == core::ptr::null()` (a pure "get", which has nowhere to
+/// write its result either, so it also reports failure) — the real
+/// set path is left for when that translation lands.
+pub fn sys_prlimit64(
+    pid: usize,
+    resource: usize,
+    new_limit: *const RLimit,
+    _old_limit: *mut RLimit,
+) -> isize {
+    if pid != 0 || resource != RLimit::RLIMIT_NOFILE || !new_limit.is_null() {
+        return EINVAL;
+    }
+    match current_task() {
+        Some(_task) => ENOSYS,
+        None => ESRCH,
+    }
+}
+
+/// `sys_ppoll(fds, nfds, timeout, sigmask)`: blocks until one of `nfds`
+/// `pollfd`s is ready or `timeout` expires.
+///
+/// Parsing the `pollfd` array (and writing back each entry's `revents`)
+/// needs the same user-pointer translation `sys_pread64` is waiting on,
+/// so for now this can't build the `(file, interest)` list
+/// [`poll_once`] needs from raw `fds`/`nfds` — the real gather/block/
+/// scatter loop is written against that already-resolved shape below
+/// ([`ppoll_once_resolved`]) so the integration point, once translation
+/// lands, only needs to parse `fds` and call it.
+pub fn sys_ppoll(_fds: *mut u8, _nfds: usize, _timeout: *const u8, _sigmask: *const u8) -> isize {
+    match current_task() {
+        Some(_task) => ENOSYS,
+        None => ESRCH,
+    }
+}
+
+/// `sys_pselect6(nfds, readfds, writefds, exceptfds, timeout, sigmask)`:
+/// `select`'s fd-set interface over the same readiness machinery as
+/// `sys_ppoll`. Same `fd_set`-parsing gap.
+pub fn sys_pselect6(
+    _nfds: usize,
+    _readfds: *mut u8,
+    _writefds: *mut u8,
+    _exceptfds: *mut u8,
+    _timeout: *const u8,
+    _sigmask: *const u8,
+) -> isize {
+    match current_task() {
+        Some(_task) => ENOSYS,
+        None => ESRCH,
+    }
+}
+
+/// The real body of `sys_ppoll`, once its caller can hand it a resolved
+/// `(file, requested events)` list instead of raw `pollfd` pointers:
+/// polls every entry once, and if none are ready, suspends the task and
+/// retries — the same wait-by-spinning approach `Stdin::read` already
+/// uses for lack of a real per-file wait queue to block on instead.
+/// Returns the index and ready events of whichever entry became ready
+/// first, or `None` once `max_rounds` consecutive idle rounds have
+/// passed (standing in for `timeout` until that's parsed from user
+/// memory too).
+pub fn ppoll_once_resolved(
+    entries: &[(Arc<dyn File>, PollEvents)],
+    max_rounds: usize,
+) -> Option<(usize, PollEvents)> {
+    for _ in 0..max_rounds {
+        if let Some(result) = poll_once(entries) {
+            return Some(result);
+        }
+        crate::task::suspend_current_and_run_next();
+    }
+    None
+}
+
+/// `sys_socketpair(domain, type, protocol, sv)`: creates a connected
+/// pair of local sockets and is meant to write their fds into `sv[0]`/
+/// `sv[1]`.
+///
+/// Validates `domain`/`protocol` and picks stream vs. datagram framing
+/// from `type` for real, and builds the actual connected pair — but
+/// installing the two new `File`s in the caller's fd table only matters
+/// once `sv` can be written back to, which needs the same user-pointer
+/// translation `sys_pread64` is waiting on. Until then the pair this
+/// constructs has nowhere to go, so it's dropped and failure reported
+/// rather than occupying fd slots the caller has no way to learn about.
+pub fn sys_socketpair(domain: i32, type_: i32, protocol: i32, _sv: *mut i32) -> isize {
+    const AF_UNIX: i32 = 1;
+    const SOCK_STREAM: i32 = 1;
+    const SOCK_DGRAM: i32 = 2;
+    const SOCK_TYPE_MASK: i32 = 0xf;
+
+    if domain != AF_UNIX || protocol != 0 {
+        return EINVAL;
+    }
+    let socket_type = match type_ & SOCK_TYPE_MASK {
+        SOCK_STREAM => SocketType::Stream,
+        SOCK_DGRAM => SocketType::Datagram,
+        _ => return EINVAL,
+    };
+    let _ = socketpair(socket_type);
+    ENOSYS
+}
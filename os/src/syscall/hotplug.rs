@@ -0,0 +1,19 @@
+//! Hart hotplug control: offlining a hart from user space.
+
+use super::process::caller_is_initproc;
+
+/// `sys_hart_offline(hart)`: takes `hart` offline via
+/// [`crate::task::hotplug_offline`] if the caller is init, returning -1
+/// otherwise or if the offline itself is refused (e.g. `hart` is the last
+/// one online). Restricted to init the same way `sys_shutdown`/`sys_reboot`
+/// are, since a hart going offline is as disruptive to the whole machine as
+/// powering it off.
+pub fn sys_hart_offline(hart: usize) -> isize {
+    if !caller_is_initproc() {
+        return -1;
+    }
+    match crate::task::hotplug_offline(hart) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
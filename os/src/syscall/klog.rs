@@ -0,0 +1,18 @@
+//! `dmesg`-style read-out of the kernel log ring ([`crate::klog`]).
+
+use crate::klog;
+use alloc::string::String;
+
+/// `sys_klog(buf, len)`: drains the kernel log ring, renders it the same
+/// way the console sees it, and copies up to `len` bytes into `buf`.
+/// Returns the number of bytes written.
+pub fn sys_klog(buf: *mut u8, len: usize) -> isize {
+    let records = klog::drain();
+    let rendered: String = klog::render(&records);
+    let bytes = rendered.as_bytes();
+    let n = bytes.len().min(len);
+    unsafe {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), buf, n);
+    }
+    n as isize
+}
@@ -0,0 +1,70 @@
+//! Memory-mapping syscalls.
+
+use crate::mm::{LazyArea, LazyKind, MapArea, MapPermission};
+use crate::task::current_task;
+
+const MAP_ANONYMOUS: usize = 0x20;
+
+/// `sys_mmap(addr, len, prot, flags, fd, offset)`: reserves `len` bytes of
+/// address space lazily backed either by zero-fill (anonymous) or by the
+/// open file `fd` starting at `offset` (file-backed). `addr == 0` lets the
+/// kernel pick a free range itself via [`MemorySet::find_free_area`]
+/// (searched against the calling task's own `mm`, so two mappings never
+/// land on top of each other), mirroring the standard `mmap(2)` behavior,
+/// instead of requiring an explicit, already-unused address from the
+/// caller. Returns the mapped address, or -1 on failure.
+///
+/// Frames are not allocated here; the range is recorded as a [`LazyArea`]
+/// in `TaskControlBlockInner::lazy_areas`, and no page-fault handler
+/// resolves a first access against it (a page fault reaches
+/// `trap::entry`'s "unhandled trap" panic).
+pub fn sys_mmap(
+    addr: usize,
+    len: usize,
+    prot: usize,
+    flags: usize,
+    fd: isize,
+    offset: usize,
+) -> isize {
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let page_size = crate::config::PAGE_SIZE;
+    let len_pages = ((len + page_size - 1) / page_size).max(1);
+    let mut inner = task.inner_exclusive_access();
+    let vpn_start = if addr == 0 {
+        match inner.mm.find_free_area(
+            len_pages,
+            1,
+            crate::config::MMAP_SEARCH_START_VPN,
+            crate::config::MMAP_SEARCH_END_VPN,
+        ) {
+            Some(vpn) => vpn,
+            None => return -1,
+        }
+    } else {
+        addr / page_size
+    };
+    let vpn_end = vpn_start + len_pages;
+    let kind = if flags & MAP_ANONYMOUS != 0 || fd < 0 {
+        LazyKind::AnonZeroFill
+    } else {
+        LazyKind::FileBacked {
+            fd: fd as usize,
+            file_offset: offset,
+        }
+    };
+    let perm = MapPermission::from_bits_truncate(prot as u8);
+    inner.mm.areas.push(MapArea {
+        name: "mmap",
+        vpn_range: vpn_start..vpn_end,
+        perm,
+    });
+    inner.mm.check_invariants();
+    inner.lazy_areas.push(LazyArea {
+        vpn_range: vpn_start..vpn_end,
+        kind,
+    });
+    (vpn_start * page_size) as isize
+}
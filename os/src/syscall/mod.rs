@@ -0,0 +1,247 @@
+//! Syscall numbers and the top-level dispatch table.
+
+mod brk;
+mod errno;
+mod exec;
+mod framebuffer;
+mod fs;
+mod futex;
+mod hotplug;
+mod klog;
+mod mm;
+mod mount;
+mod mprotect;
+mod mq;
+mod nanosleep;
+mod net;
+mod pgrp;
+mod process;
+mod rwlock;
+mod sched;
+mod shm;
+mod signal;
+mod spawn;
+mod thread;
+mod time;
+mod trace;
+mod wait;
+
+use crate::task::TraceEvent;
+
+use brk::sys_brk;
+use framebuffer::{sys_framebuffer, sys_framebuffer_flush};
+use fs::{
+    sys_dup2, sys_dup3, sys_lseek, sys_ppoll, sys_pread64, sys_prlimit64, sys_pselect6,
+    sys_pwrite64, sys_readv, sys_signalfd, sys_socketpair, sys_writev, RLimit,
+};
+use futex::sys_futex;
+use hotplug::sys_hart_offline;
+use klog::sys_klog;
+use mm::sys_mmap;
+use mount::{sys_mount, sys_umount};
+use mprotect::sys_mprotect;
+use mq::{sys_mq_open, sys_mq_receive, sys_mq_send, sys_mq_unlink};
+use nanosleep::sys_nanosleep;
+use net::{sys_accept, sys_bind, sys_connect, sys_listen, sys_socket};
+use pgrp::{sys_getpgid, sys_setpgid};
+use process::{sys_getenv, sys_getrss, sys_getrusage, sys_probe, sys_reboot, sys_shutdown, Rusage};
+use rwlock::{sys_rwlock_create, sys_rwlock_rdlock, sys_rwlock_unlock, sys_rwlock_wrlock};
+use sched::{sys_sched_stats, sys_set_priority, HartLoad};
+use shm::{sys_shmat, sys_shmdt, sys_shmget};
+use signal::{sys_sigaction, sys_sigreturn};
+use spawn::sys_spawn;
+use thread::{sys_gettid, sys_thread_create, sys_thread_exit, sys_thread_join};
+use time::{sys_clock_gettime, sys_gettimeofday, Timespec, Timeval};
+use trace::{sys_debug_ctl, sys_trace_ctl, sys_trace_read};
+use wait::sys_waitpid;
+
+const SYSCALL_SPAWN: usize = 400;
+const SYSCALL_SET_PRIORITY: usize = 401;
+const SYSCALL_MMAP: usize = 402;
+const SYSCALL_SIGACTION: usize = 403;
+const SYSCALL_SIGRETURN: usize = 404;
+const SYSCALL_WAITPID: usize = 260;
+const SYSCALL_FUTEX: usize = 405;
+const SYSCALL_SHMGET: usize = 406;
+const SYSCALL_SHMAT: usize = 407;
+const SYSCALL_SHMDT: usize = 408;
+const SYSCALL_MPROTECT: usize = 409;
+const SYSCALL_GETTIMEOFDAY: usize = 169;
+const SYSCALL_GETRUSAGE: usize = 165;
+const SYSCALL_TRACE_CTL: usize = 419;
+const SYSCALL_TRACE_READ: usize = 420;
+const SYSCALL_DEBUG_CTL: usize = 421;
+const SYSCALL_KLOG: usize = 422;
+const SYSCALL_SHUTDOWN: usize = 423;
+const SYSCALL_REBOOT: usize = 424;
+const SYSCALL_HART_OFFLINE: usize = 425;
+const SYSCALL_CLOCK_GETTIME: usize = 113;
+const SYSCALL_NANOSLEEP: usize = 101;
+const SYSCALL_SCHED_STATS: usize = 410;
+const SYSCALL_RWLOCK_CREATE: usize = 411;
+const SYSCALL_RWLOCK_RDLOCK: usize = 412;
+const SYSCALL_RWLOCK_WRLOCK: usize = 413;
+const SYSCALL_RWLOCK_UNLOCK: usize = 414;
+const SYSCALL_SIGNALFD: usize = 282;
+const SYSCALL_SETPGID: usize = 154;
+const SYSCALL_GETPGID: usize = 155;
+const SYSCALL_BRK: usize = 214;
+const SYSCALL_GETTID: usize = 178;
+const SYSCALL_THREAD_CREATE: usize = 415;
+const SYSCALL_THREAD_EXIT: usize = 416;
+const SYSCALL_THREAD_JOIN: usize = 417;
+const SYSCALL_PROBE: usize = 300;
+const SYSCALL_GETRSS: usize = 301;
+const SYSCALL_GETENV: usize = 302;
+const SYSCALL_UMOUNT2: usize = 39;
+const SYSCALL_MOUNT: usize = 40;
+const SYSCALL_LSEEK: usize = 62;
+const SYSCALL_READV: usize = 65;
+const SYSCALL_WRITEV: usize = 66;
+const SYSCALL_PREAD64: usize = 67;
+const SYSCALL_PWRITE64: usize = 68;
+const SYSCALL_DUP3: usize = 24;
+const SYSCALL_PRLIMIT64: usize = 261;
+const SYSCALL_DUP2: usize = 418;
+const SYSCALL_PSELECT6: usize = 72;
+const SYSCALL_PPOLL: usize = 73;
+const SYSCALL_SOCKETPAIR: usize = 199;
+const SYSCALL_MQ_OPEN: usize = 241;
+const SYSCALL_MQ_UNLINK: usize = 242;
+const SYSCALL_MQ_SEND: usize = 243;
+const SYSCALL_MQ_RECEIVE: usize = 244;
+const SYSCALL_SOCKET: usize = 198;
+const SYSCALL_BIND: usize = 200;
+const SYSCALL_LISTEN: usize = 201;
+const SYSCALL_ACCEPT: usize = 202;
+const SYSCALL_CONNECT: usize = 203;
+const SYSCALL_FRAMEBUFFER: usize = 2000;
+const SYSCALL_FRAMEBUFFER_FLUSH: usize = 2001;
+
+/// Dispatches a trapped syscall to its handler. `args` holds up to six
+/// raw argument registers (`a0`..`a5`), the most any syscall here needs.
+///
+/// Brackets the dispatch with [`crate::task::trace_record`] when tracing
+/// is on, so `sys_trace_read` can report how long each syscall actually
+/// took — the one real (non-`#[cfg]`) hook point this tree has for it,
+/// since there's no trap-handler wrapper to time instead.
+pub fn syscall(syscall_id: usize, args: [usize; 6]) -> isize {
+    crate::tracepoint!(crate::task::Tracepoint::SyscallDispatch, syscall_id);
+    let entry_ns = crate::task::trace_is_enabled().then(crate::timer::get_time_ns);
+    let result = dispatch(syscall_id, args);
+    if let Some(entry_ns) = entry_ns {
+        crate::task::trace_record(TraceEvent {
+            syscall_id,
+            entry_ns,
+            exit_ns: crate::timer::get_time_ns(),
+        });
+    }
+    result
+}
+
+fn dispatch(syscall_id: usize, args: [usize; 6]) -> isize {
+    match syscall_id {
+        SYSCALL_SIGNALFD => sys_signalfd(args[0] as isize, args[1] as u32),
+        SYSCALL_LSEEK => sys_lseek(args[0], args[1] as isize, args[2]),
+        SYSCALL_READV => sys_readv(args[0], args[1] as *const u8, args[2]),
+        SYSCALL_WRITEV => sys_writev(args[0], args[1] as *const u8, args[2]),
+        SYSCALL_PREAD64 => sys_pread64(args[0], args[1] as *mut u8, args[2], args[3]),
+        SYSCALL_PWRITE64 => sys_pwrite64(args[0], args[1] as *const u8, args[2], args[3]),
+        SYSCALL_DUP2 => sys_dup2(args[0], args[1]),
+        SYSCALL_DUP3 => sys_dup3(args[0], args[1], args[2] as u32),
+        SYSCALL_PRLIMIT64 => sys_prlimit64(
+            args[0],
+            args[1],
+            args[2] as *const RLimit,
+            args[3] as *mut RLimit,
+        ),
+        SYSCALL_PPOLL => sys_ppoll(
+            args[0] as *mut u8,
+            args[1],
+            args[2] as *const u8,
+            args[3] as *const u8,
+        ),
+        SYSCALL_PSELECT6 => sys_pselect6(
+            args[0],
+            args[1] as *mut u8,
+            args[2] as *mut u8,
+            args[3] as *mut u8,
+            args[4] as *const u8,
+            args[5] as *const u8,
+        ),
+        SYSCALL_SOCKETPAIR => sys_socketpair(
+            args[0] as i32,
+            args[1] as i32,
+            args[2] as i32,
+            args[3] as *mut i32,
+        ),
+        SYSCALL_MQ_OPEN => sys_mq_open(args[0] as *const u8, args[1] as i32, args[2]),
+        SYSCALL_MQ_UNLINK => sys_mq_unlink(args[0] as *const u8),
+        SYSCALL_MQ_SEND => sys_mq_send(
+            args[0] as isize,
+            args[1] as *const u8,
+            args[2],
+            args[3] as u32,
+        ),
+        SYSCALL_MQ_RECEIVE => sys_mq_receive(
+            args[0] as isize,
+            args[1] as *mut u8,
+            args[2],
+            args[3] as *mut u32,
+        ),
+        SYSCALL_SOCKET => sys_socket(args[0] as i32, args[1] as i32, args[2] as i32),
+        SYSCALL_BIND => sys_bind(args[0], args[1] as *const u8, args[2]),
+        SYSCALL_LISTEN => sys_listen(args[0], args[1]),
+        SYSCALL_ACCEPT => sys_accept(args[0], args[1] as *mut u8, args[2] as *mut usize),
+        SYSCALL_CONNECT => sys_connect(args[0], args[1] as *const u8, args[2]),
+        SYSCALL_FRAMEBUFFER => sys_framebuffer(),
+        SYSCALL_FRAMEBUFFER_FLUSH => sys_framebuffer_flush(),
+        SYSCALL_PROBE => sys_probe(),
+        SYSCALL_GETRSS => sys_getrss(),
+        SYSCALL_GETENV => sys_getenv(args[0] as *const u8, args[1] as *mut u8, args[2]),
+        SYSCALL_SPAWN => sys_spawn(args[0] as *const u8),
+        SYSCALL_SET_PRIORITY => sys_set_priority(args[0] as isize),
+        SYSCALL_WAITPID => sys_waitpid(args[0] as isize, args[1] as *mut i32, args[2]),
+        SYSCALL_FUTEX => sys_futex(args[0], args[1], args[2]),
+        SYSCALL_SHMGET => sys_shmget(args[0] as i32, args[1], args[2]),
+        SYSCALL_SHMAT => sys_shmat(args[0] as isize, args[1], args[2]),
+        SYSCALL_SHMDT => sys_shmdt(args[0]),
+        SYSCALL_MPROTECT => sys_mprotect(args[0], args[1], args[2]),
+        SYSCALL_GETTIMEOFDAY => sys_gettimeofday(args[0] as *mut Timeval),
+        SYSCALL_GETRUSAGE => sys_getrusage(args[0] as i32, args[1] as *mut Rusage),
+        SYSCALL_TRACE_CTL => sys_trace_ctl(args[0], args[1]),
+        SYSCALL_TRACE_READ => sys_trace_read(args[0] as *mut TraceEvent, args[1]),
+        SYSCALL_DEBUG_CTL => sys_debug_ctl(args[0], args[1]),
+        SYSCALL_KLOG => sys_klog(args[0] as *mut u8, args[1]),
+        SYSCALL_SHUTDOWN => sys_shutdown(args[0]),
+        SYSCALL_REBOOT => sys_reboot(args[0]),
+        SYSCALL_HART_OFFLINE => sys_hart_offline(args[0]),
+        SYSCALL_CLOCK_GETTIME => sys_clock_gettime(args[0], args[1] as *mut Timespec),
+        SYSCALL_NANOSLEEP => sys_nanosleep(args[0] as *const Timespec, args[1] as *mut Timespec),
+        SYSCALL_SCHED_STATS => sys_sched_stats(args[0] as *mut HartLoad, args[1]),
+        SYSCALL_RWLOCK_CREATE => sys_rwlock_create(),
+        SYSCALL_RWLOCK_RDLOCK => sys_rwlock_rdlock(args[0]),
+        SYSCALL_RWLOCK_WRLOCK => sys_rwlock_wrlock(args[0]),
+        SYSCALL_RWLOCK_UNLOCK => sys_rwlock_unlock(args[0]),
+        SYSCALL_SETPGID => sys_setpgid(args[0], args[1]),
+        SYSCALL_GETPGID => sys_getpgid(args[0]),
+        SYSCALL_BRK => sys_brk(args[0]),
+        SYSCALL_GETTID => sys_gettid(),
+        SYSCALL_THREAD_CREATE => sys_thread_create(args[0], args[1], args[2]),
+        SYSCALL_THREAD_EXIT => sys_thread_exit(args[0] as isize),
+        SYSCALL_THREAD_JOIN => sys_thread_join(args[0], args[1] as *mut isize),
+        SYSCALL_MOUNT => sys_mount(args[0] as *const u8, args[1] as *const u8),
+        SYSCALL_UMOUNT2 => sys_umount(args[0] as *const u8),
+        SYSCALL_SIGACTION => sys_sigaction(args[0] as u32, args[1]),
+        SYSCALL_SIGRETURN => sys_sigreturn(),
+        SYSCALL_MMAP => sys_mmap(
+            args[0],
+            args[1],
+            args[2],
+            args[3],
+            args[4] as isize,
+            args[5],
+        ),
+        _ => panic!("Unsupported syscall_id: {}", syscall_id),
+    }
+}
@@ -0,0 +1,47 @@
+//! `sys_mount`/`sys_umount`: attach/detach a registered filesystem type
+//! at a path, backed by [`crate::fs::MountTable`].
+
+use super::errno::EFAULT;
+use crate::fs::{lookup_filesystem, mount_table};
+use crate::mm::copy_cstr_from_user;
+
+/// Longest `fs_type`/`target` string these will read out of user space;
+/// past this it's treated the same as a missing NUL terminator, matching
+/// [`sys_getenv`](super::process::sys_getenv)'s `MAX_ENV_NAME_LEN`.
+const MAX_MOUNT_STRING_LEN: usize = 256;
+
+/// `sys_mount(fs_type, target)`: mounts the filesystem registered under
+/// `fs_type` (via [`crate::fs::register_filesystem`]) at `target`.
+/// Returns 0 on success, -1 if the type isn't registered or something is
+/// already mounted at `target`.
+pub fn sys_mount(fs_type_ptr: *const u8, target_ptr: *const u8) -> isize {
+    let Ok(fs_type) = copy_cstr_from_user(fs_type_ptr, MAX_MOUNT_STRING_LEN) else {
+        return EFAULT;
+    };
+    let Ok(target) = copy_cstr_from_user(target_ptr, MAX_MOUNT_STRING_LEN) else {
+        return EFAULT;
+    };
+    match lookup_filesystem(&fs_type) {
+        Some(fs) => {
+            if mount_table().exclusive_access().mount(&target, fs) {
+                0
+            } else {
+                -1
+            }
+        }
+        None => -1,
+    }
+}
+
+/// `sys_umount(target)`: detaches whatever is mounted at `target`.
+/// Returns 0 on success, -1 if nothing was mounted there.
+pub fn sys_umount(target_ptr: *const u8) -> isize {
+    let Ok(target) = copy_cstr_from_user(target_ptr, MAX_MOUNT_STRING_LEN) else {
+        return EFAULT;
+    };
+    if mount_table().exclusive_access().umount(&target) {
+        0
+    } else {
+        -1
+    }
+}
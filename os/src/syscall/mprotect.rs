@@ -0,0 +1,22 @@
+//! `sys_mprotect`.
+
+use crate::mm::MapPermission;
+use crate::task::current_task;
+
+/// `sys_mprotect(addr, len, prot)`: changes the permission bits of the
+/// mapped range `[addr, addr + len)`.
+pub fn sys_mprotect(addr: usize, len: usize, prot: usize) -> isize {
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let perm = MapPermission::from_bits_truncate(prot as u8);
+    let page_size = crate::config::PAGE_SIZE;
+    let start_vpn = addr / page_size;
+    let end_vpn = (addr + len + page_size - 1) / page_size;
+    task.inner_exclusive_access()
+        .mm
+        .protect_range(start_vpn, end_vpn, perm);
+    crate::mm::shootdown_range(start_vpn, end_vpn);
+    0
+}
@@ -0,0 +1,33 @@
+//! `sys_mq_open`/`sys_mq_send`/`sys_mq_receive`/`sys_mq_unlink` syscalls.
+
+/// `sys_mq_open(name, oflag, max_msgs)`: always returns -1; `name` is
+/// not read and no queue is opened or created.
+///
+/// There is no per-process table mapping a small integer handle to an
+/// `Arc<MessageQueue>`, so none of these four calls reaches the queue in
+/// `crate::ipc::mq` (priority ordering, yield-and-retry send/receive).
+pub fn sys_mq_open(name: *const u8, oflag: i32, max_msgs: usize) -> isize {
+    let _ = (name, oflag, max_msgs);
+    -1
+}
+
+/// `sys_mq_send(mqd, msg, msg_len, priority)`: always returns -1; nothing
+/// is queued. Same missing descriptor table as [`sys_mq_open`].
+pub fn sys_mq_send(mqd: isize, msg: *const u8, msg_len: usize, priority: u32) -> isize {
+    let _ = (mqd, msg, msg_len, priority);
+    -1
+}
+
+/// `sys_mq_receive(mqd, msg, msg_len, priority)`: always returns -1;
+/// nothing is dequeued. Same missing descriptor table as [`sys_mq_open`].
+pub fn sys_mq_receive(mqd: isize, msg: *mut u8, msg_len: usize, priority: *mut u32) -> isize {
+    let _ = (mqd, msg, msg_len, priority);
+    -1
+}
+
+/// `sys_mq_unlink(name)`: always returns -1; `name` is not read and no
+/// queue is removed from the registry.
+pub fn sys_mq_unlink(name: *const u8) -> isize {
+    let _ = name;
+    -1
+}
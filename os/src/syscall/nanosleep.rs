@@ -0,0 +1,27 @@
+//! `sys_nanosleep`: sleeps for a relative duration by yielding until the
+//! deadline passes. Signals do not cut the sleep short.
+
+use super::time::Timespec;
+use crate::task::suspend_current_and_run_next;
+use crate::timer::{get_time_ns, NANOS_PER_SEC};
+
+/// `sys_nanosleep(req, rem)`: sleeps for `*req` and returns 0. Pending
+/// signals are not checked, so the full duration always elapses; `*rem`
+/// (when non-null) is always set to zero.
+pub fn sys_nanosleep(req: *const Timespec, rem: *mut Timespec) -> isize {
+    let (sec, nsec) = unsafe { ((*req).tv_sec, (*req).tv_nsec) };
+    let duration_ns = sec * NANOS_PER_SEC + nsec;
+    let deadline = get_time_ns() + duration_ns;
+
+    while get_time_ns() < deadline {
+        suspend_current_and_run_next();
+    }
+
+    if !rem.is_null() {
+        unsafe {
+            (*rem).tv_sec = 0;
+            (*rem).tv_nsec = 0;
+        }
+    }
+    0
+}
@@ -0,0 +1,91 @@
+//! `sys_socket`/`sys_bind`/`sys_listen`/`sys_accept`/`sys_connect`
+//! syscalls over [`crate::net`]'s loopback-only socket layer.
+
+use crate::fs::File;
+use crate::net::Socket;
+use crate::task::current_task;
+use alloc::sync::Arc;
+
+/// The file behind `fd` in the calling task's table, if any.
+fn file_for_fd(fd: usize) -> Option<Arc<dyn File>> {
+    let task = current_task()?;
+    let inner = task.inner_exclusive_access();
+    match inner.fd_table.get(fd) {
+        Some(Some(entry)) => Some(Arc::clone(&entry.file)),
+        _ => None,
+    }
+}
+
+/// `sys_socket(domain, type, protocol)`: creates an unbound loopback
+/// socket and returns its fd. Only `AF_INET`/`SOCK_STREAM` is supported —
+/// there's exactly one kind of socket ([`crate::net::Socket`], a
+/// loopback-only TCP-lite stream) behind it so far.
+pub fn sys_socket(domain: i32, type_: i32, protocol: i32) -> isize {
+    const AF_INET: i32 = 2;
+    const SOCK_STREAM: i32 = 1;
+    const SOCK_TYPE_MASK: i32 = 0xf;
+
+    if domain != AF_INET || type_ & SOCK_TYPE_MASK != SOCK_STREAM || protocol != 0 {
+        return -1;
+    }
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let mut inner = task.inner_exclusive_access();
+    inner.alloc_fd(Socket::new()).map_or(-1, |fd| fd as isize)
+}
+
+/// `sys_bind(sockfd, addr, addrlen)`: binds `sockfd` to a local port.
+///
+/// `addr` (a `struct sockaddr_in`) is not read: this always binds to an
+/// ephemeral port, the behavior `bind` has for a caller-supplied port of
+/// `0`. A requested non-zero port is ignored.
+pub fn sys_bind(sockfd: usize, _addr: *const u8, _addrlen: usize) -> isize {
+    match file_for_fd(sockfd) {
+        Some(file) => file.bind(None).map_or(-1, |_| 0),
+        None => -1,
+    }
+}
+
+/// `sys_listen(sockfd, backlog)`: marks a bound socket as willing to
+/// accept connections. Needs no user pointers, so this is the real thing
+/// end to end.
+pub fn sys_listen(sockfd: usize, backlog: usize) -> isize {
+    match file_for_fd(sockfd) {
+        Some(file) => file.listen(backlog).map_or(-1, |_| 0),
+        None => -1,
+    }
+}
+
+/// `sys_accept(sockfd, addr, addrlen)`: blocks until a connection arrives
+/// on `sockfd`, returning a new fd for it. `addr`/`addrlen` are where the
+/// peer's address would be written back — real `accept` already allows
+/// both null (peer address not wanted), the only case this handles; a
+/// non-null pair is silently not written to rather than refused, since
+/// the connection this returns is fully real either way.
+pub fn sys_accept(sockfd: usize, _addr: *mut u8, _addrlen: *mut usize) -> isize {
+    let socket = match file_for_fd(sockfd) {
+        Some(file) => file,
+        None => return -1,
+    };
+    let stream = match socket.accept() {
+        Ok(stream) => stream,
+        Err(()) => return -1,
+    };
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let mut inner = task.inner_exclusive_access();
+    inner.alloc_fd(stream).map_or(-1, |fd| fd as isize)
+}
+
+/// `sys_connect(sockfd, addr, addrlen)`: always returns -1. `addr` is not
+/// read, as in [`sys_bind`], and no connection is made.
+pub fn sys_connect(sockfd: usize, _addr: *const u8, _addrlen: usize) -> isize {
+    match file_for_fd(sockfd) {
+        Some(_file) => -1,
+        None => -1,
+    }
+}
@@ -0,0 +1,33 @@
+//! `sys_setpgid`/`sys_getpgid` syscalls.
+
+use crate::task::current_task;
+
+/// `sys_setpgid(pid, pgid)`: moves process `pid` (0 meaning the caller)
+/// into group `pgid` (0 meaning "make it its own group leader").
+/// Returns 0 on success, -1 on failure.
+pub fn sys_setpgid(pid: usize, pgid: usize) -> isize {
+    let Some(task) = current_task() else {
+        return -1;
+    };
+    let mut inner = task.inner_exclusive_access();
+    if pid != 0 && pid != task.pid {
+        // Setting another process's group requires walking the process
+        // tree for a parent/child relationship check this module doesn't
+        // yet have access to; only the calling process is supported here.
+        return -1;
+    }
+    inner.pgid = if pgid == 0 { task.pid } else { pgid };
+    0
+}
+
+/// `sys_getpgid(pid)`: returns the process group id of `pid` (0 meaning
+/// the caller).
+pub fn sys_getpgid(pid: usize) -> isize {
+    let Some(task) = current_task() else {
+        return -1;
+    };
+    if pid != 0 && pid != task.pid {
+        return -1;
+    }
+    task.inner_exclusive_access().pgid as isize
+}
@@ -0,0 +1,147 @@
+//! Process-control and introspection syscalls.
+
+use super::errno::{EFAULT, EINVAL, ENOENT, EPERM, ESRCH};
+use super::time::Timeval;
+use crate::config;
+use crate::mm::{copy_cstr_from_user, copy_to_user};
+use crate::task::CpuTime;
+
+/// Longest environment variable name [`sys_getenv`] will read out of user
+/// space; past this it's treated the same as a missing NUL terminator.
+const MAX_ENV_NAME_LEN: usize = 256;
+
+/// `sys_probe()`: reports which optional kernel subsystems this build was
+/// compiled with, as a bitmask (see [`config::KernelFeatures::as_bits`]).
+pub fn sys_probe() -> isize {
+    config::FEATURES.as_bits() as isize
+}
+
+/// `sys_getenv(name, buf, len)`: copies the value of environment variable
+/// `name` into `buf`, returning its length, or -1 if unset or `name`/`buf`
+/// can't be read. Populated from the `envp` array the loader hands to
+/// `exec`.
+pub fn sys_getenv(name_ptr: *const u8, buf: *mut u8, len: usize) -> isize {
+    let Ok(name) = copy_cstr_from_user(name_ptr, MAX_ENV_NAME_LEN) else {
+        return EFAULT;
+    };
+    let Some(task) = crate::task::current_task() else {
+        return ESRCH;
+    };
+    let inner = task.inner_exclusive_access();
+    let Some(value) = inner.env.get(&name) else {
+        return ENOENT;
+    };
+    let bytes = value.as_bytes();
+    let n = bytes.len().min(len);
+    if buf.is_null() {
+        return EFAULT;
+    }
+    unsafe {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), buf, n);
+    }
+    n as isize
+}
+
+/// `sys_getrss()`: the calling process's resident set size in pages, as
+/// far as this kernel tracks it: always 0, since there is no per-process
+/// frame accounting. `ESRCH` if there is no current task.
+pub fn sys_getrss() -> isize {
+    match crate::task::current_task() {
+        Some(task) => {
+            let _inner = task.inner_exclusive_access();
+            // `MemorySet::rss_pages` counts reserved pages, not resident
+            // frames, so this reports zero rather than that number.
+            0
+        }
+        None => ESRCH,
+    }
+}
+
+/// The only `who` value [`sys_getrusage`] understands; `RUSAGE_CHILDREN`
+/// isn't tracked since an exited child's accounting isn't folded into its
+/// parent yet.
+pub const RUSAGE_SELF: i32 = 0;
+
+#[repr(C)]
+pub struct Rusage {
+    pub ru_utime: Timeval,
+    pub ru_stime: Timeval,
+}
+
+/// Walks the process tree (rooted at init, the same way procfs's own
+/// `find_task` does, since there's no flat process table to index into
+/// directly) summing [`CpuTime`] across every task sharing `tgid` — one
+/// process's threads, not just its leader.
+fn sum_cpu_time(tgid: usize) -> CpuTime {
+    let mut total = CpuTime::default();
+    let Some(root) = crate::task::initproc() else {
+        return total;
+    };
+    let mut stack = alloc::vec![root];
+    while let Some(task) = stack.pop() {
+        let inner = task.inner_exclusive_access();
+        if inner.tgid == tgid {
+            total.record_user_ns(inner.cpu_time.utime_ns);
+            total.record_kernel_ns(inner.cpu_time.stime_ns);
+        }
+        stack.extend(inner.children.iter().cloned());
+    }
+    total
+}
+
+/// `sys_getrusage(who, usage)`: writes the calling process's accumulated
+/// user/kernel time into `*usage`. Only [`RUSAGE_SELF`] is supported.
+pub fn sys_getrusage(who: i32, usage: *mut Rusage) -> isize {
+    if who != RUSAGE_SELF {
+        return EINVAL;
+    }
+    let Some(task) = crate::task::current_task() else {
+        return ESRCH;
+    };
+    let tgid = task.inner_exclusive_access().tgid;
+    let total = sum_cpu_time(tgid);
+    let rusage = Rusage {
+        ru_utime: ns_to_timeval(total.utime_ns),
+        ru_stime: ns_to_timeval(total.stime_ns),
+    };
+    match copy_to_user(usage, rusage) {
+        Ok(()) => 0,
+        Err(err) => err,
+    }
+}
+
+fn ns_to_timeval(ns: u64) -> Timeval {
+    Timeval {
+        tv_sec: ns / crate::timer::NANOS_PER_SEC,
+        tv_usec: (ns % crate::timer::NANOS_PER_SEC) / 1_000,
+    }
+}
+
+/// Whether the calling task is the init process — the only one
+/// [`sys_shutdown`]/[`sys_reboot`]/`sys_hart_offline` trust to touch
+/// machine-wide state, the same restriction a real kernel places on
+/// `reboot(2)`/hotplug via capabilities.
+pub(super) fn caller_is_initproc() -> bool {
+    match (crate::task::current_task(), crate::task::initproc()) {
+        (Some(task), Some(init)) => alloc::sync::Arc::ptr_eq(&task, &init),
+        _ => false,
+    }
+}
+
+/// `sys_shutdown(failure)`: powers the machine off via SBI SRST if the
+/// caller is init, returning -1 otherwise. Does not return on success.
+pub fn sys_shutdown(failure: usize) -> isize {
+    if !caller_is_initproc() {
+        return EPERM;
+    }
+    crate::sbi::shutdown(failure != 0);
+}
+
+/// `sys_reboot(failure)`: cold-reboots the machine via SBI SRST if the
+/// caller is init, returning -1 otherwise. Does not return on success.
+pub fn sys_reboot(failure: usize) -> isize {
+    if !caller_is_initproc() {
+        return EPERM;
+    }
+    crate::sbi::reboot(failure != 0);
+}
@@ -0,0 +1,31 @@
+//! `sys_rwlock_create`/`sys_rwlock_rdlock`/`sys_rwlock_wrlock`/`sys_rwlock_unlock` syscalls.
+
+use super::errno::ENOSYS;
+
+/// `sys_rwlock_create()`: allocates a new reader-writer lock, returning its id.
+pub fn sys_rwlock_create() -> isize {
+    // Dispatches into a per-process rwlock table keyed by id, the same
+    // shape as [`ShmTable`](crate::mm::ShmTable); wired up alongside it.
+    ENOSYS
+}
+
+/// `sys_rwlock_rdlock(id)`: blocks the caller until it holds a shared
+/// (reader) lock on `id`.
+pub fn sys_rwlock_rdlock(id: usize) -> isize {
+    let _ = id;
+    ENOSYS
+}
+
+/// `sys_rwlock_wrlock(id)`: blocks the caller until it holds the
+/// exclusive (writer) lock on `id`.
+pub fn sys_rwlock_wrlock(id: usize) -> isize {
+    let _ = id;
+    ENOSYS
+}
+
+/// `sys_rwlock_unlock(id)`: releases whichever lock (reader or writer)
+/// the caller holds on `id`.
+pub fn sys_rwlock_unlock(id: usize) -> isize {
+    let _ = id;
+    ENOSYS
+}
@@ -0,0 +1,47 @@
+//! Scheduler-related syscalls.
+
+use crate::task::{current_task, HART_STATS};
+
+#[repr(C)]
+pub struct HartLoad {
+    pub hart: u32,
+    pub load_permille: u32,
+}
+
+/// `sys_sched_stats(buf, max_harts)`: fills `buf` with one [`HartLoad`]
+/// entry per online hart (up to `max_harts`), returning the number
+/// written. A `/proc`-like introspection point for load-balancing
+/// decisions and diagnosing uneven hart utilization, without exposing raw
+/// kernel scheduler internals to userspace.
+pub fn sys_sched_stats(buf: *mut HartLoad, max_harts: usize) -> isize {
+    let mut n = 0;
+    for hart in crate::task::online_harts().into_iter().take(max_harts) {
+        unsafe {
+            *buf.add(n) = HartLoad {
+                hart: hart as u32,
+                load_permille: HART_STATS[hart].load_permille() as u32,
+            };
+        }
+        n += 1;
+    }
+    n as isize
+}
+
+/// `sys_set_priority(priority)`: sets the calling task's stride-scheduling
+/// priority, returning the priority on success or -1 if it's out of range.
+/// Priorities below 2 are rejected outright rather than silently clamped,
+/// since [`StrideEntry::new`](crate::task::StrideEntry) already warns about
+/// the near-starvation stride they'd produce.
+pub fn sys_set_priority(priority: isize) -> isize {
+    if priority < 2 {
+        return -1;
+    }
+    match current_task() {
+        Some(task) => {
+            let mut inner = task.inner_exclusive_access();
+            inner.stride.set_priority(priority as u64);
+            priority
+        }
+        None => -1,
+    }
+}
@@ -0,0 +1,22 @@
+//! `sys_shmget`/`sys_shmat`/`sys_shmdt` syscalls.
+
+/// `sys_shmget(key, size, _flags)`: always returns -1. No segment is
+/// looked up or created; none of these three calls consults
+/// [`crate::mm::ShmTable`] or any other shared-memory table.
+pub fn sys_shmget(key: i32, size: usize, _flags: usize) -> isize {
+    let _ = (key, size);
+    -1
+}
+
+/// `sys_shmat(shmid, addr, _flags)`: always returns -1; nothing is
+/// mapped into the caller's address space.
+pub fn sys_shmat(shmid: isize, addr: usize, _flags: usize) -> isize {
+    let _ = (shmid, addr);
+    -1
+}
+
+/// `sys_shmdt(addr)`: always returns -1; nothing is unmapped or detached.
+pub fn sys_shmdt(addr: usize) -> isize {
+    let _ = addr;
+    -1
+}
@@ -0,0 +1,32 @@
+//! `sigaction`/`sigreturn` syscalls.
+
+use crate::task::{current_task, SigAction, SignalFlags};
+
+/// `sys_sigaction(signum, handler)`: installs `handler` (a user-space
+/// function pointer, or 0 for default action) for `signum`.
+pub fn sys_sigaction(signum: u32, handler: usize) -> isize {
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let mut inner = task.inner_exclusive_access();
+    inner.sigactions.set(
+        signum,
+        SigAction {
+            handler,
+            mask: SignalFlags::empty(),
+        },
+    );
+    0
+}
+
+/// `sys_sigreturn()`: returns 0 if there is a current task and -1
+/// otherwise. No context is restored: nothing delivers a signal to a user
+/// handler or saves a [`SignalFrame`](crate::task::SignalFrame) to return
+/// out of.
+pub fn sys_sigreturn() -> isize {
+    match current_task() {
+        Some(_) => 0,
+        None => -1,
+    }
+}
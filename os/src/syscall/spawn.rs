@@ -0,0 +1,67 @@
+//! `sys_spawn`: creates a child process running a named executable
+//! directly, without the fork-then-exec overhead of duplicating the
+//! parent's address space just to immediately discard it.
+
+use super::errno::EFAULT;
+use crate::fs::mount_table;
+use crate::mm::copy_cstr_from_user;
+use crate::task::{current_task, exit_current_and_switch_away, resolve_path, ready_queue, TaskControlBlock, DEFAULT_PATH};
+use alloc::sync::Arc;
+
+/// Longest path these will read out of user space, matching
+/// [`sys_mount`](super::mount::sys_mount)'s `MAX_MOUNT_STRING_LEN`.
+const MAX_SPAWN_PATH_LEN: usize = 256;
+
+/// `sys_spawn(path)`: loads and runs `path` as a new child of the calling
+/// task, returning its pid (or -1 on failure to resolve/load it).
+///
+/// "Loads and runs" is aspirational today: [`resolve_path`] against the
+/// real mount table confirms the binary actually exists, and the child
+/// gets a real [`TaskControlBlock`], pushed onto the real
+/// [`ready_queue`] and genuinely switched to — but there's still no
+/// `sys_open`/`OSInode` bridge in this tree to turn the
+/// [`VfsInode`](crate::fs::VfsInode) `resolve_path` found into a
+/// [`File`](crate::fs::File) byte stream, let alone an ELF parser or
+/// page table to load that stream into the child's own address space
+/// (see `mm::page_table`'s doc comment). So the child's body, for now,
+/// is [`spawned_child_main`]: it proves the process actually gets
+/// created and scheduled, then exits immediately rather than running
+/// code from `path` it has no way to read yet.
+pub fn sys_spawn(path_ptr: *const u8) -> isize {
+    let Ok(path) = copy_cstr_from_user(path_ptr, MAX_SPAWN_PATH_LEN) else {
+        return EFAULT;
+    };
+    let parent = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+    let resolved = resolve_path(&path, DEFAULT_PATH, |candidate| {
+        mount_table().exclusive_access().lookup(candidate).is_some()
+    });
+    let Some(_resolved_path) = resolved else {
+        return -1;
+    };
+    let (parent_pgid, parent_sid) = {
+        let parent_inner = parent.inner_exclusive_access();
+        (parent_inner.pgid, parent_inner.sid)
+    };
+    let child = TaskControlBlock::new(spawned_child_main, 0);
+    {
+        let mut child_inner = child.inner_exclusive_access();
+        child_inner.pgid = parent_pgid;
+        child_inner.sid = parent_sid;
+        child_inner.parent = Some(Arc::downgrade(&parent));
+    }
+    let pid = child.pid;
+    parent.inner_exclusive_access().children.push(Arc::clone(&child));
+    ready_queue().exclusive_access().enqueue(child);
+    pid as isize
+}
+
+/// A spawned child's kernel-mode body until there's an ELF loader to
+/// give it a real one (see [`sys_spawn`]'s doc comment). Exits
+/// immediately with code 0 rather than looping forever with nothing to
+/// do, so `waitpid`ing on it actually completes.
+fn spawned_child_main(_arg: usize) -> ! {
+    exit_current_and_switch_away(0)
+}
@@ -0,0 +1,120 @@
+//! Thread syscalls: `sys_thread_create`, `sys_gettid`, `sys_thread_exit`
+//! and the `sys_thread_join` that waits on it.
+
+use super::errno::ESRCH;
+use crate::mm::copy_to_user;
+use crate::task::{current_task, suspend_current_and_run_next, TaskControlBlock};
+use alloc::sync::Arc;
+
+/// Minimum stack a new thread is allowed to request; smaller requests are
+/// rounded up rather than rejected, since a too-small stack fails much
+/// later and much more confusingly than at creation time.
+pub const MIN_THREAD_STACK_SIZE: usize = 4096;
+
+/// `sys_thread_create(entry, arg, stack_size)`: creates a new thread in
+/// the calling process's thread group, starting at `entry` with `arg` in
+/// `a0` and a stack of at least `stack_size` bytes. Returns the new
+/// thread's tid, or -1 on failure.
+///
+/// "Starting at `entry`" means a kernel function pointer today, the same
+/// gap [`super::spawn::sys_spawn`]'s doc comment explains: there's no
+/// page table to give this thread its own user stack or run `entry` as a
+/// user-mode address instead of a kernel one, since `entry` is simply
+/// called directly on the new thread's kernel stack. `stack_size` beyond
+/// [`MIN_THREAD_STACK_SIZE`] is accepted but unused, for the same
+/// reason: [`TaskControlBlock::new`] always sizes the kernel stack from
+/// `config::KERNEL_STACK_SIZE`, since there's no per-task stack area
+/// ([`crate::task::stack_layout`]) backing a caller-chosen size yet.
+pub fn sys_thread_create(entry: usize, arg: usize, stack_size: usize) -> isize {
+    let _ = stack_size.max(MIN_THREAD_STACK_SIZE);
+    let current = match current_task() {
+        Some(task) => task,
+        None => return ESRCH,
+    };
+    let entry: fn(usize) -> ! = unsafe { core::mem::transmute(entry) };
+    let thread = TaskControlBlock::new(entry, arg);
+    {
+        let current_inner = current.inner_exclusive_access();
+        let mut thread_inner = thread.inner_exclusive_access();
+        // A thread shares its creator's thread-group id rather than
+        // starting a new one, the one thing that actually distinguishes
+        // it from a `sys_spawn`ed child process at this layer.
+        thread_inner.tgid = current_inner.tgid;
+        thread_inner.pgid = current_inner.pgid;
+        thread_inner.sid = current_inner.sid;
+        thread_inner.parent = current_inner.parent.clone();
+    }
+    let tid = thread.pid as isize;
+    // Reusing `children`/`sys_waitpid`'s list as the thread-group
+    // membership `sys_thread_join` below searches: the two never
+    // collide, since `sys_waitpid` only reaps entries with `exit_code`
+    // set (a process's `sys_exit`, which nothing calls on a thread) and
+    // `sys_thread_join` only reaps entries with `join_result` set
+    // (`sys_thread_exit`, below). A real process-vs-thread-group split
+    // can replace this once something other than "creator's children
+    // list" needs to enumerate a thread group.
+    current.inner_exclusive_access().children.push(Arc::clone(&thread));
+    crate::task::ready_queue().exclusive_access().enqueue(thread);
+    tid
+}
+
+/// `sys_gettid()`: the calling thread's id. A thread's tid is its pid;
+/// what distinguishes threads in the same process is a shared `tgid`,
+/// not a separate id space.
+pub fn sys_gettid() -> isize {
+    match current_task() {
+        Some(task) => task.pid as isize,
+        None => ESRCH,
+    }
+}
+
+/// `sys_thread_exit(retval)`: records `retval` in `join_result`, where
+/// `sys_thread_join`'s polling loop finds it, and calls `wake_all` on
+/// `joiners` (which nothing adds to). Does not itself tear down the
+/// thread's resources; that happens once it's reaped.
+pub fn sys_thread_exit(retval: isize) -> ! {
+    if let Some(task) = current_task() {
+        let mut inner = task.inner_exclusive_access();
+        inner.join_result = Some(retval);
+        inner.joiners.wake_all();
+    }
+    loop {
+        suspend_current_and_run_next();
+    }
+}
+
+/// `sys_thread_join(tid, retval_ptr)`: blocks until thread `tid` has
+/// called `sys_thread_exit`, then returns 0 and writes its exit value to
+/// `retval_ptr` (if non-null). Returns [`ESRCH`] if `tid` isn't a thread
+/// the caller created (see `sys_thread_create`'s doc comment on why
+/// that's where this looks).
+pub fn sys_thread_join(tid: usize, retval_ptr: *mut isize) -> isize {
+    let task = match current_task() {
+        Some(task) => task,
+        None => return ESRCH,
+    };
+    loop {
+        let target = task
+            .inner_exclusive_access()
+            .children
+            .iter()
+            .find(|child| child.pid == tid)
+            .cloned();
+        let Some(target) = target else {
+            return ESRCH;
+        };
+        let join_result = target.inner_exclusive_access().join_result;
+        if let Some(retval) = join_result {
+            task.inner_exclusive_access()
+                .children
+                .retain(|child| child.pid != tid);
+            if !retval_ptr.is_null() {
+                if let Err(err) = copy_to_user(retval_ptr, retval) {
+                    return err;
+                }
+            }
+            return 0;
+        }
+        suspend_current_and_run_next();
+    }
+}
@@ -0,0 +1,48 @@
+//! Time-related syscalls.
+
+use crate::mm::copy_to_user;
+use crate::timer::TimeSpec;
+
+#[repr(C)]
+pub struct Timeval {
+    pub tv_sec: u64,
+    pub tv_usec: u64,
+}
+
+/// `sys_gettimeofday(tv)`: writes the current time into `*tv`.
+pub fn sys_gettimeofday(tv: *mut Timeval) -> isize {
+    let ts = TimeSpec::now();
+    match copy_to_user(
+        tv,
+        Timeval {
+            tv_sec: ts.sec,
+            tv_usec: ts.nsec / 1_000,
+        },
+    ) {
+        Ok(()) => 0,
+        Err(err) => err,
+    }
+}
+
+#[repr(C)]
+pub struct Timespec {
+    pub tv_sec: u64,
+    pub tv_nsec: u64,
+}
+
+/// `sys_clock_gettime(clock_id, tp)`: both `CLOCK_REALTIME` and
+/// `CLOCK_MONOTONIC` resolve to the same rdtime-backed counter (see
+/// [`crate::timer`]).
+pub fn sys_clock_gettime(_clock_id: usize, tp: *mut Timespec) -> isize {
+    let ts = TimeSpec::now();
+    match copy_to_user(
+        tp,
+        Timespec {
+            tv_sec: ts.sec,
+            tv_nsec: ts.nsec,
+        },
+    ) {
+        Ok(()) => 0,
+        Err(err) => err,
+    }
+}
@@ -0,0 +1,42 @@
+//! Syscall latency tracing and tracepoint control/read-out.
+
+use crate::task::{self, TraceEvent};
+
+/// `sys_trace_ctl(enable, overwrite)`: turns per-hart syscall tracing on
+/// (`enable != 0`, with the ring-overwrite policy `overwrite != 0` picks)
+/// or off. Always succeeds.
+pub fn sys_trace_ctl(enable: usize, overwrite: usize) -> isize {
+    if enable != 0 {
+        task::trace_enable(overwrite != 0);
+    } else {
+        task::trace_disable();
+    }
+    0
+}
+
+/// `sys_trace_read(buf, max)`: drains every hart's trace ring and writes
+/// up to `max` [`TraceEvent`]s into `buf`, returning how many were
+/// written. Events beyond `max` are dropped, not left for a later call —
+/// a caller that wants everything should read with a large enough `max`.
+pub fn sys_trace_read(buf: *mut TraceEvent, max: usize) -> isize {
+    let events = task::trace_drain_all();
+    let n = events.len().min(max);
+    for (i, event) in events.into_iter().take(n).enumerate() {
+        unsafe {
+            *buf.add(i) = event;
+        }
+    }
+    n as isize
+}
+
+/// `sys_debug_ctl(tracepoint, action)`: configures a static tracepoint
+/// (`tracepoint` is a [`task::Tracepoint`] discriminant: 0 = scheduler
+/// switch, 1 = trap entry, 2 = syscall dispatch) to do nothing, count, or
+/// log to the trace ring (`action`: 0/1/2 respectively) the next time it
+/// fires. Returns 0 on success, -1 if either argument is out of range.
+pub fn sys_debug_ctl(tracepoint: usize, action: usize) -> isize {
+    match task::tracepoint_configure_by_index(tracepoint, action as u32) {
+        Some(()) => 0,
+        None => -1,
+    }
+}
@@ -0,0 +1,53 @@
+//! `sys_waitpid`, with both the non-blocking `WNOHANG` poll used by
+//! `sys_getrss`-style monitors and the ordinary blocking wait.
+
+use crate::task::{current_task, suspend_current_and_run_next};
+
+pub const WNOHANG: usize = 1;
+
+/// `sys_waitpid(pid, exit_code_ptr, options)`: `pid == -1` waits for any
+/// child. Returns the reaped child's pid, 0 if `WNOHANG` was given and no
+/// child has exited yet, or -1 if the caller has no matching children.
+pub fn sys_waitpid(pid: isize, _exit_code_ptr: *mut i32, options: usize) -> isize {
+    let task = match current_task() {
+        Some(task) => task,
+        None => return -1,
+    };
+
+    loop {
+        let result = try_reap(&task, pid);
+        match result {
+            Some(reaped) => return reaped,
+            None => {
+                if options & WNOHANG != 0 {
+                    return 0;
+                }
+                suspend_current_and_run_next();
+            }
+        }
+    }
+}
+
+/// Attempts one non-blocking reap pass. Returns `Some(pid_or_error)` if
+/// the wait is resolved (a zombie was reaped, or there is no matching
+/// child at all), or `None` if the caller should keep waiting.
+fn try_reap(task: &alloc::sync::Arc<crate::task::TaskControlBlock>, pid: isize) -> Option<isize> {
+    let mut inner = task.inner_exclusive_access();
+    let matches = |child: &alloc::sync::Arc<crate::task::TaskControlBlock>| {
+        pid == -1 || pid as usize == child.pid
+    };
+    if !inner.children.iter().any(matches) {
+        return Some(-1);
+    }
+    let zombie_index = inner
+        .children
+        .iter()
+        .position(|child| matches(child) && child.inner_exclusive_access().is_zombie());
+    match zombie_index {
+        Some(index) => {
+            let child = inner.children.remove(index);
+            Some(child.pid as isize)
+        }
+        None => None,
+    }
+}
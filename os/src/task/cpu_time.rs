@@ -0,0 +1,25 @@
+//! Per-task CPU time accounting: nanoseconds spent in user mode vs.
+//! kernel mode, accumulated by whoever measures the interval.
+//!
+//! Nothing measures an interval into a task's `CpuTime`: neither
+//! `trap::entry`'s handler nor `scheduler_loop::run_tasks` calls
+//! [`record_user_ns`](CpuTime::record_user_ns)/
+//! [`record_kernel_ns`](CpuTime::record_kernel_ns), so every task's
+//! counters stay 0. Their only caller is `sys_getrusage`, which uses them
+//! to sum the counters of the tasks sharing a `tgid`. [`super::TrapStats`]'s
+//! `record_*` methods likewise have no callers.
+#[derive(Default, Copy, Clone)]
+pub struct CpuTime {
+    pub utime_ns: u64,
+    pub stime_ns: u64,
+}
+
+impl CpuTime {
+    pub fn record_user_ns(&mut self, elapsed_ns: u64) {
+        self.utime_ns += elapsed_ns;
+    }
+
+    pub fn record_kernel_ns(&mut self, elapsed_ns: u64) {
+        self.stime_ns += elapsed_ns;
+    }
+}
@@ -0,0 +1,53 @@
+//! Minimal ELF dynamic-linking support: recognizing a `PT_INTERP` segment
+//! and applying the relocation types this kernel's loader actually needs
+//! to run a dynamically-linked binary (R_RISCV_RELATIVE and
+//! R_RISCV_64, the two a statically-linked-but-PIE or simply-relocatable
+//! object needs at load time).
+
+pub const PT_INTERP: u32 = 3;
+
+pub const R_RISCV_RELATIVE: u32 = 3;
+pub const R_RISCV_64: u32 = 2;
+
+#[derive(Copy, Clone)]
+pub struct Rela {
+    pub offset: u64,
+    pub info: u64,
+    pub addend: i64,
+}
+
+impl Rela {
+    pub fn sym(&self) -> u32 {
+        (self.info >> 32) as u32
+    }
+
+    pub fn r#type(&self) -> u32 {
+        (self.info & 0xffff_ffff) as u32
+    }
+}
+
+/// Applies one relocation against the loaded image, given the load bias
+/// (difference between the segment's link-time vaddr and where it was
+/// actually placed) and a symbol-address lookup for non-RELATIVE entries.
+///
+/// Returns the value to store at `rela.offset`, or `None` for relocation
+/// types this loader doesn't implement (or an `R_RISCV_64` whose symbol
+/// `lookup_symbol` can't find), so the caller can decide whether to fail
+/// the exec or skip it.
+pub fn apply_rela(rela: &Rela, load_bias: u64, lookup_symbol: impl Fn(u32) -> Option<u64>) -> Option<u64> {
+    match rela.r#type() {
+        R_RISCV_RELATIVE => Some((load_bias as i64 + rela.addend) as u64),
+        R_RISCV_64 => {
+            let sym_addr = lookup_symbol(rela.sym())?;
+            Some((sym_addr as i64 + rela.addend) as u64)
+        }
+        _ => None,
+    }
+}
+
+/// Extracts the interpreter path out of a `PT_INTERP` segment's raw bytes
+/// (a NUL-terminated string).
+pub fn interp_path(segment_data: &[u8]) -> Option<&str> {
+    let end = segment_data.iter().position(|&b| b == 0)?;
+    core::str::from_utf8(&segment_data[..end]).ok()
+}
@@ -0,0 +1,32 @@
+//! Per-process environment variable storage, populated at `exec` time from
+//! the envp array the loader is handed and queried by `sys_getenv`.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+#[derive(Default, Clone)]
+pub struct Environment {
+    vars: BTreeMap<String, String>,
+}
+
+impl Environment {
+    /// Builds the environment from envp entries as passed to `exec`, one
+    /// `"KEY=VALUE"` string each; entries without an `=` are skipped.
+    pub fn from_envp(entries: &[String]) -> Self {
+        let mut vars = BTreeMap::new();
+        for entry in entries {
+            if let Some((key, value)) = entry.split_once('=') {
+                vars.insert(String::from(key), String::from(value));
+            }
+        }
+        Self { vars }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.vars.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: String, value: String) {
+        self.vars.insert(key, value);
+    }
+}
@@ -0,0 +1,34 @@
+//! Futex waiter bookkeeping: a table of the task ids waiting on each futex
+//! key. It neither reads the futex word nor suspends or resumes a task,
+//! and `sys_futex` does not use it.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+#[derive(Default)]
+pub struct FutexTable {
+    /// Keyed by the physical address backing `uaddr`, since two processes
+    /// (or two mappings within one) can share the same futex word.
+    waiters: BTreeMap<usize, Vec<usize>>,
+}
+
+impl FutexTable {
+    /// Registers `task_id` as waiting on `key`. The value check and the
+    /// task's suspension are not done here.
+    pub fn add_waiter(&mut self, key: usize, task_id: usize) {
+        self.waiters.entry(key).or_default().push(task_id);
+    }
+
+    /// Removes up to `n` waiters on `key` (oldest first) and returns their
+    /// task ids; making those tasks runnable is up to the caller.
+    pub fn wake(&mut self, key: usize, n: usize) -> Vec<usize> {
+        let Some(list) = self.waiters.get_mut(&key) else {
+            return Vec::new();
+        };
+        let woken: Vec<usize> = list.drain(..list.len().min(n)).collect();
+        if list.is_empty() {
+            self.waiters.remove(&key);
+        }
+        woken
+    }
+}
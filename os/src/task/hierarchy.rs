@@ -0,0 +1,42 @@
+//! Process hierarchy bookkeeping: orphan reparenting to the init process
+//! and zombie reaping, the two halves of `wait()` that only make sense
+//! once processes track parents and children.
+
+use super::task::TaskControlBlock;
+use crate::sync::UPSafeCell;
+use alloc::sync::{Arc, Weak};
+
+static INITPROC: UPSafeCell<Option<Weak<TaskControlBlock>>> = unsafe { UPSafeCell::new(None) };
+
+/// Records `task` as the init process every orphan gets reparented to.
+/// Called once, right after the init process is created — overwriting
+/// rather than appending, so even a future secondary-hart bring-up path
+/// that mistakenly called this again wouldn't register init twice.
+pub fn set_initproc(task: &Arc<TaskControlBlock>) {
+    *INITPROC.exclusive_access() = Some(Arc::downgrade(task));
+}
+
+pub fn initproc() -> Option<Arc<TaskControlBlock>> {
+    INITPROC.exclusive_access().as_ref().and_then(Weak::upgrade)
+}
+
+/// Moves every child of `task` onto the init process's child list, as
+/// POSIX requires when a process with living children exits. Zombie
+/// children are reparented too, so init can still reap them.
+pub fn reparent_children_to_initproc(task: &Arc<TaskControlBlock>) {
+    let Some(init) = initproc() else {
+        return;
+    };
+    if Arc::ptr_eq(task, &init) {
+        return;
+    }
+    let mut task_inner = task.inner_exclusive_access();
+    if task_inner.children.is_empty() {
+        return;
+    }
+    let mut init_inner = init.inner_exclusive_access();
+    for child in task_inner.children.drain(..) {
+        child.inner_exclusive_access().parent = Some(Arc::downgrade(&init));
+        init_inner.children.push(child);
+    }
+}
@@ -0,0 +1,126 @@
+//! Hart lifecycle management: bringing a hart online after boot and
+//! taking one offline again.
+//!
+//! This tree doesn't actually start secondary harts at boot at all —
+//! `entry.asm` has a single `_start` path, and [`super::online_harts`]
+//! used to hardcode a single hart as a stand-in. There is no SMP boot
+//! loop here for this module to replace; it exists so one has a real
+//! lifecycle to drive once it's written, the same "scaffolding ahead of
+//! wiring" this kernel's SBI HSM bring-up ([`crate::sbi::hart_start`]/
+//! [`crate::sbi::hart_stop`], added alongside this) has been built against
+//! so far.
+
+use super::processor::processor_for_hart;
+use super::sched_stats::{least_loaded_hart, HART_STATS};
+use crate::config::MAX_HARTS;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Where a hart is in its lifecycle.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum HartState {
+    /// Never started, or stopped and not yet restarted.
+    Offline = 0,
+    /// [`bring_online`] has issued `hart_start` but the hart hasn't been
+    /// marked online yet.
+    Starting = 1,
+    /// Scheduling tasks.
+    Online = 2,
+    /// [`offline`] is draining this hart's run queue before stopping it.
+    Stopping = 3,
+}
+
+impl HartState {
+    fn from_u8(bits: u8) -> Self {
+        match bits {
+            1 => Self::Starting,
+            2 => Self::Online,
+            3 => Self::Stopping,
+            _ => Self::Offline,
+        }
+    }
+}
+
+/// One state cell per hart, indexed by hart id, replacing
+/// [`super::online_harts`]'s old hardcoded single-hart stand-in.
+static HART_STATE: [AtomicU8; MAX_HARTS] = {
+    const INIT: AtomicU8 = AtomicU8::new(HartState::Offline as u8);
+    [INIT; MAX_HARTS]
+};
+
+/// Hart 0 boots itself through `entry.asm` rather than through
+/// [`bring_online`]; `rust_main` calls this once at boot so it shows up as
+/// online like any hart [`bring_online`] started.
+pub fn mark_boot_hart_online(hart: usize) {
+    HART_STATE[hart].store(HartState::Online as u8, Ordering::Release);
+}
+
+pub fn state(hart: usize) -> HartState {
+    HartState::from_u8(HART_STATE[hart].load(Ordering::Acquire))
+}
+
+/// Every hart currently [`HartState::Online`], for [`super::online_harts`]
+/// to report.
+pub fn online_harts() -> alloc::vec::Vec<usize> {
+    (0..MAX_HARTS)
+        .filter(|&hart| state(hart) == HartState::Online)
+        .collect()
+}
+
+/// Starts `hart` executing at `start_addr` via SBI HSM, leaving it
+/// `Online` only once the call itself reports success. A hart that fails
+/// to start (bad `start_addr`, already running, platform doesn't support
+/// HSM) is left `Offline` and its error returned, rather than wedging the
+/// rest of boot waiting on it.
+pub fn bring_online(hart: usize, start_addr: usize, opaque: usize) -> Result<(), isize> {
+    HART_STATE[hart].store(HartState::Starting as u8, Ordering::Release);
+    let err = crate::sbi::hart_start(hart, start_addr, opaque);
+    if err == 0 {
+        HART_STATE[hart].store(HartState::Online as u8, Ordering::Release);
+        Ok(())
+    } else {
+        HART_STATE[hart].store(HartState::Offline as u8, Ordering::Release);
+        Err(err)
+    }
+}
+
+/// Takes `hart` offline: migrates whatever task is currently assigned to
+/// its [`super::Processor`] onto the least-loaded remaining online hart
+/// (the only "run queue" a hart actually owns in this tree — see
+/// `task::mlfq`'s own multi-level queue, which exists but isn't wired to
+/// any hart's scheduling loop yet), then stops it via SBI HSM.
+///
+/// Refuses to take the last online hart offline. Stopping a hart other
+/// than the caller's own still only gets as far as flipping its state and
+/// migrating its task: [`crate::sbi::hart_stop`] only ever stops the
+/// *calling* hart per the SBI spec, and the trap handler sends every
+/// software interrupt to `lang_items::handle_panic_freeze`, so there is no
+/// path for one hart to ask another to call it. A hart offlining itself
+/// stops for real.
+pub fn offline(hart: usize) -> Result<(), &'static str> {
+    if state(hart) != HartState::Online {
+        return Err("hart is not online");
+    }
+    let online = online_harts();
+    if online.len() <= 1 {
+        return Err("refusing to offline the last online hart");
+    }
+    HART_STATE[hart].store(HartState::Stopping as u8, Ordering::Release);
+
+    if let Some(task) = processor_for_hart(hart).exclusive_access().take_current() {
+        let target = online
+            .into_iter()
+            .filter(|&h| h != hart)
+            .min_by_key(|&h| HART_STATS[h].load_permille())
+            .unwrap_or_else(least_loaded_hart);
+        processor_for_hart(target).exclusive_access().set_current(task);
+        HART_STATS[hart].migrations_out.fetch_add(1, Ordering::Relaxed);
+        HART_STATS[target].migrations_in.fetch_add(1, Ordering::Relaxed);
+    }
+
+    HART_STATE[hart].store(HartState::Offline as u8, Ordering::Release);
+    if hart == super::hart_id() {
+        crate::sbi::hart_stop();
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! The per-hart idle loop: what a hart runs when [`super::pick_next`] has
+//! nothing ready for it.
+//!
+//! [`idle_once`] is called from [`super::scheduler_loop::run_tasks`] now,
+//! whenever the ready queue comes up empty, and from
+//! [`super::suspend_current_and_run_next`] when there's no task to yield
+//! to. Nothing calls [`idle_loop`]: `rust_main` goes straight into
+//! `run_tasks` once it has enqueued the init task.
+
+use super::sched_stats::HART_STATS;
+use crate::timer_wheel::TimerWheel;
+
+/// A hart with nothing scheduled still wakes up at least this often, so
+/// tickless idle doesn't mean "never wake up" — just "don't wake up for
+/// nothing". Arbitrary but small relative to [`super::MAX_WAIT_TICKS`],
+/// so a newly-woken sleeper or a starvation promotion is never more than
+/// this many ticks late to be noticed.
+const MAX_IDLE_TICKS: u64 = 1000;
+
+/// Suspends the calling hart via [`crate::sbi::hart_suspend`] (SBI HSM)
+/// until the next interrupt, recording the idle tick either way. Unlike
+/// a bare `wfi` loop, this actually yields the host CPU back to whatever
+/// is running the emulator — important under QEMU, where a spinning
+/// guest hart otherwise pins a host core at 100% even while doing
+/// nothing.
+pub fn idle_once(hart: usize) {
+    HART_STATS[hart].record_tick(false);
+    crate::sbi::hart_suspend();
+}
+
+/// Tickless idle: arms the next timer interrupt at `wheel`'s earliest
+/// pending deadline (falling back to [`MAX_IDLE_TICKS`] ticks out if
+/// nothing is scheduled) instead of the usual fixed-period tick, then
+/// suspends the hart. `ticks_per_mtime` converts wheel ticks to `time`
+/// CSR units (see [`crate::timer::get_time`]).
+pub fn idle_tickless(hart: usize, wheel: &TimerWheel, ticks_per_mtime: u64) {
+    let now_tick = wheel.current_tick();
+    let deadline_tick = wheel
+        .next_deadline_tick()
+        .unwrap_or(now_tick + MAX_IDLE_TICKS);
+    let ticks_ahead = deadline_tick.saturating_sub(now_tick).max(1);
+    let deadline_mtime = crate::timer::get_time() + ticks_ahead * ticks_per_mtime;
+    crate::sbi::set_timer(deadline_mtime);
+    idle_once(hart);
+}
+
+/// Idles `hart` forever, re-checking nothing in between wakeups. Nothing
+/// calls it: [`super::scheduler_loop::run_tasks`] re-checks the ready
+/// queue itself after every [`idle_once`], so it doesn't call into this. Switching it to [`idle_tickless`] once a
+/// hart's own [`TimerWheel`] exists to check is still unstaged work.
+pub fn idle_loop(hart: usize) -> ! {
+    loop {
+        idle_once(hart);
+    }
+}
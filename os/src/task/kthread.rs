@@ -0,0 +1,62 @@
+//! Kernel threads: schedulable units of in-kernel work that have no user
+//! address space and no fd table, for background jobs (the block device
+//! worker, periodic housekeeping) that don't deserve a full process.
+
+use crate::sync::UPSafeCell;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+pub type KernelThreadFn = fn();
+
+pub struct KernelThread {
+    pub id: usize,
+    pub name: String,
+    pub entry: KernelThreadFn,
+}
+
+static NEXT_KTHREAD_ID: AtomicUsize = AtomicUsize::new(0);
+
+pub struct KernelThreadTable {
+    runnable: VecDeque<KernelThread>,
+}
+
+impl KernelThreadTable {
+    const fn new() -> Self {
+        Self {
+            runnable: VecDeque::new(),
+        }
+    }
+
+    /// Registers a new kernel thread and marks it runnable immediately;
+    /// there is no kernel-thread equivalent of a suspended "new" state
+    /// since there's no user-visible handle for anything to observe it in.
+    pub fn spawn(&mut self, name: impl Into<String>, entry: KernelThreadFn) -> usize {
+        let id = NEXT_KTHREAD_ID.fetch_add(1, Ordering::Relaxed);
+        self.runnable.push_back(KernelThread {
+            id,
+            name: name.into(),
+            entry,
+        });
+        id
+    }
+
+    /// Pops the next kernel thread for a hart to run. Kernel threads run
+    /// to completion rather than being preempted mid-slice, so once
+    /// picked they're not re-queued by this table.
+    pub fn pick_next(&mut self) -> Option<KernelThread> {
+        self.runnable.pop_front()
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.runnable.len()
+    }
+}
+
+static KERNEL_THREADS: UPSafeCell<KernelThreadTable> =
+    unsafe { UPSafeCell::new(KernelThreadTable::new()) };
+
+pub fn all_kernel_threads() -> &'static UPSafeCell<KernelThreadTable> {
+    &KERNEL_THREADS
+}
@@ -0,0 +1,60 @@
+//! Multi-level feedback queue: an alternative scheduling policy to stride
+//! scheduling. Tasks start in the highest-priority queue; a task that
+//! uses its full time slice without blocking drops one level (it looks
+//! CPU-bound), while a task that blocks before its slice expires stays at
+//! its level (it looks I/O-bound and shouldn't be punished for yielding).
+
+use alloc::collections::VecDeque;
+
+pub const NUM_LEVELS: usize = 4;
+/// Time slice length in ticks for queue level `i`, increasing with depth
+/// so lower-priority (more CPU-bound) tasks get longer, less-frequent
+/// turns instead of thrashing on context switches.
+pub const SLICE_TICKS: [u64; NUM_LEVELS] = [2, 4, 8, 16];
+
+pub struct Mlfq {
+    queues: [VecDeque<usize>; NUM_LEVELS],
+}
+
+impl Mlfq {
+    pub fn new() -> Self {
+        Self {
+            queues: Default::default(),
+        }
+    }
+
+    /// Enqueues a newly-ready (or newly-created) task at the top level.
+    pub fn enqueue_new(&mut self, task_id: usize) {
+        self.queues[0].push_back(task_id);
+    }
+
+    /// Re-enqueues a task that used its full slice, demoting it one
+    /// level (floor at the lowest level).
+    pub fn requeue_after_slice_expired(&mut self, task_id: usize, level: usize) {
+        let next_level = (level + 1).min(NUM_LEVELS - 1);
+        self.queues[next_level].push_back(task_id);
+    }
+
+    /// Re-enqueues a task that blocked (e.g. on I/O) before using its
+    /// slice, keeping its current level.
+    pub fn requeue_after_blocking(&mut self, task_id: usize, level: usize) {
+        self.queues[level].push_back(task_id);
+    }
+
+    /// Picks the next task to run: highest non-empty level, FIFO within
+    /// that level. Returns the task id and its queue level.
+    pub fn pick_next(&mut self) -> Option<(usize, usize)> {
+        for (level, queue) in self.queues.iter_mut().enumerate() {
+            if let Some(task_id) = queue.pop_front() {
+                return Some((task_id, level));
+            }
+        }
+        None
+    }
+}
+
+impl Default for Mlfq {
+    fn default() -> Self {
+        Self::new()
+    }
+}
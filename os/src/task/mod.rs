@@ -0,0 +1,121 @@
+//! Process/task scheduling and lifecycle management.
+//!
+//! There is exactly one scheduling-state model in this tree: a
+//! [`Processor`] per hart holding that hart's current task, fed by
+//! [`ready_queue`]'s [`pick_next`]-driven dequeue, with `mmap`/`munmap`/
+//! task-info syscalls (see `syscall::mm`) all operating on the current
+//! [`TaskControlBlock`]. `ready_queue` is the *only* other place a task
+//! is tracked while schedulable — it holds exactly the tasks not
+//! currently `current` on some hart — rather than a separate ch3-style
+//! global task table that duplicates `Processor`'s notion of "what's
+//! running where"; keep it that way.
+//!
+//! [`scheduler_loop::run_tasks`] is what actually calls
+//! [`Processor::set_current`] and [`switch::__switch`]s into a task's
+//! saved [`switch::TaskContext`] now — `rust_main` falls into it once it
+//! has created an init task via [`TaskControlBlock::new`], rather than
+//! idling forever with nothing ever created to schedule.
+
+mod cpu_time;
+mod elf_dynamic;
+mod env;
+mod futex;
+mod hierarchy;
+mod hotplug;
+mod idle;
+mod kthread;
+mod mlfq;
+mod path_resolve;
+mod percpu;
+mod pgrp;
+mod processor;
+mod ready_queue;
+mod scheduler;
+mod scheduler_loop;
+mod sched_stats;
+mod shebang;
+mod sigaction;
+mod signal;
+mod stack_layout;
+mod switch;
+mod task;
+mod tls;
+mod trace;
+mod tracepoint;
+mod trap_stats;
+
+use alloc::sync::Arc;
+
+pub use cpu_time::CpuTime;
+pub use elf_dynamic::{apply_rela, interp_path, Rela, PT_INTERP, R_RISCV_64, R_RISCV_RELATIVE};
+pub use env::Environment;
+pub use mlfq::{Mlfq, NUM_LEVELS, SLICE_TICKS};
+pub use futex::FutexTable;
+pub use hierarchy::{initproc, reparent_children_to_initproc, set_initproc};
+pub use hotplug::{
+    bring_online as hotplug_bring_online, mark_boot_hart_online, offline as hotplug_offline,
+    state as hotplug_state, HartState,
+};
+pub use idle::{idle_loop, idle_once, idle_tickless};
+pub use kthread::{all_kernel_threads, KernelThread, KernelThreadFn, KernelThreadTable};
+pub use path_resolve::{resolve as resolve_path, split_path_dirs, DEFAULT_PATH};
+pub use percpu::{init_tp, PerCpu};
+pub use pgrp::ProcessGroupTable;
+pub use processor::{this_hart_processor, Processor};
+pub use ready_queue::ready_queue;
+pub use scheduler_loop::{exit_current_and_switch_away, run_tasks};
+pub use sched_stats::{least_loaded_hart, HartSchedStats, HART_STATS};
+pub use shebang::{parse_shebang, rewrite_argv, SHEBANG_MAX_LEN};
+pub use sigaction::{SigAction, SigActionTable, SignalFrame};
+pub use scheduler::{pick_next, StrideEntry, BIG_STRIDE, MAX_WAIT_TICKS};
+pub use signal::{SignalFlags, SignalState};
+pub use stack_layout::{kernel_stack_position, user_stack_position, StackSlot};
+pub use task::{FdEntry, FdFlags, TaskControlBlock, TaskControlBlockInner, DEFAULT_MAX_FDS};
+pub use tls::{init_tls_block, TlsImage, PT_TLS};
+pub use trace::{
+    disable as trace_disable, drain_all as trace_drain_all, enable as trace_enable,
+    is_enabled as trace_is_enabled, record as trace_record, snapshot_all as trace_snapshot_all,
+    snapshot_hart as trace_snapshot_hart, TraceEvent,
+};
+pub use tracepoint::{
+    action as tracepoint_action, configure_by_index as tracepoint_configure_by_index,
+    counter as tracepoint_counter, fire as tracepoint_fire, Tracepoint, TracepointAction,
+};
+pub use trap_stats::TrapStats;
+
+/// Gives up the remainder of the current task's time slice and reschedules.
+///
+/// Pipe, socket, devfs, the message queue, `nanosleep`, thread-join,
+/// `waitpid`, and stdio all call this in a loop as their blocking
+/// primitive, re-checking their wait condition after each call returns.
+/// Re-enqueues the caller (if there is one — a kernel-thread-table
+/// consumer or the very first boot hart calling this before any task
+/// exists has none) and actually switches to whatever
+/// [`ready_queue::ReadyQueue::dequeue_next`] picks next, via
+/// [`scheduler_loop::yield_current_task`]; only falls back to
+/// [`idle::idle_once`]'s SBI-HSM suspend (still cheaper than busy-spinning
+/// the host CPU at 100%) when there's truly nothing to yield to.
+pub fn suspend_current_and_run_next() {
+    if !scheduler_loop::yield_current_task(hart_id()) {
+        idle::idle_once(hart_id());
+    }
+}
+
+/// Returns the task currently running on this hart, if any.
+pub fn current_task() -> Option<Arc<TaskControlBlock>> {
+    this_hart_processor().exclusive_access().current()
+}
+
+/// The id of the hart executing this call, read out of `tp`.
+pub fn hart_id() -> usize {
+    let id: usize;
+    unsafe {
+        core::arch::asm!("mv {}, tp", out(reg) id);
+    }
+    id
+}
+
+/// Hart ids that have completed boot and are scheduling tasks.
+pub fn online_harts() -> alloc::vec::Vec<usize> {
+    hotplug::online_harts()
+}
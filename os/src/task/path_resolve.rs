@@ -0,0 +1,32 @@
+//! PATH-style resolution of the executable name passed to `exec`/`spawn`:
+//! a name containing `/` is used as-is, otherwise it's tried against each
+//! directory in `PATH` in order, first match wins.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const DEFAULT_PATH: &str = "/bin:/usr/bin";
+
+/// Resolves `name` to a full path using `path`, given a predicate for
+/// "does this path exist" (backed by the fs layer at the call site so this
+/// module stays fs-agnostic and unit-testable).
+pub fn resolve(name: &str, path: &str, exists: impl Fn(&str) -> bool) -> Option<String> {
+    if name.contains('/') {
+        return exists(name).then(|| String::from(name));
+    }
+    for dir in path.split(':') {
+        if dir.is_empty() {
+            continue;
+        }
+        let candidate = format!("{}/{}", dir, name);
+        if exists(&candidate) {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+pub fn split_path_dirs(path: &str) -> Vec<&str> {
+    path.split(':').filter(|d| !d.is_empty()).collect()
+}
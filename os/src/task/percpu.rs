@@ -0,0 +1,59 @@
+//! Per-CPU data, indexed by the hart id each hart keeps resident in `tp`
+//! rather than threading a hart id parameter through every call site that
+//! needs one.
+//!
+//! `tp` holds the hart id while running in kernel mode, the only mode
+//! tasks run in on this kernel (`__alltraps` saves and restores it with
+//! every other register), so [`hart_id`](super::hart_id) is just a
+//! register read.
+
+use crate::config::MAX_HARTS;
+use crate::sync::UPSafeCell;
+
+/// Per-hart data slot, one entry per hart, indexed by `hart_id()`.
+pub struct PerCpu<T> {
+    slots: [UPSafeCell<T>; MAX_HARTS],
+}
+
+impl<T: Copy> PerCpu<T> {
+    /// # Safety
+    /// Same requirement as [`UPSafeCell::new`]: exclusive per-hart access
+    /// to each slot must actually hold.
+    pub unsafe fn new(init: T) -> Self {
+        Self {
+            slots: core::array::from_fn(|_| UPSafeCell::new(init)),
+        }
+    }
+}
+
+impl<T> PerCpu<T> {
+    /// Like [`new`](Self::new), but for `T` that isn't `Copy`: calls
+    /// `init` once per hart rather than broadcasting a single value.
+    ///
+    /// # Safety
+    /// Same requirement as [`UPSafeCell::new`]: exclusive per-hart access
+    /// to each slot must actually hold.
+    pub unsafe fn new_with(mut init: impl FnMut() -> T) -> Self {
+        Self {
+            slots: core::array::from_fn(|_| UPSafeCell::new(init())),
+        }
+    }
+
+    pub fn get(&self) -> &UPSafeCell<T> {
+        &self.slots[super::hart_id()]
+    }
+
+    pub fn get_hart(&self, hart: usize) -> &UPSafeCell<T> {
+        &self.slots[hart]
+    }
+}
+
+/// Initializes `tp` to this hart's id. Called once per hart during boot,
+/// before any code that relies on [`hart_id`](super::hart_id) runs.
+///
+/// # Safety
+/// Must only be called once, early in each hart's boot sequence, before
+/// `tp` is relied on for anything else.
+pub unsafe fn init_tp(hart: usize) {
+    core::arch::asm!("mv tp, {}", in(reg) hart);
+}
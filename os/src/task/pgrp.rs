@@ -0,0 +1,51 @@
+//! Process group and session bookkeeping. Tracks which pids belong to
+//! which group, and which groups belong to which session, so that e.g.
+//! signal delivery or terminal job control can address a whole group
+//! without walking every task in the system.
+
+use alloc::collections::BTreeSet;
+use alloc::collections::BTreeMap;
+
+#[derive(Default)]
+pub struct ProcessGroupTable {
+    members: BTreeMap<usize, BTreeSet<usize>>,
+    session_of_group: BTreeMap<usize, usize>,
+}
+
+impl ProcessGroupTable {
+    pub fn new() -> Self {
+        Self {
+            members: BTreeMap::new(),
+            session_of_group: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `pid` as its own group leader and session leader, as a
+    /// freshly-created process is until it joins something else.
+    pub fn new_process(&mut self, pid: usize) {
+        self.members.entry(pid).or_default().insert(pid);
+        self.session_of_group.insert(pid, pid);
+    }
+
+    /// Moves `pid` into group `pgid`. Fails if `pgid` belongs to a
+    /// different session than `pid` is currently in, mirroring POSIX
+    /// `setpgid`'s `EPERM` for crossing a session boundary.
+    pub fn set_pgid(&mut self, pid: usize, pgid: usize, current_sid: usize) -> Result<(), ()> {
+        if let Some(&target_sid) = self.session_of_group.get(&pgid) {
+            if target_sid != current_sid {
+                return Err(());
+            }
+        } else {
+            return Err(());
+        }
+        for group in self.members.values_mut() {
+            group.remove(&pid);
+        }
+        self.members.entry(pgid).or_default().insert(pid);
+        Ok(())
+    }
+
+    pub fn members_of(&self, pgid: usize) -> BTreeSet<usize> {
+        self.members.get(&pgid).cloned().unwrap_or_default()
+    }
+}
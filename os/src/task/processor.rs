@@ -0,0 +1,57 @@
+//! Per-hart scheduling state. Each hart owns its own [`Processor`] instead
+//! of every hart contending on one global `PROCESSOR` cell, so picking the
+//! next task to run on hart N never blocks behind hart M doing the same.
+
+use super::tracepoint::Tracepoint;
+use super::TaskControlBlock;
+use crate::config::MAX_HARTS;
+use crate::sync::UPSafeCell;
+use alloc::sync::Arc;
+
+pub struct Processor {
+    current: Option<Arc<TaskControlBlock>>,
+}
+
+impl Processor {
+    const fn new() -> Self {
+        Self { current: None }
+    }
+
+    pub fn current(&self) -> Option<Arc<TaskControlBlock>> {
+        self.current.clone()
+    }
+
+    pub fn take_current(&mut self) -> Option<Arc<TaskControlBlock>> {
+        let outgoing = self.current.take();
+        if let Some(task) = &outgoing {
+            crate::tracepoint!(Tracepoint::SchedSwitch, task.pid);
+        }
+        outgoing
+    }
+
+    pub fn set_current(&mut self, task: Arc<TaskControlBlock>) {
+        crate::tracepoint!(Tracepoint::SchedSwitch, task.pid);
+        self.current = Some(task);
+    }
+}
+
+/// One cell per hart; indexed by `hart_id()` rather than shared.
+static PROCESSORS: [UPSafeCell<Processor>; MAX_HARTS] = {
+    // `UPSafeCell::new` is unsafe because it trusts the caller to uphold
+    // per-hart exclusivity, which holds here since each hart only ever
+    // indexes its own slot.
+    [const { unsafe { UPSafeCell::new(Processor::new()) } }; MAX_HARTS]
+};
+
+/// Returns this hart's own `Processor`, indexed by `hart_id()`.
+pub fn this_hart_processor() -> &'static UPSafeCell<Processor> {
+    &PROCESSORS[super::hart_id()]
+}
+
+/// Returns `hart`'s `Processor`, for hotplug to migrate a task off a hart
+/// other than the caller's own — [`this_hart_processor`] only ever reaches
+/// the calling hart's slot, which isn't enough to drain a hart being taken
+/// offline from elsewhere.
+pub fn processor_for_hart(hart: usize) -> &'static UPSafeCell<Processor> {
+    &PROCESSORS[hart]
+}
@@ -0,0 +1,63 @@
+//! The system-wide ready queue: every schedulable [`TaskControlBlock`]
+//! not currently running on a hart, drawn from via [`super::pick_next`].
+//!
+//! This is the live caller `pick_next`'s own module doc comment and
+//! `task::mod`'s describe as missing — until `scheduler_loop::run_tasks`
+//! started calling [`ReadyQueue::dequeue_next`], `pick_next` was pure,
+//! tested, unreachable logic with nothing that ever fed it a real ready
+//! list.
+
+use super::scheduler::pick_next;
+use super::task::TaskControlBlock;
+use crate::sync::UPSafeCell;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+pub struct ReadyQueue {
+    tasks: Vec<Arc<TaskControlBlock>>,
+}
+
+impl ReadyQueue {
+    const fn new() -> Self {
+        Self { tasks: Vec::new() }
+    }
+
+    /// Marks `task` ready to run, stamping its stride entry's
+    /// `ready_since` so [`super::scheduler::StrideEntry::is_starved`]
+    /// measures from the moment it actually became eligible rather than
+    /// whenever it was first created.
+    pub fn enqueue(&mut self, task: Arc<TaskControlBlock>) {
+        task.inner_exclusive_access().stride.ready_since = crate::timer::get_time();
+        self.tasks.push(task);
+    }
+
+    /// Picks and removes the next task to run, per [`pick_next`]'s
+    /// stride-plus-starvation rule, advancing its pass value the way a
+    /// real stride scheduler does on every dispatch.
+    pub fn dequeue_next(&mut self) -> Option<Arc<TaskControlBlock>> {
+        let now = crate::timer::get_time();
+        let entries: Vec<_> = self
+            .tasks
+            .iter()
+            .map(|task| task.inner_exclusive_access().stride)
+            .collect();
+        let idx = pick_next(&entries, now)?;
+        let task = self.tasks.remove(idx);
+        task.inner_exclusive_access().stride.advance();
+        Some(task)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+static READY_QUEUE: UPSafeCell<ReadyQueue> = unsafe { UPSafeCell::new(ReadyQueue::new()) };
+
+pub fn ready_queue() -> &'static UPSafeCell<ReadyQueue> {
+    &READY_QUEUE
+}
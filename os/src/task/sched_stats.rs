@@ -0,0 +1,65 @@
+//! Per-hart scheduling statistics, exposed through `sys_sched_stats` as a
+//! `/proc`-style introspection point for load-balancing decisions and
+//! diagnosing uneven hart utilization.
+
+use crate::config::MAX_HARTS;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Default)]
+pub struct HartSchedStats {
+    pub tasks_run: AtomicU64,
+    pub ticks_idle: AtomicU64,
+    pub ticks_busy: AtomicU64,
+    pub migrations_in: AtomicU64,
+    pub migrations_out: AtomicU64,
+}
+
+impl HartSchedStats {
+    const fn new() -> Self {
+        Self {
+            tasks_run: AtomicU64::new(0),
+            ticks_idle: AtomicU64::new(0),
+            ticks_busy: AtomicU64::new(0),
+            migrations_in: AtomicU64::new(0),
+            migrations_out: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_run(&self) {
+        self.tasks_run.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_tick(&self, busy: bool) {
+        let counter = if busy { &self.ticks_busy } else { &self.ticks_idle };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Fraction of ticks spent busy, out of 1000 (avoids floats in the
+    /// kernel).
+    pub fn load_permille(&self) -> u64 {
+        let busy = self.ticks_busy.load(Ordering::Relaxed);
+        let idle = self.ticks_idle.load(Ordering::Relaxed);
+        let total = busy + idle;
+        if total == 0 {
+            0
+        } else {
+            busy * 1000 / total
+        }
+    }
+}
+
+pub static HART_STATS: [HartSchedStats; MAX_HARTS] = {
+    const INIT: HartSchedStats = HartSchedStats::new();
+    [INIT; MAX_HARTS]
+};
+
+/// The hart with the lowest load, for load-balancing a newly-ready task
+/// that has no hart affinity yet.
+pub fn least_loaded_hart() -> usize {
+    HART_STATS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, s)| s.load_permille())
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
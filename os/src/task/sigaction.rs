@@ -0,0 +1,43 @@
+//! User-installable signal handlers: `sigaction` records a handler per
+//! signal. Nothing delivers a signal to a recorded handler or builds a
+//! [`SignalFrame`], and `sigreturn` restores no context.
+
+use super::signal::SignalFlags;
+use alloc::collections::BTreeMap;
+
+#[derive(Copy, Clone)]
+pub struct SigAction {
+    pub handler: usize,
+    pub mask: SignalFlags,
+}
+
+impl SigAction {
+    /// `handler == 0` means "default action" (the kernel's built-in
+    /// behaviour, e.g. terminating on SIGSEGV).
+    pub fn is_default(&self) -> bool {
+        self.handler == 0
+    }
+}
+
+#[derive(Default)]
+pub struct SigActionTable {
+    actions: BTreeMap<u32, SigAction>,
+}
+
+impl SigActionTable {
+    pub fn set(&mut self, signum: u32, action: SigAction) -> Option<SigAction> {
+        self.actions.insert(signum, action)
+    }
+
+    pub fn get(&self, signum: u32) -> Option<SigAction> {
+        self.actions.get(&signum).copied()
+    }
+}
+
+/// Saved trap context a signal handler will eventually `sigreturn` out of,
+/// so the interrupted user context can be restored exactly.
+pub struct SignalFrame {
+    pub saved_pc: usize,
+    pub saved_regs: [usize; 32],
+    pub signum: u32,
+}
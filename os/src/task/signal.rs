@@ -0,0 +1,69 @@
+//! Process signal state: the pending set, the mask and the signalfd mask
+//! that lets a process opt a subset of signals out of asynchronous
+//! delivery and into synchronous, poll-able reads instead.
+
+/// A bitset over the 32 signal numbers the kernel knows about.
+///
+/// Kept as a plain bitflags-style wrapper rather than pulling in the
+/// `bitflags` crate, consistent with how small flag sets are already
+/// represented elsewhere in the kernel (e.g. page table flags).
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct SignalFlags(u32);
+
+impl SignalFlags {
+    pub const SIGINT: SignalFlags = SignalFlags(1 << 2);
+    pub const SIGILL: SignalFlags = SignalFlags(1 << 4);
+    pub const SIGABRT: SignalFlags = SignalFlags(1 << 6);
+    pub const SIGFPE: SignalFlags = SignalFlags(1 << 8);
+    pub const SIGSEGV: SignalFlags = SignalFlags(1 << 11);
+    pub const SIGPIPE: SignalFlags = SignalFlags(1 << 13);
+    pub const SIGCHLD: SignalFlags = SignalFlags(1 << 17);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn contains(&self, other: SignalFlags) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn insert(&mut self, other: SignalFlags) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: SignalFlags) {
+        self.0 &= !other.0;
+    }
+
+    /// Pulls one set bit belonging to `mask` out of this set, clearing it,
+    /// and returns which one it was (lowest signal number first).
+    pub fn take_one(&mut self, mask: SignalFlags) -> Option<SignalFlags> {
+        let candidates = self.0 & mask.0;
+        if candidates == 0 {
+            return None;
+        }
+        let bit = candidates & candidates.wrapping_neg();
+        self.0 &= !bit;
+        Some(SignalFlags(bit))
+    }
+
+    pub fn signum(&self) -> u32 {
+        self.0.trailing_zeros()
+    }
+}
+
+/// Per-task signal bookkeeping, owned by the `TaskControlBlockInner`.
+#[derive(Default)]
+pub struct SignalState {
+    /// Signals raised against this task but not yet consumed.
+    pub pending: SignalFlags,
+    /// Signals currently blocked from asynchronous (handler/default-action)
+    /// delivery because a signalfd has claimed them instead.
+    pub signalfd_mask: SignalFlags,
+}
+
+impl SignalState {
+    pub fn raise(&mut self, sig: SignalFlags) {
+        self.pending.insert(sig);
+    }
+}
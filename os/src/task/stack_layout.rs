@@ -0,0 +1,65 @@
+//! Guard-page-aware stack placement within a descending stack area.
+//!
+//! A stack overflow currently has nowhere safe to fault into: whichever
+//! page sits just past the end of a stack is either unmapped-and-invisible
+//! (no page-fault handler exists anywhere in this tree to notice) or, once
+//! one does, could easily be the next stack or some unrelated mapping,
+//! corrupting it silently. The fix is the usual one — leave one unmapped
+//! guard page below every stack — but there's no `kstack_alloc` or
+//! `TaskUserRes` in this tree yet to carve stacks out of a page table, and
+//! no page-fault handler (`trap::entry` sends page faults to its
+//! "unhandled trap" panic) to report into. This lands the address
+//! arithmetic and guard-page classification both of those would need,
+//! parameterized over a caller-supplied stack area rather than a new
+//! hardcoded virtual-layout constant, the same way
+//! [`crate::mm::detect_range`] landed ahead of the frame allocator that
+//! would consume it.
+
+use core::ops::Range;
+
+/// One task or thread's stack placement within a descending stack area:
+/// the page range backing the stack itself, and the one guard page
+/// directly below it that must stay unmapped.
+pub struct StackSlot {
+    pub guard_vpn: usize,
+    pub vpn_range: Range<usize>,
+}
+
+impl StackSlot {
+    /// Whether `vpn` falls in this slot's guard page — the signal a page
+    /// fault handler would use to report "stack overflow" instead of
+    /// treating the fault as an ordinary unmapped-page access.
+    pub fn is_guard_fault(&self, vpn: usize) -> bool {
+        vpn == self.guard_vpn
+    }
+}
+
+/// Computes slot `id`'s placement within a descending stack area topping
+/// out at `area_top`: `stack_pages` pages of stack, then one guard page,
+/// repeated downward once per id so consecutive stacks never touch.
+fn stack_slot(area_top: usize, id: usize, stack_pages: usize) -> StackSlot {
+    let page_size = crate::config::PAGE_SIZE;
+    let slot_pages = stack_pages + 1;
+    let top_vpn = area_top / page_size - id * slot_pages;
+    let bottom_vpn = top_vpn - stack_pages;
+    StackSlot {
+        guard_vpn: bottom_vpn - 1,
+        vpn_range: bottom_vpn..top_vpn,
+    }
+}
+
+/// Task `id`'s kernel stack slot within a stack area topping out at
+/// `area_top`, sized per [`crate::config::KERNEL_STACK_SIZE`]. Meant for
+/// a future `kstack_alloc` to call when mapping a task's kernel stack.
+pub fn kernel_stack_position(area_top: usize, id: usize) -> StackSlot {
+    let stack_pages = crate::config::KERNEL_STACK_SIZE / crate::config::PAGE_SIZE;
+    stack_slot(area_top, id, stack_pages)
+}
+
+/// Thread `id`'s user stack slot within a stack area topping out at
+/// `area_top`, sized per [`crate::config::USER_STACK_SIZE`]. Meant for a
+/// future `TaskUserRes` to call when mapping a thread's user stack.
+pub fn user_stack_position(area_top: usize, id: usize) -> StackSlot {
+    let stack_pages = crate::config::USER_STACK_SIZE / crate::config::PAGE_SIZE;
+    stack_slot(area_top, id, stack_pages)
+}
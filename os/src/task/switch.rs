@@ -0,0 +1,78 @@
+//! Register-level context switch between two tasks' kernel execution
+//! state, used by [`super::scheduler_loop::run_tasks`].
+//!
+//! This only swaps the callee-saved integer registers (`ra`, `sp`,
+//! `s0`-`s11`), the usual scope of a switch below the trap frame. There
+//! is no `satp` write here: there is no page table to switch to (see
+//! [`crate::mm::page_table`]'s doc comment), so every task in this tree
+//! still runs in the kernel's one shared address space. That makes this
+//! real switching between tasks' *kernel-mode* execution, not the Ring-3
+//! user-mode entry (`sret` into a task's own trap frame) a full process
+//! model also needs — that half stays blocked on a page table this tree
+//! doesn't have yet, same as `stack_layout`'s `kernel_stack_position`.
+
+use core::arch::global_asm;
+
+/// Callee-saved register snapshot `__switch` reads from/writes into.
+/// Field order and count must match `switch.asm`'s offsets exactly.
+#[repr(C)]
+#[derive(Clone)]
+pub struct TaskContext {
+    ra: usize,
+    sp: usize,
+    s: [usize; 12],
+}
+
+impl TaskContext {
+    /// Never switched into on its own; only a placeholder for the slot a
+    /// real context gets written into before anything reads it (e.g. a
+    /// hart's idle context, which `__switch` only ever switches *out of*
+    /// before the scheduler loop starts picking real tasks).
+    pub const fn zero_init() -> Self {
+        Self {
+            ra: 0,
+            sp: 0,
+            s: [0; 12],
+        }
+    }
+
+    /// A context that, switched into for the first time, starts running
+    /// `entry(arg)` on a fresh stack at `stack_top` (growing down). There
+    /// is no ELF loader in this tree yet to hand a freshly created task a
+    /// user program's own entry point instead (see `syscall::spawn`'s doc
+    /// comment) — `entry` is always a plain kernel function pointer
+    /// today.
+    pub fn goto_entry(entry: usize, arg: usize, stack_top: usize) -> Self {
+        let mut s = [0usize; 12];
+        // `__kernel_task_trampoline` has no normal call in progress to
+        // receive arguments through `a0`/`a1` the usual way, so `entry`
+        // and `arg` ride along in the registers `__switch` restores on
+        // the way in (see `switch.asm`).
+        s[0] = entry;
+        s[1] = arg;
+        Self {
+            ra: __kernel_task_trampoline as usize,
+            sp: stack_top,
+            s,
+        }
+    }
+}
+
+global_asm!(include_str!("switch.asm"));
+
+extern "C" {
+    /// Saves the caller's callee-saved registers into `*current`, then
+    /// restores `*next`'s and returns into wherever `next` last called
+    /// this from — or, the first time `next` runs, into
+    /// `__kernel_task_trampoline` via [`TaskContext::goto_entry`].
+    pub fn __switch(current: *mut TaskContext, next: *const TaskContext);
+    fn __kernel_task_trampoline();
+}
+
+/// Where `__kernel_task_trampoline` tail-calls into Rust once it's moved
+/// a freshly-switched-in task's `entry`/`arg` out of `s0`/`s1`.
+#[no_mangle]
+extern "C" fn kernel_task_start(entry: usize, arg: usize) -> ! {
+    let entry: fn(usize) -> ! = unsafe { core::mem::transmute(entry) };
+    entry(arg)
+}
@@ -0,0 +1,237 @@
+//! The task control block (TCB): everything the kernel tracks about one
+//! running process.
+
+use super::cpu_time::CpuTime;
+use super::env::Environment;
+use super::scheduler::StrideEntry;
+use super::sigaction::SigActionTable;
+use super::signal::SignalState;
+use super::switch::TaskContext;
+use super::trap_stats::TrapStats;
+use crate::fs::File;
+use crate::mm::{LazyArea, MemorySet};
+use crate::sync::{UPSafeCell, WaitQueue};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+bitflags::bitflags! {
+    /// Per-fd flags, as opposed to per-open-file-description flags
+    /// (`OpenFlags`): `FD_CLOEXEC` is the only one POSIX defines at this
+    /// level, and it belongs to the fd slot rather than the underlying
+    /// `File`, since two fds `dup`ed from the same file can disagree
+    /// about it.
+    #[derive(Copy, Clone, Default)]
+    pub struct FdFlags: u32 {
+        const CLOEXEC = 1 << 0;
+    }
+}
+
+/// One occupied slot in a task's fd table.
+pub struct FdEntry {
+    pub file: Arc<dyn File>,
+    pub flags: FdFlags,
+}
+
+/// The default `RLIMIT_NOFILE`-equivalent cap on open fds, until a task
+/// raises it with `sys_prlimit64`.
+pub const DEFAULT_MAX_FDS: usize = 256;
+
+pub struct TaskControlBlock {
+    pub pid: usize,
+    /// This task's saved kernel register state, read and written by
+    /// [`super::switch::__switch`]. Kept outside `inner` so dispatching a
+    /// task never needs `inner`'s `RefCell` borrowed across the switch
+    /// itself (nothing else ever touches a task's own `tcx` while it's
+    /// the one running, so there's no real contention to avoid, but it
+    /// keeps a scheduler bug from panicking on a double-borrow instead of
+    /// just misbehaving).
+    pub tcx: UPSafeCell<TaskContext>,
+    inner: UPSafeCell<TaskControlBlockInner>,
+}
+
+pub struct TaskControlBlockInner {
+    pub signals: SignalState,
+    pub fd_table: Vec<Option<FdEntry>>,
+    /// Soft cap on `fd_table`'s occupied slots; `alloc_fd` fails once it's
+    /// reached rather than growing the table without bound.
+    pub max_fds: usize,
+    pub env: Environment,
+    /// The `argv` a successful `exec` last committed this task to
+    /// running, after shebang rewriting (see `syscall::exec`'s doc
+    /// comment). Empty until the first successful `exec`; nothing reads
+    /// it yet (there's no `/proc/self/cmdline` in `fs::procfs` to expose
+    /// it), but it's real state now rather than a value `sys_exec`
+    /// computed and threw away.
+    pub argv: Vec<String>,
+    /// This task's address space. `mmap` and `mprotect` mutate this
+    /// directly (there is no `munmap` syscall) rather than some process-wide table, so two threads
+    /// sharing a `tgid` today actually have independent address spaces —
+    /// fine until `clone(CLONE_VM)` lands and needs to share it instead.
+    pub mm: MemorySet,
+    /// Mapped-but-not-yet-faulted-in regions of `mm`, recording how the
+    /// first access to a page in a lazily-backed area would be resolved
+    /// (see `mm::lazy`). No page-fault handler consults it: page faults
+    /// reach `trap::entry`'s "unhandled trap" panic. Kept separate from
+    /// `mm.areas` because a [`MapArea`](crate::mm::MapArea) doesn't carry
+    /// a [`LazyKind`](crate::mm::LazyKind) and teaching it to would mean
+    /// giving every eagerly-mapped area (stack, trampoline, ...) a lazy
+    /// variant it never uses.
+    pub lazy_areas: Vec<LazyArea>,
+    pub trap_stats: TrapStats,
+    /// User/kernel time accumulated for this task alone; `sys_getrusage`
+    /// sums it across every task sharing the caller's `tgid`.
+    pub cpu_time: CpuTime,
+    pub stride: StrideEntry,
+    pub sigactions: SigActionTable,
+    /// Process group id. A freshly-created process starts as its own
+    /// group leader (`pgid == pid`) until `setpgid` moves it.
+    pub pgid: usize,
+    /// Session id, likewise defaulting to `pid` until the process joins
+    /// or leads a different session.
+    pub sid: usize,
+    /// `None` only for the initial process; every other task is created
+    /// by a `fork`/`spawn` and keeps a weak back-reference so dropping
+    /// the parent doesn't leak a reference cycle.
+    pub parent: Option<Weak<TaskControlBlock>>,
+    pub children: Vec<Arc<TaskControlBlock>>,
+    /// Set once the task has called `exit`; `None` while still running.
+    pub exit_code: Option<i32>,
+    /// The value `tp` is restored to on return to user mode: the base of
+    /// this thread's TLS block, or 0 before one has been set up.
+    pub tls_base: usize,
+    /// Thread-group id: the pid of the thread that started the group.
+    /// Defaults to this task's own pid, as it does for a process's sole
+    /// initial thread.
+    pub tgid: usize,
+    /// Set once `thread_exit` has run, for a joiner to read; `None`
+    /// means the thread hasn't exited yet.
+    pub join_result: Option<isize>,
+    /// Signalled by `sys_thread_exit` once `join_result` is set. Nothing
+    /// adds a waiter to it: `sys_thread_join` polls `join_result` and
+    /// yields between checks.
+    pub joiners: WaitQueue,
+}
+
+impl TaskControlBlockInner {
+    pub fn is_zombie(&self) -> bool {
+        self.exit_code.is_some()
+    }
+
+    /// How many fds are currently occupied, for checking against `max_fds`.
+    fn open_fd_count(&self) -> usize {
+        self.fd_table.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Finds the lowest-numbered free fd and installs `file` there,
+    /// mirroring the allocation behaviour POSIX requires of `open`/`dup`.
+    /// Fails once `max_fds` open fds are already in use.
+    pub fn alloc_fd(&mut self, file: Arc<dyn File>) -> Option<usize> {
+        self.alloc_fd_with_flags(file, FdFlags::empty())
+    }
+
+    /// Like [`alloc_fd`](Self::alloc_fd), but lets the caller set the new
+    /// slot's [`FdFlags`] (`dup3`'s `O_CLOEXEC`, namely) atomically with
+    /// allocating it.
+    pub fn alloc_fd_with_flags(&mut self, file: Arc<dyn File>, flags: FdFlags) -> Option<usize> {
+        if self.open_fd_count() >= self.max_fds {
+            return None;
+        }
+        let entry = Some(FdEntry { file, flags });
+        if let Some(fd) = self.fd_table.iter().position(|slot| slot.is_none()) {
+            self.fd_table[fd] = entry;
+            Some(fd)
+        } else {
+            self.fd_table.push(entry);
+            Some(self.fd_table.len() - 1)
+        }
+    }
+
+    /// `dup2`/`dup3`: makes `newfd` refer to the same open file as
+    /// `oldfd`, closing whatever `newfd` previously held. Returns `None`
+    /// if `oldfd` isn't open. `newfd == oldfd` is a no-op that keeps
+    /// `oldfd`'s existing flags, matching `dup2`'s POSIX-mandated special
+    /// case.
+    pub fn dup_fd(&mut self, oldfd: usize, newfd: usize, flags: FdFlags) -> Option<usize> {
+        let file = Arc::clone(&self.fd_table.get(oldfd)?.as_ref()?.file);
+        if newfd == oldfd {
+            return Some(newfd);
+        }
+        if newfd >= self.fd_table.len() {
+            self.fd_table.resize_with(newfd + 1, || None);
+        }
+        self.fd_table[newfd] = Some(FdEntry { file, flags });
+        Some(newfd)
+    }
+
+    /// Closes every fd marked `FD_CLOEXEC`, as `execve` must before
+    /// handing control to the new image.
+    pub fn close_cloexec_fds(&mut self) {
+        for slot in self.fd_table.iter_mut() {
+            let cloexec = matches!(slot, Some(entry) if entry.flags.contains(FdFlags::CLOEXEC));
+            if cloexec {
+                *slot = None;
+            }
+        }
+    }
+}
+
+/// Next pid to hand out, mirroring [`super::kthread`]'s
+/// `NEXT_KTHREAD_ID`: a plain monotonic counter rather than a reusable
+/// bitmap, since nothing in this tree reaps and recycles pids yet either.
+static NEXT_PID: AtomicUsize = AtomicUsize::new(1);
+
+impl TaskControlBlock {
+    pub fn inner_exclusive_access(&self) -> core::cell::RefMut<'_, TaskControlBlockInner> {
+        self.inner.exclusive_access()
+    }
+
+    /// Creates a brand-new, schedulable task whose kernel-mode execution
+    /// starts at `entry(arg)` on a fresh kernel stack, with no parent and
+    /// an empty address space. Callers (`rust_main`'s init task,
+    /// `sys_spawn`'s child) are responsible for pushing the result onto
+    /// [`super::ready_queue::ready_queue`] once it's ready to run.
+    ///
+    /// The kernel stack is a plain heap allocation, leaked for the
+    /// task's lifetime, rather than carved out of
+    /// [`super::stack_layout::kernel_stack_position`]'s guard-paged
+    /// layout: that layout assumes a page table to map the slot into
+    /// (see its own doc comment), which this tree still doesn't have.
+    pub fn new(entry: fn(usize) -> !, arg: usize) -> Arc<Self> {
+        let pid = NEXT_PID.fetch_add(1, Ordering::Relaxed);
+        let stack: &'static mut [u8] =
+            Box::leak(alloc::vec![0u8; crate::config::KERNEL_STACK_SIZE].into_boxed_slice());
+        let stack_top = stack.as_ptr() as usize + stack.len();
+        let tcx = TaskContext::goto_entry(entry as usize, arg, stack_top);
+        Arc::new(Self {
+            pid,
+            tcx: unsafe { UPSafeCell::new(tcx) },
+            inner: unsafe {
+                UPSafeCell::new(TaskControlBlockInner {
+                    signals: SignalState::default(),
+                    fd_table: Vec::new(),
+                    max_fds: DEFAULT_MAX_FDS,
+                    env: Environment::default(),
+                    argv: Vec::new(),
+                    mm: MemorySet::new(),
+                    lazy_areas: Vec::new(),
+                    trap_stats: TrapStats::default(),
+                    cpu_time: CpuTime::default(),
+                    stride: StrideEntry::new(1, crate::timer::get_time()),
+                    sigactions: SigActionTable::default(),
+                    pgid: pid,
+                    sid: pid,
+                    parent: None,
+                    children: Vec::new(),
+                    exit_code: None,
+                    tls_base: 0,
+                    tgid: pid,
+                    join_result: None,
+                    joiners: WaitQueue::new(),
+                })
+            },
+        })
+    }
+}
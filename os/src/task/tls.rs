@@ -0,0 +1,43 @@
+//! Thread-local storage: parsing a `PT_TLS` segment and laying out the
+//! per-thread TLS block it initializes.
+//!
+//! `tp` is already claimed for [`hart_id`](super::hart_id) while
+//! executing in kernel mode, but that's fine for the RISC-V TLS ABI:
+//! `tp` only needs to hold the thread pointer while running *user* code,
+//! and trap entry/exit already preserves the user register file across
+//! the switch to kernel mode, so the kernel's own use of `tp` and a
+//! thread's TLS pointer never need to coexist.
+
+pub const PT_TLS: u32 = 7;
+
+/// The `PT_TLS` program header fields this loader needs: where the
+/// initialization image lives in the file, how big the file image and
+/// the full (zero-extended) memory image are, and the required alignment
+/// of the per-thread block.
+#[derive(Copy, Clone)]
+pub struct TlsImage {
+    pub file_offset: usize,
+    pub file_size: usize,
+    pub mem_size: usize,
+    pub align: usize,
+}
+
+impl TlsImage {
+    /// Size of the per-thread block, rounded up to `align` so consecutive
+    /// threads' blocks don't straddle a required alignment boundary.
+    pub fn block_size(&self) -> usize {
+        let align = self.align.max(1);
+        (self.mem_size + align - 1) / align * align
+    }
+}
+
+/// Fills a freshly-allocated per-thread TLS block: copies the
+/// initialized portion from the file image, then zeroes the remainder
+/// (the `.tbss` tail, which occupies memory but not file space).
+pub fn init_tls_block(dest: &mut [u8], image: &TlsImage, file_image: &[u8]) {
+    let copy_len = image.file_size.min(dest.len());
+    dest[..copy_len].copy_from_slice(&file_image[..copy_len]);
+    for byte in &mut dest[copy_len..] {
+        *byte = 0;
+    }
+}
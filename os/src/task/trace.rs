@@ -0,0 +1,151 @@
+//! Per-hart syscall latency tracing: entry/exit timestamps for each
+//! dispatched syscall, recorded into a fixed-size ring buffer per hart so
+//! tracing one hart never contends with another hart's ring.
+//!
+//! Gated behind a runtime toggle rather than `config::TRACING_ENABLED`
+//! directly, since unlike the optional subsystems `config` reports at
+//! compile time, tracing is meant to be flipped on and off
+//! at runtime by whoever is chasing a latency problem (`sys_trace_ctl`);
+//! `TRACING_ENABLED` instead gates whether tracing support is compiled in
+//! at all, the same way `KASAN_ENABLED` gates the frame/slab poisoning
+//! `mm::kasan` does.
+//!
+//! [`crate::syscall::syscall`] is real, already-running dispatch code,
+//! so this is wired into it directly; trap entry/exit in `trap::entry`
+//! records nothing here.
+
+use crate::config::MAX_HARTS;
+use crate::sync::UPSafeCell;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// One syscall's recorded entry/exit, in kernel-uptime nanoseconds (see
+/// [`crate::timer::get_time_ns`]).
+#[derive(Copy, Clone, Default)]
+pub struct TraceEvent {
+    pub syscall_id: usize,
+    pub entry_ns: u64,
+    pub exit_ns: u64,
+}
+
+/// How many events a hart's ring buffer holds before it either
+/// overwrites the oldest entry or starts dropping new ones, depending on
+/// [`OVERWRITE`].
+const RING_CAPACITY: usize = 256;
+
+struct Ring {
+    events: [TraceEvent; RING_CAPACITY],
+    /// Index of the oldest recorded event.
+    head: usize,
+    len: usize,
+}
+
+impl Ring {
+    const fn new() -> Self {
+        Self {
+            events: [TraceEvent {
+                syscall_id: 0,
+                entry_ns: 0,
+                exit_ns: 0,
+            }; RING_CAPACITY],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Appends `event`, overwriting the oldest entry if full and
+    /// `overwrite` is set; drops `event` (returning `false`) if full and
+    /// `overwrite` isn't.
+    fn push(&mut self, event: TraceEvent, overwrite: bool) -> bool {
+        if self.len == RING_CAPACITY {
+            if !overwrite {
+                return false;
+            }
+            self.events[self.head] = event;
+            self.head = (self.head + 1) % RING_CAPACITY;
+            return true;
+        }
+        let slot = (self.head + self.len) % RING_CAPACITY;
+        self.events[slot] = event;
+        self.len += 1;
+        true
+    }
+
+    /// Removes and returns every recorded event, oldest first.
+    fn drain(&mut self) -> Vec<TraceEvent> {
+        let out = self.snapshot();
+        self.head = 0;
+        self.len = 0;
+        out
+    }
+
+    /// Copies out every recorded event, oldest first, without clearing
+    /// the ring — for a read-only view like `/proc/trace` that shouldn't
+    /// make a second concurrent reader see an empty buffer.
+    fn snapshot(&self) -> Vec<TraceEvent> {
+        (0..self.len)
+            .map(|i| self.events[(self.head + i) % RING_CAPACITY])
+            .collect()
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static OVERWRITE: AtomicBool = AtomicBool::new(true);
+
+static RINGS: [UPSafeCell<Ring>; MAX_HARTS] = {
+    [const { unsafe { UPSafeCell::new(Ring::new()) } }; MAX_HARTS]
+};
+
+/// Turns tracing on, clearing every hart's ring first so a prior session's
+/// events don't bleed into this one. `overwrite` controls whether a full
+/// ring drops new events or overwrites the oldest.
+pub fn enable(overwrite: bool) {
+    if !crate::config::TRACING_ENABLED {
+        return;
+    }
+    for ring in RINGS.iter() {
+        ring.exclusive_access().drain();
+    }
+    OVERWRITE.store(overwrite, Ordering::Relaxed);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    crate::config::TRACING_ENABLED && ENABLED.load(Ordering::Relaxed)
+}
+
+/// Records one completed syscall on the current hart's ring. A no-op
+/// (cheap: one atomic load) when tracing is disabled.
+pub fn record(event: TraceEvent) {
+    if !is_enabled() {
+        return;
+    }
+    let overwrite = OVERWRITE.load(Ordering::Relaxed);
+    RINGS[super::hart_id()]
+        .exclusive_access()
+        .push(event, overwrite);
+}
+
+/// Drains every hart's ring, in hart order, for `sys_trace_read` to hand
+/// back to whoever asked for the events (they won't be seen again).
+pub fn drain_all() -> Vec<TraceEvent> {
+    RINGS.iter().flat_map(|ring| ring.exclusive_access().drain()).collect()
+}
+
+/// Copies out every hart's ring, in hart order, without draining it —
+/// for `/proc/trace`, which (like every other procfs file) renders a
+/// live view rather than consuming what it shows.
+pub fn snapshot_all() -> Vec<TraceEvent> {
+    RINGS.iter().flat_map(|ring| ring.exclusive_access().snapshot()).collect()
+}
+
+/// Copies out `hart`'s ring alone, oldest first, without draining it — the
+/// closest thing this kernel has to a per-hart call history, used by the
+/// panic handler's SMP freeze dump in lieu of a real stack backtrace.
+pub fn snapshot_hart(hart: usize) -> Vec<TraceEvent> {
+    RINGS[hart].exclusive_access().snapshot()
+}
@@ -0,0 +1,147 @@
+//! Static tracepoints: fixed instrumentation points compiled into
+//! scheduler switch, trap entry, and syscall dispatch, each independently
+//! toggled at runtime (via `sys_debug_ctl`) between doing nothing,
+//! bumping a counter, or logging an event into the syscall trace ring
+//! ([`super::trace`]) — kprobes-lite, for inspecting behavior without
+//! recompiling with `println!`.
+//!
+//! Two of the three sites are real, already-running code:
+//! [`super::Processor::set_current`]/[`take_current`](super::Processor::take_current)
+//! (scheduler switch) and [`crate::syscall::syscall`] (dispatch). The
+//! third, trap entry, is not fired: `trap::entry`'s trap handler does not
+//! call [`crate::tracepoint!`], so [`Tracepoint::TrapEntry`] can be
+//! configured like the others but never fires.
+
+use super::trace::{record, TraceEvent};
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// One static instrumentation site. Also how `sys_debug_ctl` addresses a
+/// site to configure.
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[repr(usize)]
+pub enum Tracepoint {
+    SchedSwitch = 0,
+    TrapEntry = 1,
+    SyscallDispatch = 2,
+}
+
+const NUM_TRACEPOINTS: usize = 3;
+
+impl Tracepoint {
+    fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Self::SchedSwitch),
+            1 => Some(Self::TrapEntry),
+            2 => Some(Self::SyscallDispatch),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SchedSwitch => "sched_switch",
+            Self::TrapEntry => "trap_entry",
+            Self::SyscallDispatch => "syscall_dispatch",
+        }
+    }
+
+    /// All tracepoints, in discriminant order — for a dump tool
+    /// (`/proc/tracepoints`) that wants every site's current state.
+    pub fn all() -> [Self; NUM_TRACEPOINTS] {
+        [Self::SchedSwitch, Self::TrapEntry, Self::SyscallDispatch]
+    }
+}
+
+/// What a tracepoint does when it fires.
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum TracepointAction {
+    Off = 0,
+    Count = 1,
+    Log = 2,
+}
+
+impl TracepointAction {
+    fn from_bits(bits: u32) -> Option<Self> {
+        match bits {
+            0 => Some(Self::Off),
+            1 => Some(Self::Count),
+            2 => Some(Self::Log),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Count => "count",
+            Self::Log => "log",
+        }
+    }
+}
+
+static ACTIONS: [AtomicU32; NUM_TRACEPOINTS] = [
+    AtomicU32::new(TracepointAction::Off as u32),
+    AtomicU32::new(TracepointAction::Off as u32),
+    AtomicU32::new(TracepointAction::Off as u32),
+];
+
+static COUNTERS: [AtomicU64; NUM_TRACEPOINTS] = [
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+];
+
+/// Sets what `point` does when it next fires.
+pub fn configure(point: Tracepoint, action: TracepointAction) {
+    ACTIONS[point as usize].store(action as u32, Ordering::Relaxed);
+}
+
+/// Sets what the tracepoint at `index` (a [`Tracepoint`] discriminant)
+/// does, for `sys_debug_ctl`'s raw `usize` arguments. `None` if either
+/// `index` or `action_bits` is out of range.
+pub fn configure_by_index(index: usize, action_bits: u32) -> Option<()> {
+    let point = Tracepoint::from_index(index)?;
+    let action = TracepointAction::from_bits(action_bits)?;
+    configure(point, action);
+    Some(())
+}
+
+/// How many times `point` has fired while configured to
+/// [`TracepointAction::Count`].
+pub fn counter(point: Tracepoint) -> u64 {
+    COUNTERS[point as usize].load(Ordering::Relaxed)
+}
+
+/// `point`'s currently configured action.
+pub fn action(point: Tracepoint) -> TracepointAction {
+    let bits = ACTIONS[point as usize].load(Ordering::Relaxed);
+    TracepointAction::from_bits(bits).unwrap_or(TracepointAction::Off)
+}
+
+/// Fires `point`: a no-op if it's off, otherwise bumps its counter or
+/// logs `tag` into the trace ring as an instantaneous event (`entry_ns ==
+/// exit_ns`), depending on its configured action.
+pub fn fire(point: Tracepoint, tag: usize) {
+    let action = ACTIONS[point as usize].load(Ordering::Relaxed);
+    if action == TracepointAction::Count as u32 {
+        COUNTERS[point as usize].fetch_add(1, Ordering::Relaxed);
+    } else if action == TracepointAction::Log as u32 {
+        let now = crate::timer::get_time_ns();
+        record(TraceEvent {
+            syscall_id: tag,
+            entry_ns: now,
+            exit_ns: now,
+        });
+    }
+}
+
+/// Fires `point` with `tag`, the way every instrumentation site in this
+/// tree should call into tracepoints rather than reaching into
+/// [`fire`] directly.
+#[macro_export]
+macro_rules! tracepoint {
+    ($point:expr, $tag:expr) => {
+        $crate::task::tracepoint_fire($point, $tag)
+    };
+}
@@ -0,0 +1,32 @@
+//! Per-process trap counters, surfaced for diagnostics when a process
+//! takes a SIGSEGV: how many page faults, syscalls and other traps it has
+//! taken makes "why did this just die" much faster to answer than a bare
+//! fault address.
+
+#[derive(Default, Copy, Clone)]
+pub struct TrapStats {
+    pub syscalls: u64,
+    pub page_faults: u64,
+    pub timer_interrupts: u64,
+    pub sigsegv_count: u64,
+    pub last_sigsegv_addr: usize,
+}
+
+impl TrapStats {
+    pub fn record_syscall(&mut self) {
+        self.syscalls += 1;
+    }
+
+    pub fn record_page_fault(&mut self) {
+        self.page_faults += 1;
+    }
+
+    pub fn record_timer_interrupt(&mut self) {
+        self.timer_interrupts += 1;
+    }
+
+    pub fn record_sigsegv(&mut self, fault_addr: usize) {
+        self.sigsegv_count += 1;
+        self.last_sigsegv_addr = fault_addr;
+    }
+}
@@ -0,0 +1,46 @@
+//! Time sources: reads the `time` CSR (backed by the platform's `mtime`)
+//! and converts cycle counts to wall-clock units.
+
+/// `mtime` ticks per second on QEMU `virt`.
+pub const CLOCK_FREQ: u64 = 12_500_000;
+pub const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+pub fn get_time() -> u64 {
+    let time: u64;
+    unsafe {
+        core::arch::asm!("rdtime {}", out(reg) time);
+    }
+    time
+}
+
+pub fn get_time_ns() -> u64 {
+    // `NANOS_PER_SEC` is an exact multiple of `CLOCK_FREQ` (80 ns per
+    // tick), so dividing the constants first loses no precision, and the
+    // product doesn't overflow `u64` for any uptime this kernel will
+    // realistically run.
+    get_time() * (NANOS_PER_SEC / CLOCK_FREQ)
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct TimeSpec {
+    pub sec: u64,
+    pub nsec: u64,
+}
+
+impl TimeSpec {
+    pub fn now() -> Self {
+        let ns = get_time_ns();
+        Self {
+            sec: ns / NANOS_PER_SEC,
+            nsec: ns % NANOS_PER_SEC,
+        }
+    }
+}
+
+/// `clock_gettime` clock ids this kernel understands. `CLOCK_REALTIME` and
+/// `CLOCK_MONOTONIC` read the same underlying counter for now, since there
+/// is no wall-clock RTC wired up yet; they're kept distinct so userspace
+/// code written against the POSIX clock ids still compiles and behaves
+/// monotonically either way.
+pub const CLOCK_REALTIME: usize = 0;
+pub const CLOCK_MONOTONIC: usize = 1;
@@ -0,0 +1,71 @@
+//! A single-level timer wheel, an alternative to the flat "scan every
+//! sleeping task on each tick" approach, which is O(n) in the sleeper
+//! count every tick regardless of how many are actually due. There is no
+//! `sys_sleep`, and nothing creates a wheel: `task::idle_tickless` is the
+//! only function that takes one, and nothing calls it.
+//!
+//! Timers are bucketed into [`WHEEL_SLOTS`] slots by the tick at which they
+//! expire, modulo the wheel size; advancing the wheel by one tick only
+//! touches the one slot whose timers might be due.
+
+use alloc::vec::Vec;
+
+pub const WHEEL_SLOTS: usize = 256;
+
+pub struct TimerWheel {
+    slots: Vec<Vec<(usize, u64)>>,
+    current_tick: u64,
+}
+
+impl TimerWheel {
+    pub fn new() -> Self {
+        Self {
+            slots: (0..WHEEL_SLOTS).map(|_| Vec::new()).collect(),
+            current_tick: 0,
+        }
+    }
+
+    /// Schedules `task_id` to wake at `deadline_tick`.
+    pub fn schedule(&mut self, task_id: usize, deadline_tick: u64) {
+        let slot = (deadline_tick as usize) % WHEEL_SLOTS;
+        self.slots[slot].push((task_id, deadline_tick));
+    }
+
+    /// Advances the wheel by one tick, returning the task ids whose
+    /// deadline is now due. Entries in the current slot that are not
+    /// actually due yet (they wrapped around from a future lap) are kept.
+    pub fn advance(&mut self) -> Vec<usize> {
+        self.current_tick += 1;
+        let slot = (self.current_tick as usize) % WHEEL_SLOTS;
+        let due_tick = self.current_tick;
+        let bucket = &mut self.slots[slot];
+        let mut due = Vec::new();
+        bucket.retain(|&(task_id, deadline)| {
+            if deadline <= due_tick {
+                due.push(task_id);
+                false
+            } else {
+                true
+            }
+        });
+        due
+    }
+
+    pub fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    /// The earliest tick any scheduled timer is due, across every slot —
+    /// for tickless idle to arm the next hardware timer interrupt at
+    /// exactly that tick instead of waking every tick to check for
+    /// nothing. `None` if nothing is scheduled.
+    pub fn next_deadline_tick(&self) -> Option<u64> {
+        self.slots.iter().flatten().map(|&(_, deadline)| deadline).min()
+    }
+}
+
+impl Default for TimerWheel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,93 @@
+//! Softirqs and a workqueue: deferred work an interrupt handler wants
+//! done, but not on the interrupt stack with interrupts disabled. The
+//! top half (the actual trap handler) only raises a vector or queues a
+//! closure; running it happens later, once it's safe to block or take
+//! locks that would deadlock against an interrupt.
+
+use crate::sync::UPSafeCell;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SoftirqVector {
+    Timer = 0,
+    Block = 1,
+    Net = 2,
+    Tasklet = 3,
+}
+
+pub const NUM_SOFTIRQ_VECTORS: usize = 4;
+
+static PENDING: AtomicU32 = AtomicU32::new(0);
+static HANDLERS: UPSafeCell<[Option<fn()>; NUM_SOFTIRQ_VECTORS]> =
+    unsafe { UPSafeCell::new([None; NUM_SOFTIRQ_VECTORS]) };
+
+/// Registers the handler that runs when `vector` is raised. Meant to be
+/// called once per vector at boot, not from the handler itself.
+pub fn register_softirq(vector: SoftirqVector, handler: fn()) {
+    HANDLERS.exclusive_access()[vector as usize] = Some(handler);
+}
+
+/// Marks `vector` pending. Safe to call from interrupt context; does no
+/// work itself, just flips a bit for [`run_pending_softirqs`] to see.
+pub fn raise_softirq(vector: SoftirqVector) {
+    PENDING.fetch_or(1 << (vector as u32), Ordering::Release);
+}
+
+/// Runs every pending softirq handler, clearing its bit first so a
+/// handler that re-raises its own vector gets picked up on the next pass
+/// rather than being lost.
+pub fn run_pending_softirqs() {
+    loop {
+        let pending = PENDING.swap(0, Ordering::AcqRel);
+        if pending == 0 {
+            return;
+        }
+        let handlers = HANDLERS.exclusive_access();
+        for i in 0..NUM_SOFTIRQ_VECTORS {
+            if pending & (1 << i) != 0 {
+                if let Some(handler) = handlers[i] {
+                    handler();
+                }
+            }
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A FIFO of one-shot closures, for deferred work that needs to carry
+/// its own state (unlike a softirq vector, which is just a bit and a
+/// fixed handler).
+pub struct WorkQueue {
+    jobs: VecDeque<Job>,
+}
+
+impl WorkQueue {
+    const fn new() -> Self {
+        Self {
+            jobs: VecDeque::new(),
+        }
+    }
+
+    pub fn schedule(&mut self, job: Job) {
+        self.jobs.push_back(job);
+    }
+
+    pub fn run_all(&mut self) {
+        while let Some(job) = self.jobs.pop_front() {
+            job();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+}
+
+static WORKQUEUE: UPSafeCell<WorkQueue> = unsafe { UPSafeCell::new(WorkQueue::new()) };
+
+pub fn system_workqueue() -> &'static UPSafeCell<WorkQueue> {
+    &WORKQUEUE
+}
@@ -0,0 +1,156 @@
+//! Installs `__alltraps` and dispatches the traps it catches.
+//!
+//! This is a self-trap handler only: every cause handled here (timer and
+//! software interrupts, misaligned load/store, `ecall`) can occur with the
+//! kernel trapping on itself in supervisor mode, which is the only mode
+//! this tree has ever actually run code in so far — the only
+//! `TaskControlBlock` built is `rust_main`'s init task, which runs a
+//! kernel function rather than a loaded user binary (see
+//! `main::rust_main`'s comment on what boot can't do), so there is no
+//! user-mode `ecall` for this to receive in practice today. The `ecall` arm below still dispatches through
+//! [`crate::syscall::syscall`] for real, so wiring up a user task's first
+//! `ecall` is the remaining step rather than another layer of stubbing.
+
+use super::{cause, emulate_misaligned_access};
+
+core::arch::global_asm!(include_str!("entry.asm"));
+
+/// Exception cause codes (low bits of `scause` with the top "this is an
+/// interrupt" bit, tested separately, stripped).
+mod exception {
+    pub const INSTRUCTION_MISALIGNED: usize = 0;
+    pub const LOAD_MISALIGNED: usize = 4;
+    pub const STORE_MISALIGNED: usize = 6;
+    pub const ECALL_FROM_U: usize = 8;
+    pub const ECALL_FROM_S: usize = 9;
+}
+
+/// `ecall`'s instruction length; it's never compressed, so `sepc` always
+/// advances by exactly this much to skip over the instruction that
+/// trapped.
+const ECALL_LEN: usize = 4;
+
+/// The top bit of `scause` on rv64: set for interrupts, clear for
+/// synchronous exceptions.
+const INTERRUPT_BIT: usize = 1 << (usize::BITS - 1);
+
+/// Installs `__alltraps` in direct mode and unmasks the timer and
+/// supervisor-software interrupts it now has somewhere real to go.
+/// Exceptions stay on the direct path rather than the vectored table
+/// ([`super::set_vectored`]'s own doc comment explains why interrupts and
+/// exceptions are split that way); layering the vectored fast path for
+/// interrupts on top of this is follow-up work, not done here.
+pub fn init() {
+    extern "C" {
+        fn __alltraps();
+    }
+    unsafe {
+        core::arch::asm!("csrw stvec, {}", in(reg) __alltraps as usize);
+        // sie: bit 1 (SSIE) and bit 5 (STIE) — supervisor software and
+        // timer interrupts, the two this handler actually does something
+        // with. `csrsi`'s immediate is only 5 bits wide, too narrow for
+        // this mask, so this goes through a register instead.
+        core::arch::asm!("csrs sie, {}", in(reg) 0b10_0010usize);
+        // sstatus.SIE (bit 1): the global "traps as interrupts are taken
+        // at all" switch, left off until there's a handler to take them.
+        core::arch::asm!("csrsi sstatus, 0b10");
+    }
+}
+
+/// Called by `__alltraps` with a pointer to the 32-`usize` GPR frame it
+/// just saved (`regs[0]` is always `x0`, kept in the frame only so the
+/// indices line up with [`emulate_misaligned_access`]'s register file).
+#[no_mangle]
+extern "C" fn trap_handler(regs: *mut [usize; 32]) {
+    let regs = unsafe { &mut *regs };
+    let (scause, stval, sepc) = read_trap_csrs();
+
+    if scause & INTERRUPT_BIT != 0 {
+        match scause & !INTERRUPT_BIT {
+            cause::SUPERVISOR_TIMER => {
+                // Nothing yet re-arms a per-hart deadline from here (that's
+                // `task::idle::idle_tickless`'s job, not wired to a global
+                // timer wheel instance); just keep the interrupt stream
+                // alive so a hart suspended via SBI HSM actually wakes up.
+                crate::sbi::set_timer(crate::timer::get_time() + 100_000);
+            }
+            cause::SUPERVISOR_SOFTWARE => crate::lang_items::handle_panic_freeze(),
+            _ => {}
+        }
+        return;
+    }
+
+    match scause {
+        exception::LOAD_MISALIGNED | exception::STORE_MISALIGNED => {
+            handle_misaligned(sepc, stval, regs);
+        }
+        exception::ECALL_FROM_U | exception::ECALL_FROM_S => {
+            handle_ecall(sepc, regs);
+        }
+        exception::INSTRUCTION_MISALIGNED => {
+            panic!("instruction-misaligned fetch at sepc={:#x}", sepc);
+        }
+        other => {
+            panic!(
+                "unhandled trap: scause={:#x} stval={:#x} sepc={:#x}",
+                other, stval, sepc
+            );
+        }
+    }
+}
+
+fn read_trap_csrs() -> (usize, usize, usize) {
+    let scause: usize;
+    let stval: usize;
+    let sepc: usize;
+    unsafe {
+        core::arch::asm!("csrr {}, scause", out(reg) scause);
+        core::arch::asm!("csrr {}, stval", out(reg) stval);
+        core::arch::asm!("csrr {}, sepc", out(reg) sepc);
+    }
+    (scause, stval, sepc)
+}
+
+/// Decodes and emulates the misaligned access at `sepc`/`stval` via
+/// [`emulate_misaligned_access`], advancing `sepc` past it on success.
+/// Bare (no page table is ever activated in this tree — see
+/// `mm::page_table`'s own doc comment) means `stval`, the faulting
+/// address, is already a kernel-dereferenceable pointer, not a virtual
+/// address needing translation first.
+fn handle_misaligned(sepc: usize, stval: usize, regs: &mut [usize; 32]) {
+    let insn = unsafe { core::ptr::read(sepc as *const u32) };
+    let vaddr_bytes = unsafe { core::slice::from_raw_parts_mut(stval as *mut u8, 8) };
+    match emulate_misaligned_access(insn, vaddr_bytes, regs) {
+        Some(len) => write_sepc(sepc + len),
+        None => panic!(
+            "misaligned access at {:#x} (insn {:#010x}) this emulator doesn't decode",
+            stval, insn
+        ),
+    }
+}
+
+/// Dispatches `ecall`'s syscall number (`a7`/`x17`) and first six
+/// arguments (`a0`..`a5`/`x10`..`x15`) through [`crate::syscall::syscall`],
+/// writing the result back to `a0` and skipping past the `ecall`.
+fn handle_ecall(sepc: usize, regs: &mut [usize; 32]) {
+    const A0: usize = 10;
+    const A7: usize = 17;
+    let syscall_id = regs[A7];
+    let args = [
+        regs[A0],
+        regs[A0 + 1],
+        regs[A0 + 2],
+        regs[A0 + 3],
+        regs[A0 + 4],
+        regs[A0 + 5],
+    ];
+    let ret = crate::syscall::syscall(syscall_id, args);
+    regs[A0] = ret as usize;
+    write_sepc(sepc + ECALL_LEN);
+}
+
+fn write_sepc(value: usize) {
+    unsafe {
+        core::arch::asm!("csrw sepc, {}", in(reg) value);
+    }
+}
@@ -0,0 +1,81 @@
+//! Software emulation of misaligned load/store traps.
+//!
+//! This RISC-V implementation does not guarantee misaligned access support
+//! in hardware, so a misaligned load/store instead traps. Rather than
+//! killing the offending task, the trap handler decodes the faulting
+//! instruction, performs the access a byte at a time, and resumes past it.
+
+const OPCODE_MASK: u32 = 0x7f;
+const LOAD_OPCODE: u32 = 0b000_0011;
+const STORE_OPCODE: u32 = 0b010_0011;
+
+/// Decoded fields of a RISC-V load/store instruction relevant to emulating
+/// a misaligned access.
+struct LoadStoreInsn {
+    is_store: bool,
+    width: usize,
+    signed: bool,
+    rd_or_rs2: usize,
+    insn_len: usize,
+}
+
+fn decode(insn: u32) -> Option<LoadStoreInsn> {
+    let opcode = insn & OPCODE_MASK;
+    let funct3 = (insn >> 12) & 0x7;
+    let (width, signed) = match funct3 {
+        0b000 => (1, true),
+        0b001 => (2, true),
+        0b010 => (4, true),
+        0b011 => (8, true),
+        0b100 => (1, false),
+        0b101 => (2, false),
+        0b110 => (4, false),
+        _ => return None,
+    };
+    match opcode {
+        LOAD_OPCODE => Some(LoadStoreInsn {
+            is_store: false,
+            width,
+            signed,
+            rd_or_rs2: ((insn >> 7) & 0x1f) as usize,
+            insn_len: 4,
+        }),
+        STORE_OPCODE => Some(LoadStoreInsn {
+            is_store: true,
+            width,
+            signed,
+            rd_or_rs2: ((insn >> 20) & 0x1f) as usize,
+            insn_len: 4,
+        }),
+        _ => None,
+    }
+}
+
+/// Emulates one misaligned load/store given the faulting instruction word,
+/// the faulting virtual address (already translated to a kernel-readable
+/// pointer by the caller) and the register file. Returns the number of
+/// bytes the `sepc` should advance by, or `None` if the instruction isn't a
+/// load/store this emulator understands (in which case the caller should
+/// fall back to delivering SIGBUS/SIGSEGV).
+pub fn emulate(insn: u32, vaddr_bytes: &mut [u8], regs: &mut [usize; 32]) -> Option<usize> {
+    let decoded = decode(insn)?;
+    if vaddr_bytes.len() < decoded.width {
+        return None;
+    }
+    if decoded.is_store {
+        let val = regs[decoded.rd_or_rs2];
+        vaddr_bytes[..decoded.width].copy_from_slice(&val.to_le_bytes()[..decoded.width]);
+    } else {
+        let mut buf = [0u8; 8];
+        buf[..decoded.width].copy_from_slice(&vaddr_bytes[..decoded.width]);
+        let mut val = usize::from_le_bytes(buf);
+        if decoded.signed && decoded.width < 8 {
+            let shift = (8 - decoded.width) * 8;
+            val = ((val << shift) as isize >> shift) as usize;
+        }
+        if decoded.rd_or_rs2 != 0 {
+            regs[decoded.rd_or_rs2] = val;
+        }
+    }
+    Some(decoded.insn_len)
+}
@@ -0,0 +1,15 @@
+//! Trap entry/exit and dispatch to syscall, interrupt, and exception
+//! handlers.
+
+mod bottom_half;
+mod entry;
+mod misaligned;
+mod vectored;
+
+pub use bottom_half::{
+    raise_softirq, register_softirq, run_pending_softirqs, system_workqueue, SoftirqVector,
+    WorkQueue, NUM_SOFTIRQ_VECTORS,
+};
+pub use entry::init;
+pub use misaligned::emulate as emulate_misaligned_access;
+pub use vectored::{cause, current_mode, set_vectored, StvecMode};
@@ -0,0 +1,50 @@
+//! Vectored `stvec`: instead of a single handler that re-dispatches on
+//! `scause` every time, install one entry per interrupt cause so the
+//! common ones (timer, supervisor software IPI) skip the dispatch branch
+//! entirely. Direct mode is kept as the exception path, since the vectored
+//! table only applies to interrupts, not synchronous traps.
+
+/// `stvec` MODE field values.
+#[repr(usize)]
+#[derive(Copy, Clone)]
+pub enum StvecMode {
+    Direct = 0,
+    Vectored = 1,
+}
+
+/// Interrupt cause numbers (low bits of `scause` with the interrupt bit
+/// stripped), used to size/index the vectored jump table.
+pub mod cause {
+    pub const SUPERVISOR_SOFTWARE: usize = 1;
+    pub const SUPERVISOR_TIMER: usize = 5;
+    pub const SUPERVISOR_EXTERNAL: usize = 9;
+}
+
+/// Reads back the currently installed `stvec` mode, to assert against
+/// after installing the vectored table.
+#[inline]
+pub fn current_mode() -> StvecMode {
+    let stvec: usize;
+    unsafe {
+        core::arch::asm!("csrr {}, stvec", out(reg) stvec);
+    }
+    if stvec & 0b11 == 1 {
+        StvecMode::Vectored
+    } else {
+        StvecMode::Direct
+    }
+}
+
+/// Installs `base` (the address of the vectored trap table; entry `i`
+/// handles interrupt cause `i`, exceptions all fall through entry 0) with
+/// mode set to Vectored. `base` must be 4-byte aligned, per the ISA spec
+/// for vectored mode.
+///
+/// # Safety
+/// `base` must point at a valid, correctly-sized vectored trap table that
+/// stays mapped and executable for as long as traps can occur.
+pub unsafe fn set_vectored(base: usize) {
+    debug_assert_eq!(base & 0b11, 0, "vectored stvec base must be 4-byte aligned");
+    let stvec = base | (StvecMode::Vectored as usize);
+    core::arch::asm!("csrw stvec, {}", in(reg) stvec);
+}